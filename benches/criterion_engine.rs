@@ -130,6 +130,7 @@ fn bench_flat(c: &mut Criterion) {
                 subject: subject.clone(),
                 permission: permission.clone(),
                 target: scope.clone(),
+                context: Default::default(),
             }))
             .unwrap();
             black_box(decision);
@@ -149,6 +150,7 @@ fn bench_flat(c: &mut Criterion) {
             let decision = block_on(engine.can_tenant(TenantAccessRequest {
                 subject: subject.clone(),
                 permission: permission.clone(),
+                context: Default::default(),
             }))
             .unwrap();
             black_box(decision);
@@ -177,6 +179,7 @@ fn bench_hierarchy_depth(c: &mut Criterion) {
                     subject: subject.clone(),
                     permission: permission.clone(),
                     target: scope.clone(),
+                    context: Default::default(),
                 }))
                 .unwrap();
                 black_box(decision);
@@ -202,6 +205,7 @@ fn bench_role_fanout(c: &mut Criterion) {
                 let decision = block_on(engine.can_tenant(TenantAccessRequest {
                     subject: subject.clone(),
                     permission: permission.clone(),
+                    context: Default::default(),
                 }))
                 .unwrap();
                 black_box(decision);
@@ -31,6 +31,7 @@ fn main() -> rs_tenant::Result<()> {
         let request = TenantAccessRequest {
             subject: AuthSubject::new(tenant.clone(), principal),
             permission: Permission::parse("invoice:read")?,
+            context: Default::default(),
         };
 
         assert_eq!(engine.can_tenant(request).await?, AccessDecision::Allow);
@@ -61,6 +61,7 @@ fn main() -> rs_tenant::Result<()> {
                     rs_tenant::PrincipalId::parse("user_demo")?,
                 ),
                 permission: Permission::parse("invoice:read")?,
+                context: Default::default(),
             })
             .await?;
         Ok(())
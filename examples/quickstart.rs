@@ -30,6 +30,7 @@ fn main() -> rs_tenant::Result<()> {
                 subject: AuthSubject::new(tenant, principal),
                 permission,
                 target,
+                context: Default::default(),
             })
             .await?;
 
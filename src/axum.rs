@@ -11,8 +11,8 @@ use crate::engine::Engine;
 use crate::permission::Permission;
 #[cfg(feature = "platform")]
 use crate::platform::{
-    PlatformAccessRequest, PlatformAuthorizationSource, PlatformEngine, PlatformPrincipalId,
-    PlatformSubject,
+    NoPlatformCache, PlatformAccessRequest, PlatformAuthorizationSource, PlatformCache,
+    PlatformEngine, PlatformPrincipalId, PlatformSubject,
 };
 use crate::request::{AuthSubject, TenantAccessRequest};
 use crate::source::AuthorizationSource;
@@ -132,6 +132,7 @@ where
                 .can_tenant(TenantAccessRequest {
                     subject,
                     permission,
+                    context: Default::default(),
                 })
                 .await
             {
@@ -151,25 +152,26 @@ where
 /// 对平台自有资源请求执行授权的中间件层。
 #[cfg(feature = "platform")]
 #[derive(Debug, Clone)]
-pub struct PlatformAuthorizeLayer<S> {
-    engine: Arc<PlatformEngine<S>>,
+pub struct PlatformAuthorizeLayer<S, C = NoPlatformCache> {
+    engine: Arc<PlatformEngine<S, C>>,
     permission: Permission,
 }
 
 #[cfg(feature = "platform")]
-impl<S> PlatformAuthorizeLayer<S> {
+impl<S, C> PlatformAuthorizeLayer<S, C> {
     /// 创建平台授权中间件层。
-    pub fn new(engine: Arc<PlatformEngine<S>>, permission: Permission) -> Self {
+    pub fn new(engine: Arc<PlatformEngine<S, C>>, permission: Permission) -> Self {
         Self { engine, permission }
     }
 }
 
 #[cfg(feature = "platform")]
-impl<S, Inner> Layer<Inner> for PlatformAuthorizeLayer<S>
+impl<S, C, Inner> Layer<Inner> for PlatformAuthorizeLayer<S, C>
 where
     S: PlatformAuthorizationSource,
+    C: PlatformCache,
 {
-    type Service = PlatformAuthorizeService<Inner, S>;
+    type Service = PlatformAuthorizeService<Inner, S, C>;
 
     /// 将平台授权层应用到内层服务。
     fn layer(&self, inner: Inner) -> Self::Service {
@@ -184,18 +186,19 @@ where
 /// 执行平台自有资源权限检查的中间件服务。
 #[cfg(feature = "platform")]
 #[derive(Debug, Clone)]
-pub struct PlatformAuthorizeService<Inner, S> {
+pub struct PlatformAuthorizeService<Inner, S, C = NoPlatformCache> {
     inner: Inner,
-    engine: Arc<PlatformEngine<S>>,
+    engine: Arc<PlatformEngine<S, C>>,
     permission: Permission,
 }
 
 #[cfg(feature = "platform")]
-impl<Inner, S> Service<Request<Body>> for PlatformAuthorizeService<Inner, S>
+impl<Inner, S, C> Service<Request<Body>> for PlatformAuthorizeService<Inner, S, C>
 where
     Inner: Service<Request<Body>, Response = Response> + Clone + Send + 'static,
     Inner::Future: Send + 'static,
     S: PlatformAuthorizationSource + 'static,
+    C: PlatformCache + 'static,
 {
     type Response = Response;
     type Error = Inner::Error;
@@ -260,19 +263,21 @@ where
             subject,
             permission,
             target,
+            context: Default::default(),
         })
         .await
 }
 
 /// 检查平台自有资源请求。
 #[cfg(feature = "platform")]
-pub async fn can_platform<S>(
-    engine: &PlatformEngine<S>,
+pub async fn can_platform<S, C>(
+    engine: &PlatformEngine<S, C>,
     subject: PlatformSubject,
     permission: Permission,
 ) -> crate::Result<AccessDecision>
 where
     S: PlatformAuthorizationSource,
+    C: PlatformCache,
 {
     engine
         .can_platform(PlatformAccessRequest {
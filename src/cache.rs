@@ -1,16 +1,40 @@
 use crate::grant::ScopedGrant;
 use crate::ids::{PrincipalId, RoleId, TenantId};
 use crate::scope::GrantScope;
+use crate::source::{MembershipStatus, TenantStatus};
 use async_trait::async_trait;
+use std::time::Duration;
 
-/// 按租户主体和引擎配置缓存的内部有效授权。
-#[doc(hidden)]
+/// 按租户主体和引擎配置缓存的有效授权。
+///
+/// 进程外缓存可以用 [`encode_effective_grants`](crate::encode_effective_grants)
+/// 序列化，或在启用 `serde` 后直接使用 serde。
 pub type EffectiveGrant = ScopedGrant<RoleId, GrantScope>;
 
+/// 写入有效授权时附带的缓存条目元数据。
+#[derive(Debug, Clone, Default, Eq, PartialEq)]
+pub struct CacheEntryMeta {
+    /// 条目的最长存活时间，由最早生效或失效的角色分配决定。
+    pub max_ttl: Option<Duration>,
+    /// 生成该条目时涉及的全部角色，包括继承展开得到的角色。
+    ///
+    /// 为 `None` 时缓存无法判断条目依赖哪些角色，角色级失效应保守地清理它。
+    pub roles: Option<Vec<RoleId>>,
+}
+
+/// 写入租户状态或成员状态时附带的缓存条目元数据。
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq)]
+pub struct StatusEntryMeta {
+    /// 条目的最长存活时间，`None` 表示不额外限制。
+    pub max_ttl: Option<Duration>,
+}
+
 /// 有效授权的缓存接口。
 #[async_trait]
 pub trait Cache: Send + Sync {
     /// 按配置签名获取租户主体的缓存授权。
+    ///
+    /// 配置了策略版本来源时，引擎传入的签名已包含策略版本。
     async fn get_effective_grants(
         &self,
         tenant: &TenantId,
@@ -18,6 +42,35 @@ pub trait Cache: Send + Sync {
         config_signature: &str,
     ) -> Option<Vec<EffectiveGrant>>;
 
+    /// 读取缓存授权，并返回条目是否临近过期、需要提前在后台刷新。
+    ///
+    /// 默认委托给 [`Cache::get_effective_grants`]，从不要求刷新。
+    async fn get_effective_grants_with_refresh(
+        &self,
+        tenant: &TenantId,
+        principal: &PrincipalId,
+        config_signature: &str,
+    ) -> Option<(Vec<EffectiveGrant>, bool)> {
+        self.get_effective_grants(tenant, principal, config_signature)
+            .await
+            .map(|grants| (grants, false))
+    }
+
+    /// 读取缓存授权及其条目元数据，用于把条目复制到另一级缓存。
+    ///
+    /// 元数据的 `max_ttl` 为条目的剩余存活时间。默认委托给 [`Cache::get_effective_grants`]
+    /// 且不返回元数据；没有元数据时调用方无法得知条目的存活时间上限，不应复制该条目。
+    async fn get_effective_grants_with_meta(
+        &self,
+        tenant: &TenantId,
+        principal: &PrincipalId,
+        config_signature: &str,
+    ) -> Option<(Vec<EffectiveGrant>, Option<CacheEntryMeta>)> {
+        self.get_effective_grants(tenant, principal, config_signature)
+            .await
+            .map(|grants| (grants, None))
+    }
+
     /// 按配置签名写入租户主体的缓存授权。
     async fn set_effective_grants(
         &self,
@@ -27,10 +80,135 @@ pub trait Cache: Send + Sync {
         grants: Vec<EffectiveGrant>,
     );
 
+    /// 按配置签名写入租户主体的缓存授权，并附带条目元数据。
+    ///
+    /// 引擎总是通过该方法写入缓存。默认实现无法保证 `meta.max_ttl`，
+    /// 因此只在没有存活时间上限时委托给 [`Cache::set_effective_grants`]。
+    async fn set_effective_grants_with_meta(
+        &self,
+        tenant: &TenantId,
+        principal: &PrincipalId,
+        config_signature: &str,
+        grants: Vec<EffectiveGrant>,
+        meta: CacheEntryMeta,
+    ) {
+        if meta.max_ttl.is_none() {
+            self.set_effective_grants(tenant, principal, config_signature, grants)
+                .await;
+        }
+    }
+
+    /// 按策略版本获取缓存的租户状态，默认不缓存。
+    ///
+    /// `policy_version` 来自 [`PolicyVersionSource`](crate::PolicyVersionSource)，
+    /// 未配置时为空字符串；版本不一致的条目应视为未命中。
+    async fn get_tenant_status(
+        &self,
+        _tenant: &TenantId,
+        _policy_version: &str,
+    ) -> Option<TenantStatus> {
+        None
+    }
+
+    /// 按策略版本获取缓存的租户状态及其元数据。
+    ///
+    /// 元数据中的 `max_ttl` 是条目的剩余存活时间；默认实现不提供元数据。
+    async fn get_tenant_status_with_meta(
+        &self,
+        tenant: &TenantId,
+        policy_version: &str,
+    ) -> Option<(TenantStatus, Option<StatusEntryMeta>)> {
+        self.get_tenant_status(tenant, policy_version)
+            .await
+            .map(|status| (status, None))
+    }
+
+    /// 按策略版本写入租户状态，非活跃状态同样缓存。
+    async fn set_tenant_status(
+        &self,
+        _tenant: &TenantId,
+        _policy_version: &str,
+        _status: TenantStatus,
+    ) {
+    }
+
+    /// 按策略版本写入租户状态，并附带条目元数据。
+    ///
+    /// 默认实现无法保证 `meta.max_ttl`，因此只在没有存活时间上限时委托给
+    /// [`Cache::set_tenant_status`]。
+    async fn set_tenant_status_with_meta(
+        &self,
+        tenant: &TenantId,
+        policy_version: &str,
+        status: TenantStatus,
+        meta: StatusEntryMeta,
+    ) {
+        if meta.max_ttl.is_none() {
+            self.set_tenant_status(tenant, policy_version, status).await;
+        }
+    }
+
+    /// 按策略版本获取缓存的主体成员状态，默认不缓存。
+    async fn get_membership_status(
+        &self,
+        _tenant: &TenantId,
+        _principal: &PrincipalId,
+        _policy_version: &str,
+    ) -> Option<MembershipStatus> {
+        None
+    }
+
+    /// 按策略版本获取缓存的主体成员状态及其元数据，默认实现不提供元数据。
+    async fn get_membership_status_with_meta(
+        &self,
+        tenant: &TenantId,
+        principal: &PrincipalId,
+        policy_version: &str,
+    ) -> Option<(MembershipStatus, Option<StatusEntryMeta>)> {
+        self.get_membership_status(tenant, principal, policy_version)
+            .await
+            .map(|status| (status, None))
+    }
+
+    /// 按策略版本写入主体成员状态，非活跃状态同样缓存。
+    async fn set_membership_status(
+        &self,
+        _tenant: &TenantId,
+        _principal: &PrincipalId,
+        _policy_version: &str,
+        _status: MembershipStatus,
+    ) {
+    }
+
+    /// 按策略版本写入主体成员状态，并附带条目元数据。
+    ///
+    /// 默认实现只在没有存活时间上限时委托给 [`Cache::set_membership_status`]。
+    async fn set_membership_status_with_meta(
+        &self,
+        tenant: &TenantId,
+        principal: &PrincipalId,
+        policy_version: &str,
+        status: MembershipStatus,
+        meta: StatusEntryMeta,
+    ) {
+        if meta.max_ttl.is_none() {
+            self.set_membership_status(tenant, principal, policy_version, status)
+                .await;
+        }
+    }
+
+    /// 失效某个租户的缓存状态，不影响有效授权。
+    async fn invalidate_tenant_status(&self, _tenant: &TenantId) {}
+
+    /// 失效某个主体的缓存成员状态，不影响有效授权。
+    async fn invalidate_membership(&self, _tenant: &TenantId, _principal: &PrincipalId) {}
+
     /// 失效某个主体的缓存。
     async fn invalidate_principal(&self, tenant: &TenantId, principal: &PrincipalId);
 
     /// 失效某个角色相关的缓存。
+    ///
+    /// 实现可以只清理 [`CacheEntryMeta::roles`] 包含该角色的条目。
     async fn invalidate_role(&self, tenant: &TenantId, role: &RoleId);
 
     /// 失效某个租户的缓存。
@@ -0,0 +1,75 @@
+use std::fmt;
+use std::time::SystemTime;
+
+/// 授权引擎判断角色分配有效期时使用的时钟。
+pub trait Clock: fmt::Debug + Send + Sync {
+    /// 返回当前时间。
+    fn now(&self) -> SystemTime;
+}
+
+/// 读取系统时间的默认时钟。
+#[derive(Debug, Default, Clone, Copy)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    /// 返回系统当前时间。
+    fn now(&self) -> SystemTime {
+        SystemTime::now()
+    }
+}
+
+/// 返回 `now` 是否位于 `[not_before, expires_at)` 有效期内。
+pub(crate) fn window_contains(
+    not_before: Option<SystemTime>,
+    expires_at: Option<SystemTime>,
+    now: SystemTime,
+) -> bool {
+    not_before.is_none_or(|at| at <= now) && expires_at.is_none_or(|at| now < at)
+}
+
+/// 返回有效期在 `now` 之后最早一次生效或失效的时间。
+pub(crate) fn next_window_change(
+    not_before: Option<SystemTime>,
+    expires_at: Option<SystemTime>,
+    now: SystemTime,
+) -> Option<SystemTime> {
+    [not_before, expires_at]
+        .into_iter()
+        .flatten()
+        .filter(|at| *at > now)
+        .min()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{next_window_change, window_contains};
+    use std::time::{Duration, SystemTime};
+
+    #[test]
+    fn window_should_include_start_and_exclude_end() {
+        let start = SystemTime::UNIX_EPOCH + Duration::from_secs(100);
+        let end = start + Duration::from_secs(10);
+
+        assert!(!window_contains(
+            Some(start),
+            Some(end),
+            start - Duration::from_secs(1)
+        ));
+        assert!(window_contains(Some(start), Some(end), start));
+        assert!(!window_contains(Some(start), Some(end), end));
+        assert!(window_contains(None, None, end));
+    }
+
+    #[test]
+    fn next_window_change_should_skip_past_boundaries() {
+        let start = SystemTime::UNIX_EPOCH + Duration::from_secs(100);
+        let end = start + Duration::from_secs(10);
+
+        assert_eq!(
+            next_window_change(Some(start), Some(end), start - Duration::from_secs(1)),
+            Some(start)
+        );
+        assert_eq!(next_window_change(Some(start), Some(end), start), Some(end));
+        assert_eq!(next_window_change(Some(start), Some(end), end), None);
+    }
+}
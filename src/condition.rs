@@ -0,0 +1,627 @@
+use crate::error::{Error, Result};
+use crate::permission::Permission;
+use std::collections::HashMap;
+use std::fmt;
+
+/// 条件表达式的最大长度。
+const MAX_CONDITION_LEN: usize = 1024;
+/// 条件表达式的最大嵌套深度。
+const MAX_CONDITION_DEPTH: usize = 32;
+
+/// 请求上下文中的属性值。
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(untagged))]
+pub enum AttributeValue {
+    /// 布尔值。
+    Bool(bool),
+    /// 整数值。
+    Int(i64),
+    /// 字符串值。
+    Str(String),
+}
+
+impl From<bool> for AttributeValue {
+    fn from(value: bool) -> Self {
+        Self::Bool(value)
+    }
+}
+
+impl From<i64> for AttributeValue {
+    fn from(value: i64) -> Self {
+        Self::Int(value)
+    }
+}
+
+impl From<&str> for AttributeValue {
+    fn from(value: &str) -> Self {
+        Self::Str(value.to_string())
+    }
+}
+
+impl From<String> for AttributeValue {
+    fn from(value: String) -> Self {
+        Self::Str(value)
+    }
+}
+
+/// 随授权请求传入、供条件求值的属性集合。
+pub type RequestContext = HashMap<String, AttributeValue>;
+
+/// 附加在角色权限上的属性条件。
+///
+/// 支持比较（`==`、`!=`、`<`、`<=`、`>`、`>=`）、`in [..]`、`and`、`or`、`not`、括号，
+/// 以及直接引用布尔属性，例如 `amount < 10000 and region in ['cn', 'sg']`。
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Condition {
+    source: String,
+    expr: Expr,
+}
+
+impl Condition {
+    /// 解析条件表达式。
+    pub fn parse(value: impl AsRef<str>) -> Result<Self> {
+        Self::parse_with_limits(value.as_ref(), Some(MAX_CONDITION_LEN), MAX_CONDITION_DEPTH)
+    }
+
+    /// 解析 [`Condition::all`] 合并出的条件，例如从缓存条目中读回的条件。
+    ///
+    /// 合并结果由多个已校验的条件拼接而成，不限制长度；每个条件最多被多包一层括号，
+    /// 嵌套深度上限相应加一。
+    pub(crate) fn parse_combined(value: &str) -> Result<Self> {
+        Self::parse_with_limits(value, None, MAX_CONDITION_DEPTH + 1)
+    }
+
+    /// 按给定的长度和嵌套深度上限解析条件表达式。
+    fn parse_with_limits(value: &str, max_len: Option<usize>, max_depth: usize) -> Result<Self> {
+        let source = value.trim();
+        if source.is_empty() {
+            return Err(invalid("condition must not be empty"));
+        }
+        if let Some(max_len) = max_len.filter(|max_len| source.len() > *max_len) {
+            return Err(invalid(format!(
+                "condition must not exceed {max_len} bytes"
+            )));
+        }
+        let tokens = tokenize(source)?;
+        let mut parser = Parser {
+            tokens,
+            position: 0,
+            depth: 0,
+            max_depth,
+        };
+        let expr = parser.parse_or()?;
+        if parser.position != parser.tokens.len() {
+            return Err(invalid("unexpected trailing input"));
+        }
+        Ok(Self {
+            source: source.to_string(),
+            expr,
+        })
+    }
+
+    /// 返回条件表达式原文。
+    pub fn as_str(&self) -> &str {
+        &self.source
+    }
+
+    /// 按三值逻辑求值；属性缺失或类型不匹配时返回 `None`。
+    pub fn evaluate(&self, context: &RequestContext) -> Option<bool> {
+        self.expr.evaluate(context)
+    }
+
+    /// 将多个条件合并为同时满足的条件。
+    ///
+    /// 生成扁平的 `a and b and c`，只给顶层为 `or` 的条件加括号，合并后的原文用
+    /// [`Condition::parse_combined`] 解析能得到相同的表达式树。
+    pub(crate) fn all(conditions: impl IntoIterator<Item = Condition>) -> Option<Self> {
+        let mut sources = Vec::new();
+        let mut operands = Vec::new();
+        for condition in conditions {
+            match condition.expr {
+                Expr::Or(..) => {
+                    sources.push(format!("({})", condition.source));
+                    operands.push(condition.expr);
+                }
+                expr => {
+                    sources.push(condition.source);
+                    expr.push_and_operands(&mut operands);
+                }
+            }
+        }
+        let expr = operands
+            .into_iter()
+            .reduce(|left, right| Expr::And(Box::new(left), Box::new(right)))?;
+        Some(Self {
+            source: sources.join(" and "),
+            expr,
+        })
+    }
+}
+
+impl fmt::Display for Condition {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for Condition {
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Condition {
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let value = String::deserialize(deserializer)?;
+        Self::parse(value).map_err(serde::de::Error::custom)
+    }
+}
+
+/// 反序列化有效授权上可能由 [`Condition::all`] 合并出的条件。
+#[cfg(feature = "serde")]
+pub(crate) fn deserialize_combined<'de, D>(
+    deserializer: D,
+) -> std::result::Result<Option<Condition>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    use serde::Deserialize;
+
+    Option::<String>::deserialize(deserializer)?
+        .map(|value| Condition::parse_combined(&value))
+        .transpose()
+        .map_err(serde::de::Error::custom)
+}
+
+/// 绑定到角色权限上的条件。
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PermissionCondition {
+    /// 被约束的权限。
+    pub permission: Permission,
+    /// 授权生效前必须满足的条件。
+    pub condition: Condition,
+}
+
+impl PermissionCondition {
+    /// 创建权限条件。
+    pub fn new(permission: Permission, condition: Condition) -> Self {
+        Self {
+            permission,
+            condition,
+        }
+    }
+}
+
+/// 构造条件解析错误。
+fn invalid(message: impl Into<String>) -> Error {
+    Error::InvalidCondition(message.into())
+}
+
+/// 比较运算符。
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum CompareOp {
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+}
+
+/// 条件表达式语法树。
+#[derive(Clone, Debug, Eq, PartialEq)]
+enum Expr {
+    Literal(bool),
+    Attribute(String),
+    Compare {
+        attribute: String,
+        op: CompareOp,
+        value: AttributeValue,
+    },
+    In {
+        attribute: String,
+        values: Vec<AttributeValue>,
+    },
+    Not(Box<Expr>),
+    And(Box<Expr>, Box<Expr>),
+    Or(Box<Expr>, Box<Expr>),
+}
+
+impl Expr {
+    /// 按解析器的左结合方式展开 `and` 链的左侧，依次追加操作数。
+    fn push_and_operands(self, operands: &mut Vec<Expr>) {
+        match self {
+            Self::And(left, right) => {
+                left.push_and_operands(operands);
+                operands.push(*right);
+            }
+            expr => operands.push(expr),
+        }
+    }
+
+    /// 按三值逻辑求值。
+    fn evaluate(&self, context: &RequestContext) -> Option<bool> {
+        match self {
+            Self::Literal(value) => Some(*value),
+            Self::Attribute(name) => match context.get(name)? {
+                AttributeValue::Bool(value) => Some(*value),
+                _ => None,
+            },
+            Self::Compare {
+                attribute,
+                op,
+                value,
+            } => compare(context.get(attribute)?, *op, value),
+            Self::In { attribute, values } => {
+                let actual = context.get(attribute)?;
+                let mut comparable = false;
+                for value in values {
+                    if std::mem::discriminant(actual) == std::mem::discriminant(value) {
+                        if actual == value {
+                            return Some(true);
+                        }
+                        comparable = true;
+                    }
+                }
+                comparable.then_some(false)
+            }
+            Self::Not(inner) => inner.evaluate(context).map(|value| !value),
+            Self::And(left, right) => match (left.evaluate(context), right.evaluate(context)) {
+                (Some(false), _) | (_, Some(false)) => Some(false),
+                (Some(true), Some(true)) => Some(true),
+                _ => None,
+            },
+            Self::Or(left, right) => match (left.evaluate(context), right.evaluate(context)) {
+                (Some(true), _) | (_, Some(true)) => Some(true),
+                (Some(false), Some(false)) => Some(false),
+                _ => None,
+            },
+        }
+    }
+}
+
+/// 比较同类型属性值，类型不匹配时返回 `None`。
+fn compare(actual: &AttributeValue, op: CompareOp, expected: &AttributeValue) -> Option<bool> {
+    let ordering = match (actual, expected) {
+        (AttributeValue::Int(left), AttributeValue::Int(right)) => left.cmp(right),
+        (AttributeValue::Str(left), AttributeValue::Str(right)) => left.cmp(right),
+        (AttributeValue::Bool(left), AttributeValue::Bool(right)) => match op {
+            CompareOp::Eq => return Some(left == right),
+            CompareOp::Ne => return Some(left != right),
+            _ => return None,
+        },
+        _ => return None,
+    };
+    Some(match op {
+        CompareOp::Eq => ordering.is_eq(),
+        CompareOp::Ne => ordering.is_ne(),
+        CompareOp::Lt => ordering.is_lt(),
+        CompareOp::Le => ordering.is_le(),
+        CompareOp::Gt => ordering.is_gt(),
+        CompareOp::Ge => ordering.is_ge(),
+    })
+}
+
+/// 词法单元。
+#[derive(Clone, Debug, Eq, PartialEq)]
+enum Token {
+    Ident(String),
+    Value(AttributeValue),
+    Op(CompareOp),
+    And,
+    Or,
+    Not,
+    In,
+    LParen,
+    RParen,
+    LBracket,
+    RBracket,
+    Comma,
+}
+
+/// 将条件表达式切分为词法单元。
+fn tokenize(source: &str) -> Result<Vec<Token>> {
+    let mut tokens = Vec::new();
+    let mut chars = source.char_indices().peekable();
+    while let Some(&(start, ch)) = chars.peek() {
+        match ch {
+            c if c.is_whitespace() => {
+                chars.next();
+            }
+            '(' | ')' | '[' | ']' | ',' => {
+                chars.next();
+                tokens.push(match ch {
+                    '(' => Token::LParen,
+                    ')' => Token::RParen,
+                    '[' => Token::LBracket,
+                    ']' => Token::RBracket,
+                    _ => Token::Comma,
+                });
+            }
+            '=' | '!' | '<' | '>' => {
+                chars.next();
+                let followed_by_eq = chars.next_if(|&(_, next)| next == '=').is_some();
+                let op = match (ch, followed_by_eq) {
+                    ('=', true) => CompareOp::Eq,
+                    ('!', true) => CompareOp::Ne,
+                    ('<', false) => CompareOp::Lt,
+                    ('<', true) => CompareOp::Le,
+                    ('>', false) => CompareOp::Gt,
+                    ('>', true) => CompareOp::Ge,
+                    _ => return Err(invalid(format!("unexpected '{ch}' at byte {start}"))),
+                };
+                tokens.push(Token::Op(op));
+            }
+            '\'' | '"' => {
+                chars.next();
+                let mut value = String::new();
+                loop {
+                    match chars.next() {
+                        Some((_, c)) if c == ch => break,
+                        Some((_, '\\')) => match chars.next() {
+                            Some((_, escaped)) => value.push(escaped),
+                            None => return Err(invalid("unterminated string literal")),
+                        },
+                        Some((_, c)) => value.push(c),
+                        None => return Err(invalid("unterminated string literal")),
+                    }
+                }
+                tokens.push(Token::Value(AttributeValue::Str(value)));
+            }
+            c if c == '-' || c.is_ascii_digit() => {
+                chars.next();
+                let mut end = start + c.len_utf8();
+                while let Some((index, _)) = chars.next_if(|(_, next)| next.is_ascii_digit()) {
+                    end = index + 1;
+                }
+                let literal = &source[start..end];
+                let value = literal
+                    .parse::<i64>()
+                    .map_err(|_| invalid(format!("invalid integer literal '{literal}'")))?;
+                tokens.push(Token::Value(AttributeValue::Int(value)));
+            }
+            c if c.is_ascii_alphabetic() || c == '_' => {
+                let mut end = start;
+                while let Some((index, next)) = chars
+                    .next_if(|(_, next)| next.is_ascii_alphanumeric() || matches!(next, '_' | '.'))
+                {
+                    end = index + next.len_utf8();
+                }
+                tokens.push(match &source[start..end] {
+                    "and" => Token::And,
+                    "or" => Token::Or,
+                    "not" => Token::Not,
+                    "in" => Token::In,
+                    "true" => Token::Value(AttributeValue::Bool(true)),
+                    "false" => Token::Value(AttributeValue::Bool(false)),
+                    ident => Token::Ident(ident.to_string()),
+                });
+            }
+            _ => return Err(invalid(format!("unexpected '{ch}' at byte {start}"))),
+        }
+    }
+    Ok(tokens)
+}
+
+/// 递归下降解析器。
+struct Parser {
+    tokens: Vec<Token>,
+    position: usize,
+    depth: usize,
+    max_depth: usize,
+}
+
+impl Parser {
+    /// 读取下一个词法单元。
+    fn next(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.position).cloned();
+        self.position += 1;
+        token
+    }
+
+    /// 如果下一个词法单元等于 `expected`，消费它。
+    fn eat(&mut self, expected: &Token) -> bool {
+        if self.tokens.get(self.position) == Some(expected) {
+            self.position += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    /// 进入一层嵌套并检查深度限制。
+    fn enter(&mut self) -> Result<()> {
+        self.depth += 1;
+        if self.depth > self.max_depth {
+            return Err(invalid(format!(
+                "condition nesting must not exceed {}",
+                self.max_depth
+            )));
+        }
+        Ok(())
+    }
+
+    fn parse_or(&mut self) -> Result<Expr> {
+        let mut expr = self.parse_and()?;
+        while self.eat(&Token::Or) {
+            expr = Expr::Or(Box::new(expr), Box::new(self.parse_and()?));
+        }
+        Ok(expr)
+    }
+
+    fn parse_and(&mut self) -> Result<Expr> {
+        let mut expr = self.parse_unary()?;
+        while self.eat(&Token::And) {
+            expr = Expr::And(Box::new(expr), Box::new(self.parse_unary()?));
+        }
+        Ok(expr)
+    }
+
+    fn parse_unary(&mut self) -> Result<Expr> {
+        if self.eat(&Token::Not) {
+            self.enter()?;
+            let expr = Expr::Not(Box::new(self.parse_unary()?));
+            self.depth -= 1;
+            return Ok(expr);
+        }
+        self.parse_primary()
+    }
+
+    fn parse_primary(&mut self) -> Result<Expr> {
+        match self.next() {
+            Some(Token::LParen) => {
+                self.enter()?;
+                let expr = self.parse_or()?;
+                self.depth -= 1;
+                if !self.eat(&Token::RParen) {
+                    return Err(invalid("expected ')'"));
+                }
+                Ok(expr)
+            }
+            Some(Token::Value(AttributeValue::Bool(value))) => Ok(Expr::Literal(value)),
+            Some(Token::Ident(attribute)) => self.parse_predicate(attribute),
+            _ => Err(invalid("expected attribute, literal or '('")),
+        }
+    }
+
+    fn parse_predicate(&mut self, attribute: String) -> Result<Expr> {
+        match self.tokens.get(self.position) {
+            Some(Token::Op(op)) => {
+                let op = *op;
+                self.position += 1;
+                match self.next() {
+                    Some(Token::Value(value)) => Ok(Expr::Compare {
+                        attribute,
+                        op,
+                        value,
+                    }),
+                    _ => Err(invalid("expected literal after comparison operator")),
+                }
+            }
+            Some(Token::In) => {
+                self.position += 1;
+                if !self.eat(&Token::LBracket) {
+                    return Err(invalid("expected '[' after 'in'"));
+                }
+                let mut values = Vec::new();
+                loop {
+                    match self.next() {
+                        Some(Token::Value(value)) => values.push(value),
+                        _ => return Err(invalid("expected literal in list")),
+                    }
+                    if self.eat(&Token::RBracket) {
+                        break;
+                    }
+                    if !self.eat(&Token::Comma) {
+                        return Err(invalid("expected ',' or ']' in list"));
+                    }
+                }
+                Ok(Expr::In { attribute, values })
+            }
+            _ => Ok(Expr::Attribute(attribute)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 构造测试请求上下文。
+    fn context(entries: &[(&str, AttributeValue)]) -> RequestContext {
+        entries
+            .iter()
+            .map(|(key, value)| (key.to_string(), value.clone()))
+            .collect()
+    }
+
+    #[test]
+    fn condition_should_evaluate_comparisons_and_lists() {
+        let condition =
+            Condition::parse("amount < 10000 and (region in ['cn', 'sg'] or not external)")
+                .expect("condition");
+        let allowed = context(&[
+            ("amount", AttributeValue::Int(9_999)),
+            ("region", "us".into()),
+            ("external", false.into()),
+        ]);
+        let too_large = context(&[
+            ("amount", AttributeValue::Int(10_000)),
+            ("region", "cn".into()),
+        ]);
+
+        assert_eq!(condition.evaluate(&allowed), Some(true));
+        assert_eq!(condition.evaluate(&too_large), Some(false));
+    }
+
+    #[test]
+    fn condition_should_return_unknown_for_missing_or_mismatched_attributes() {
+        let condition = Condition::parse("amount <= 100 or vip").expect("condition");
+
+        assert_eq!(condition.evaluate(&RequestContext::new()), None);
+        assert_eq!(
+            condition.evaluate(&context(&[("amount", "100".into())])),
+            None
+        );
+        assert_eq!(
+            condition.evaluate(&context(&[("amount", "100".into()), ("vip", true.into()),])),
+            Some(true)
+        );
+    }
+
+    #[test]
+    fn condition_should_reject_invalid_expressions() {
+        for source in [
+            "",
+            "amount <",
+            "amount = 1",
+            "region in 'cn'",
+            "(amount > 1",
+            "amount > 1 amount",
+            "name == 'open",
+        ] {
+            let err = Condition::parse(source).expect_err(source);
+            assert!(matches!(err, Error::InvalidCondition(_)), "{source}");
+        }
+
+        let nested = format!("{}true{}", "(".repeat(64), ")".repeat(64));
+        assert!(Condition::parse(nested).is_err());
+    }
+
+    #[test]
+    fn combined_conditions_should_round_trip_beyond_parse_limits() {
+        let deepest = format!(
+            "{}amount > 0 or external{}",
+            "(".repeat(MAX_CONDITION_DEPTH),
+            ")".repeat(MAX_CONDITION_DEPTH)
+        );
+        let conditions = (0..64)
+            .map(|index| format!("amount < {index} and region in ['cn', 'sg']"))
+            .chain(["external or amount > 1".to_string(), deepest])
+            .map(|source| Condition::parse(source).expect("condition"));
+
+        let combined = Condition::all(conditions).expect("combined");
+
+        assert!(combined.as_str().len() > MAX_CONDITION_LEN);
+        assert!(
+            combined
+                .as_str()
+                .starts_with("amount < 0 and region in ['cn', 'sg'] and amount < 1")
+        );
+        assert!(Condition::parse(combined.as_str()).is_err());
+        assert_eq!(
+            Condition::parse_combined(combined.as_str()).expect("reparse"),
+            combined
+        );
+    }
+}
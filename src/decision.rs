@@ -1,4 +1,7 @@
-use crate::scope::AccessScope;
+use crate::grant::ScopedGrant;
+use crate::ids::RoleId;
+use crate::permission::{Permission, PermissionMatcher};
+use crate::scope::{AccessScope, GrantScope};
 
 /// 最终的允许或拒绝决策。
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -22,6 +25,10 @@ pub enum DenyReason {
     TargetScopeRequired,
     /// 目标路径不在可访问根路径内。
     ScopeDenied,
+    /// 显式拒绝授权扣除了所需的访问范围。
+    ExplicitDeny,
+    /// 匹配的权限带有条件，但请求上下文不满足条件。
+    ConditionFailed,
 }
 
 /// 授权决策的轻量解释信息。
@@ -33,4 +40,73 @@ pub struct AccessExplanation {
     pub reason: Option<DenyReason>,
     /// 检查过程中计算出的有效访问范围。
     pub scope: AccessScope,
+    /// 逐条授权追踪，仅由 `trace_*` 系列方法填充。
+    pub trace: Option<AccessTrace>,
+}
+
+/// 委托校验拒绝的原因。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DelegationDenyReason {
+    /// 委托人没有在分配范围内持有委托权限，附带委托权限检查的拒绝原因。
+    Delegator(DenyReason),
+    /// 角色包含委托人未在分配范围内持有的权限。
+    PermissionNotHeld,
+}
+
+/// 委托校验的解释信息。
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DelegationExplanation {
+    /// 最终决策。
+    pub decision: AccessDecision,
+    /// 当决策为 [`AccessDecision::Deny`] 时的拒绝原因。
+    pub reason: Option<DelegationDenyReason>,
+    /// 角色中委托人未在分配范围内持有的权限。
+    pub missing: Vec<Permission>,
+}
+
+/// 参与授权决策的有效授权追踪。
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AccessTrace<R = RoleId, S = GrantScope> {
+    /// 匹配所需权限且范围覆盖检查目标的授权，包括允许和拒绝效果。
+    pub matched: Vec<ScopedGrant<R, S>>,
+    /// 拒绝时最接近的未匹配授权：权限匹配但范围不覆盖目标，或资源相同但动作不同。
+    pub closest: Vec<ScopedGrant<R, S>>,
+}
+
+impl<R, S> Default for AccessTrace<R, S> {
+    fn default() -> Self {
+        Self {
+            matched: Vec::new(),
+            closest: Vec::new(),
+        }
+    }
+}
+
+impl<R: Clone, S: Clone> AccessTrace<R, S> {
+    /// 从有效授权中收集匹配授权，拒绝时同时收集最接近的授权。
+    pub(crate) fn collect(
+        grants: &[ScopedGrant<R, S>],
+        required: &Permission,
+        matcher: PermissionMatcher<'_>,
+        covers: impl Fn(&ScopedGrant<R, S>) -> bool,
+        denied: bool,
+    ) -> Self {
+        let mut trace = Self::default();
+        let mut out_of_scope = Vec::new();
+        let mut same_resource = Vec::new();
+        for grant in grants {
+            if grant.matches_permission(required, matcher) {
+                if covers(grant) {
+                    trace.matched.push(grant.clone());
+                } else if denied {
+                    out_of_scope.push(grant.clone());
+                }
+            } else if denied && grant.permission.resource() == required.resource() {
+                same_resource.push(grant.clone());
+            }
+        }
+        trace.closest = out_of_scope;
+        trace.closest.extend(same_resource);
+        trace
+    }
 }
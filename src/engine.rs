@@ -1,12 +1,31 @@
-use crate::cache::{Cache, EffectiveGrant, NoCache};
-use crate::decision::{AccessDecision, AccessExplanation, DenyReason};
+use crate::cache::{Cache, CacheEntryMeta, EffectiveGrant, NoCache};
+use crate::clock::{Clock, SystemClock};
+use crate::condition::{Condition, RequestContext};
+use crate::decision::{
+    AccessDecision, AccessExplanation, AccessTrace, DelegationDenyReason, DelegationExplanation,
+    DenyReason,
+};
 use crate::error::{Error, Result};
+use crate::grant::Effect;
 use crate::ids::{PrincipalId, RoleId, TenantId};
-use crate::request::{AuthSubject, ScopeQuery, ScopedAccessRequest, TenantAccessRequest};
-use crate::role_hierarchy::{RoleHierarchy, expand_roles};
-use crate::scope::AccessScope;
+use crate::implication::ActionImplications;
+use crate::invalidation::{InvalidationEvent, InvalidationSubscription};
+use crate::permission::{Permission, PermissionMatcher};
+use crate::policy_version::PolicyVersionSource;
+use crate::refresh::RefreshQueue;
+use crate::request::{
+    AuthSubject, DelegationRequest, ScopeQuery, ScopedAccessRequest, TenantAccessRequest,
+};
+use crate::role_hierarchy::{ExpandedRole, RoleHierarchy, expand_roles};
+use crate::scope::{AccessScope, ScopePath};
+use crate::single_flight::SingleFlight;
+use crate::snapshot::{GrantedPermission, SubjectPermissions};
 use crate::source::{AuthorizationSource, MembershipStatus, TenantStatus};
 use async_trait::async_trait;
+use std::borrow::Cow;
+use std::collections::BTreeSet;
+use std::sync::Arc;
+use std::time::SystemTime;
 
 /// 引擎行为配置。
 #[derive(Debug, Clone, Eq, PartialEq)]
@@ -15,8 +34,16 @@ pub struct EngineConfig {
     pub enable_role_hierarchy: bool,
     /// 是否启用完整资源/动作通配符匹配。
     pub enable_wildcard: bool,
+    /// 是否启用 `billing/*`、`billing/**` 这类资源前缀通配符匹配。
+    pub enable_resource_prefix_wildcard: bool,
     /// 最大角色继承深度。
     pub max_role_depth: usize,
+    /// 是否求值 [`AuthorizationSource::role_permission_conditions`] 返回的权限条件。
+    ///
+    /// 关闭时不会忽略已配置的条件：有条件的允许授权会被跳过，有条件的拒绝授权按无条件生效。
+    pub enable_conditions: bool,
+    /// 动作蕴含关系，例如 `manage` 蕴含 `read`。
+    pub action_implications: ActionImplications,
 }
 
 impl Default for EngineConfig {
@@ -24,7 +51,10 @@ impl Default for EngineConfig {
         Self {
             enable_role_hierarchy: false,
             enable_wildcard: false,
+            enable_resource_prefix_wildcard: false,
             max_role_depth: 16,
+            enable_conditions: false,
+            action_implications: ActionImplications::default(),
         }
     }
 }
@@ -33,12 +63,24 @@ impl EngineConfig {
     /// 生成用于区分缓存条目的配置签名。
     fn signature(&self) -> String {
         format!(
-            "rh:{};wc:{};depth:{}",
+            "rh:{};wc:{};rp:{};depth:{};cond:{};ai:{}",
             u8::from(self.enable_role_hierarchy),
             u8::from(self.enable_wildcard),
-            self.max_role_depth
+            u8::from(self.enable_resource_prefix_wildcard),
+            self.max_role_depth,
+            u8::from(self.enable_conditions),
+            self.action_implications.signature()
         )
     }
+
+    /// 返回按当前配置匹配权限的匹配器。
+    fn matcher(&self) -> PermissionMatcher<'_> {
+        PermissionMatcher {
+            wildcard: self.enable_wildcard,
+            resource_prefix: self.enable_resource_prefix_wildcard,
+            implications: Some(&self.action_implications),
+        }
+    }
 }
 
 /// 租户 RBAC 授权引擎。
@@ -46,15 +88,22 @@ impl EngineConfig {
 pub struct Engine<S, C = NoCache> {
     source: S,
     cache: C,
+    clock: Arc<dyn Clock>,
     config: EngineConfig,
     config_signature: String,
+    policy_versions: Option<Arc<dyn PolicyVersionSource>>,
+    refresh: Option<RefreshQueue>,
+    inflight: SingleFlight<(TenantId, PrincipalId, String), Vec<EffectiveGrant>>,
 }
 
 /// [`Engine`] 构造器。
 pub struct EngineBuilder<S, C = NoCache> {
     source: S,
     cache: C,
+    clock: Arc<dyn Clock>,
     config: EngineConfig,
+    policy_versions: Option<Arc<dyn PolicyVersionSource>>,
+    refresh_ahead: bool,
 }
 
 impl<S> EngineBuilder<S, NoCache> {
@@ -63,7 +112,10 @@ impl<S> EngineBuilder<S, NoCache> {
         Self {
             source,
             cache: NoCache,
+            clock: Arc::new(SystemClock),
             config: EngineConfig::default(),
+            policy_versions: None,
+            refresh_ahead: false,
         }
     }
 }
@@ -87,29 +139,78 @@ impl<S, C> EngineBuilder<S, C> {
         self
     }
 
+    /// 启用或禁用资源前缀通配符匹配。
+    pub fn enable_resource_prefix_wildcard(mut self, on: bool) -> Self {
+        self.config.enable_resource_prefix_wildcard = on;
+        self
+    }
+
     /// 设置最大角色继承深度。
     pub fn max_role_depth(mut self, depth: usize) -> Self {
         self.config.max_role_depth = depth;
         self
     }
 
+    /// 设置动作蕴含关系。
+    pub fn action_implications(mut self, implications: ActionImplications) -> Self {
+        self.config.action_implications = implications;
+        self
+    }
+
+    /// 启用或禁用权限条件。
+    pub fn enable_conditions(mut self, on: bool) -> Self {
+        self.config.enable_conditions = on;
+        self
+    }
+
     /// 设置缓存实现。
     pub fn cache<C2: Cache>(self, cache: C2) -> EngineBuilder<S, C2> {
         EngineBuilder {
             source: self.source,
             cache,
+            clock: self.clock,
             config: self.config,
+            policy_versions: self.policy_versions,
+            refresh_ahead: self.refresh_ahead,
         }
     }
 
+    /// 设置判断角色分配有效期使用的时钟。
+    pub fn clock(mut self, clock: impl Clock + 'static) -> Self {
+        self.clock = Arc::new(clock);
+        self
+    }
+
+    /// 设置策略版本来源。
+    ///
+    /// 版本纳入缓存键，多节点部署时某个节点递增版本后，
+    /// 其他节点的有效授权和状态缓存在下一次判定时自动失效。
+    pub fn policy_versions(mut self, versions: impl PolicyVersionSource + 'static) -> Self {
+        self.policy_versions = Some(Arc::new(versions));
+        self
+    }
+
+    /// 启用或禁用提前刷新。
+    ///
+    /// 启用后，缓存提示临近过期的条目会进入队列，由 [`Engine::run_refresh_ahead`]
+    /// 在后台重新计算，请求本身继续使用缓存值。
+    pub fn refresh_ahead(mut self, on: bool) -> Self {
+        self.refresh_ahead = on;
+        self
+    }
+
     /// 构建引擎。
     pub fn build(self) -> Engine<S, C> {
         let config_signature = self.config.signature();
         Engine {
             source: self.source,
             cache: self.cache,
+            clock: self.clock,
             config: self.config,
             config_signature,
+            policy_versions: self.policy_versions,
+            refresh: self.refresh_ahead.then(RefreshQueue::default),
+            inflight: SingleFlight::new(),
         }
     }
 }
@@ -125,6 +226,8 @@ where
     }
 
     /// 计算某个权限可访问的数据范围。
+    ///
+    /// 查询不携带请求上下文：带条件的允许授权不计入范围，带条件的拒绝授权照常扣除。
     pub async fn accessible_scope(&self, query: ScopeQuery) -> Result<AccessScope> {
         let (scope, _) = self.resolve_scope(query).await?;
         Ok(scope)
@@ -145,50 +248,231 @@ where
         &self,
         request: ScopedAccessRequest,
     ) -> Result<AccessExplanation> {
-        let query = ScopeQuery {
-            subject: request.subject,
-            permission: request.permission,
-        };
-        let (scope, reason) = self.resolve_scope(query).await?;
-        let (decision, reason) = match &scope {
-            AccessScope::None => (
-                AccessDecision::Deny,
-                reason.or(Some(DenyReason::PermissionMissing)),
-            ),
-            AccessScope::Tenant { .. } => (AccessDecision::Allow, None),
-            AccessScope::Paths { .. } if scope.allows_path(&request.target) => {
-                (AccessDecision::Allow, None)
-            }
-            AccessScope::Paths { .. } => (AccessDecision::Deny, Some(DenyReason::ScopeDenied)),
-        };
-        Ok(AccessExplanation {
-            decision,
-            reason,
-            scope,
-        })
+        self.explain_request(
+            &request.subject,
+            &request.permission,
+            Some(&request.target),
+            &request.context,
+            false,
+        )
+        .await
     }
 
     /// 解释租户级访问检查结果。
     pub async fn explain_tenant(&self, request: TenantAccessRequest) -> Result<AccessExplanation> {
-        let query = ScopeQuery {
-            subject: request.subject,
-            permission: request.permission,
+        self.explain_request(
+            &request.subject,
+            &request.permission,
+            None,
+            &request.context,
+            false,
+        )
+        .await
+    }
+
+    /// 解释目标路径访问检查结果，并附带逐条授权追踪。
+    ///
+    /// 追踪列出分配角色、继承路径、匹配权限和范围，适合排障，不建议用于主链路。
+    pub async fn trace_access_scope(
+        &self,
+        request: ScopedAccessRequest,
+    ) -> Result<AccessExplanation> {
+        self.explain_request(
+            &request.subject,
+            &request.permission,
+            Some(&request.target),
+            &request.context,
+            true,
+        )
+        .await
+    }
+
+    /// 解释租户级访问检查结果，并附带逐条授权追踪。
+    pub async fn trace_tenant(&self, request: TenantAccessRequest) -> Result<AccessExplanation> {
+        self.explain_request(
+            &request.subject,
+            &request.permission,
+            None,
+            &request.context,
+            true,
+        )
+        .await
+    }
+
+    /// 批量检查同一主体的多个权限和目标路径。
+    ///
+    /// 结果顺序与输入一致，详见 [`Engine::explain_many`]。
+    pub async fn check_many(
+        &self,
+        subject: AuthSubject,
+        checks: Vec<(Permission, Option<ScopePath>)>,
+        context: &RequestContext,
+    ) -> Result<Vec<AccessDecision>> {
+        Ok(self
+            .explain_many(subject, checks, context)
+            .await?
+            .into_iter()
+            .map(|explanation| explanation.decision)
+            .collect())
+    }
+
+    /// 批量解释同一主体的多个权限和目标路径。
+    ///
+    /// 租户状态、成员状态和有效授权只解析一次。目标路径为 `Some` 时等价于
+    /// [`Engine::explain_access_scope`]，为 `None` 时等价于 [`Engine::explain_tenant`]。
+    /// 所有检查共用同一个请求上下文求值权限条件，结果与逐个携带该上下文检查一致。
+    pub async fn explain_many(
+        &self,
+        subject: AuthSubject,
+        checks: Vec<(Permission, Option<ScopePath>)>,
+        context: &RequestContext,
+    ) -> Result<Vec<AccessExplanation>> {
+        let policy_version = self.policy_version(&subject.tenant).await?;
+        if let Some(reason) = self.inactive_reason(&subject, &policy_version).await? {
+            return Ok(checks
+                .iter()
+                .map(|_| explain_tenant_level(AccessScope::None, Some(reason)))
+                .collect());
+        }
+
+        let grants = self.effective_grants(&subject, &policy_version).await?;
+        Ok(checks
+            .into_iter()
+            .map(|(permission, target)| {
+                self.explain_grants(
+                    &subject.tenant,
+                    &grants,
+                    &permission,
+                    target.as_ref(),
+                    ConditionMode::Evaluate(context),
+                    false,
+                )
+            })
+            .collect())
+    }
+
+    /// 返回主体在租户内拥有的全部权限及其访问范围。
+    ///
+    /// 复用有效授权缓存；租户或成员未激活时返回空快照。未启用通配符时，通配符授权不会出现在快照中。
+    /// 配置了动作蕴含时，被蕴含的权限也会单独列出。
+    /// 快照不求值权限条件：带条件的允许授权按满足计算并标记为 `conditional`，带条件的拒绝授权不参与扣除。
+    pub async fn subject_permissions(&self, subject: &AuthSubject) -> Result<SubjectPermissions> {
+        let mut snapshot = SubjectPermissions {
+            subject: subject.clone(),
+            permissions: Vec::new(),
         };
-        let (scope, reason) = self.resolve_scope(query).await?;
-        let (decision, reason) = match &scope {
-            AccessScope::Tenant { .. } => (AccessDecision::Allow, None),
-            AccessScope::Paths { .. } => {
-                (AccessDecision::Deny, Some(DenyReason::TargetScopeRequired))
+        let policy_version = self.policy_version(&subject.tenant).await?;
+        if self
+            .inactive_reason(subject, &policy_version)
+            .await?
+            .is_some()
+        {
+            return Ok(snapshot);
+        }
+
+        let grants = self.effective_grants(subject, &policy_version).await?;
+        let mut permissions: BTreeSet<Permission> = BTreeSet::new();
+        for grant in &grants {
+            if grant.effect == Effect::Allow
+                && grant.matches_permission(&grant.permission, self.config.matcher())
+            {
+                permissions.insert(grant.permission.clone());
+                permissions.extend(
+                    self.config
+                        .action_implications
+                        .implied_by(grant.permission.action())
+                        .map(|action| {
+                            Permission::new(grant.permission.resource().clone(), action.clone())
+                        }),
+                );
             }
-            AccessScope::None => (
-                AccessDecision::Deny,
-                reason.or(Some(DenyReason::PermissionMissing)),
-            ),
+        }
+        for permission in &permissions {
+            let (scope, _) = self.scope_from_grants(
+                &subject.tenant,
+                &grants,
+                permission,
+                ConditionMode::Optimistic,
+            );
+            if scope == AccessScope::None {
+                continue;
+            }
+            let contributing: Vec<&EffectiveGrant> = grants
+                .iter()
+                .filter(|grant| {
+                    grant.effect == Effect::Allow
+                        && grant.matches_permission(permission, self.config.matcher())
+                })
+                .collect();
+            let roles: BTreeSet<&RoleId> = contributing.iter().map(|grant| &grant.role).collect();
+            snapshot.permissions.push(GrantedPermission {
+                permission: permission.clone(),
+                scope,
+                roles: roles.into_iter().cloned().collect(),
+                conditional: contributing.iter().any(|grant| grant.condition.is_some()),
+            });
+        }
+        Ok(snapshot)
+    }
+
+    /// 检查主体能否创建拟定的角色分配。
+    pub async fn can_delegate(&self, request: DelegationRequest) -> Result<AccessDecision> {
+        Ok(self.explain_delegation(request).await?.decision)
+    }
+
+    /// 解释委托校验结果。
+    ///
+    /// 委托人必须在分配范围内持有委托权限，并在分配范围内持有角色的全部权限；
+    /// 启用角色继承时包括父角色的权限。权限条件按空请求上下文求值，带条件的允许授权不计入。
+    /// 校验只比较权限和范围，不限制分配的效果和有效期。
+    pub async fn explain_delegation(
+        &self,
+        request: DelegationRequest,
+    ) -> Result<DelegationExplanation> {
+        let subject = &request.delegator;
+        let policy_version = self.policy_version(&subject.tenant).await?;
+        if let Some(reason) = self.inactive_reason(subject, &policy_version).await? {
+            return Ok(deny_delegation(
+                DelegationDenyReason::Delegator(reason),
+                Vec::new(),
+            ));
+        }
+
+        let grants = self.effective_grants(subject, &policy_version).await?;
+        let context = RequestContext::new();
+        let held = |permission: &Permission| {
+            self.scope_from_grants(
+                &subject.tenant,
+                &grants,
+                permission,
+                ConditionMode::Evaluate(&context),
+            )
         };
-        Ok(AccessExplanation {
-            decision,
-            reason,
-            scope,
+        let (scope, reason) = held(&request.permission);
+        if !scope.covers(&request.assignment.scope) {
+            let reason = reason.unwrap_or(DenyReason::ScopeDenied);
+            return Ok(deny_delegation(
+                DelegationDenyReason::Delegator(reason),
+                Vec::new(),
+            ));
+        }
+
+        let missing: Vec<Permission> = self
+            .assignment_permissions(&subject.tenant, &request.assignment.role)
+            .await?
+            .into_iter()
+            .filter(|permission| !held(permission).0.covers(&request.assignment.scope))
+            .collect();
+        if !missing.is_empty() {
+            return Ok(deny_delegation(
+                DelegationDenyReason::PermissionNotHeld,
+                missing,
+            ));
+        }
+        Ok(DelegationExplanation {
+            decision: AccessDecision::Allow,
+            reason: None,
+            missing,
         })
     }
 
@@ -207,49 +491,336 @@ where
         self.cache.invalidate_tenant(tenant).await;
     }
 
+    /// 失效某个租户的缓存状态。
+    pub async fn invalidate_tenant_status(&self, tenant: &TenantId) {
+        self.cache.invalidate_tenant_status(tenant).await;
+    }
+
+    /// 失效某个主体的缓存成员状态。
+    pub async fn invalidate_membership(&self, tenant: &TenantId, principal: &PrincipalId) {
+        self.cache.invalidate_membership(tenant, principal).await;
+    }
+
     /// 失效所有缓存授权。
     pub async fn invalidate_all(&self) {
         self.cache.invalidate_all().await;
     }
 
+    /// 处理当前已排队的提前刷新请求，返回成功刷新的主体数量。
+    ///
+    /// 刷新失败时保留原缓存条目，等它自然过期后由请求重新计算。
+    pub async fn refresh_pending(&self) -> usize {
+        let Some(queue) = &self.refresh else {
+            return 0;
+        };
+        let mut refreshed = 0;
+        while let Some((subject, signature)) = queue.pop() {
+            refreshed += usize::from(self.refresh_grants(&subject, signature).await.is_ok());
+        }
+        refreshed
+    }
+
+    /// 持续处理提前刷新请求，未启用提前刷新时立即返回。
+    ///
+    /// 不绑定异步运行时，调用方需要自行把返回的 future 放到后台执行。
+    pub async fn run_refresh_ahead(&self) {
+        let Some(queue) = &self.refresh else {
+            return;
+        };
+        loop {
+            let (subject, signature) = queue.next().await;
+            let _ = self.refresh_grants(&subject, signature).await;
+        }
+    }
+
+    /// 按失效事件失效缓存。
+    pub async fn apply_invalidation(&self, event: &InvalidationEvent) {
+        match event {
+            InvalidationEvent::Principal { tenant, principal } => {
+                self.invalidate_principal(tenant, principal).await;
+            }
+            InvalidationEvent::Role { tenant, role } => self.invalidate_role(tenant, role).await,
+            InvalidationEvent::Tenant { tenant } => self.invalidate_tenant(tenant).await,
+            InvalidationEvent::All => self.invalidate_all().await,
+        }
+    }
+
+    /// 持续消费订阅中的失效事件并失效缓存，订阅结束后返回。
+    ///
+    /// 不绑定异步运行时，调用方需要自行把返回的 future 放到后台执行，
+    /// 例如对 `Arc<Engine>` 使用 `tokio::spawn`。
+    pub async fn follow_invalidations(&self, mut subscription: impl InvalidationSubscription) {
+        while let Some(event) = subscription.recv().await {
+            self.apply_invalidation(&event).await;
+        }
+    }
+
     /// 解析权限查询对应的最终访问范围和拒绝原因。
     async fn resolve_scope(&self, query: ScopeQuery) -> Result<(AccessScope, Option<DenyReason>)> {
-        let tenant = query.subject.tenant.clone();
-        if self.source.tenant_status(&tenant).await? != TenantStatus::Active {
-            return Ok((AccessScope::None, Some(DenyReason::TenantInactive)));
+        let policy_version = self.policy_version(&query.subject.tenant).await?;
+        if let Some(reason) = self
+            .inactive_reason(&query.subject, &policy_version)
+            .await?
+        {
+            return Ok((AccessScope::None, Some(reason)));
+        }
+
+        let grants = self
+            .effective_grants(&query.subject, &policy_version)
+            .await?;
+        let context = RequestContext::new();
+        Ok(self.scope_from_grants(
+            &query.subject.tenant,
+            &grants,
+            &query.permission,
+            ConditionMode::Evaluate(&context),
+        ))
+    }
+
+    /// 解释单个权限检查；`target` 为 `None` 时按租户级访问解释。
+    async fn explain_request(
+        &self,
+        subject: &AuthSubject,
+        permission: &Permission,
+        target: Option<&ScopePath>,
+        context: &RequestContext,
+        trace: bool,
+    ) -> Result<AccessExplanation> {
+        let policy_version = self.policy_version(&subject.tenant).await?;
+        if let Some(reason) = self.inactive_reason(subject, &policy_version).await? {
+            let mut explanation = explain_tenant_level(AccessScope::None, Some(reason));
+            explanation.trace = trace.then(AccessTrace::default);
+            return Ok(explanation);
         }
-        if self.source.membership_status(&query.subject).await? != MembershipStatus::Active {
-            return Ok((AccessScope::None, Some(DenyReason::PrincipalInactive)));
+
+        let grants = self.effective_grants(subject, &policy_version).await?;
+        Ok(self.explain_grants(
+            &subject.tenant,
+            &grants,
+            permission,
+            target,
+            ConditionMode::Evaluate(context),
+            trace,
+        ))
+    }
+
+    /// 根据已解析的有效授权解释单个权限检查。
+    fn explain_grants(
+        &self,
+        tenant: &TenantId,
+        grants: &[EffectiveGrant],
+        permission: &Permission,
+        target: Option<&ScopePath>,
+        conditions: ConditionMode<'_>,
+        trace: bool,
+    ) -> AccessExplanation {
+        let (scope, reason) = self.scope_from_grants(tenant, grants, permission, conditions);
+        let mut explanation = match target {
+            Some(target) => explain_target(scope, reason, target),
+            None => explain_tenant_level(scope, reason),
+        };
+        if trace {
+            explanation.trace = Some(AccessTrace::collect(
+                grants,
+                permission,
+                self.config.matcher(),
+                |grant| target.is_none_or(|target| grant.scope.allows_path(target)),
+                explanation.decision == AccessDecision::Deny,
+            ));
         }
+        explanation
+    }
 
-        let grants = self.effective_grants(&query.subject).await?;
-        let matched_scopes = grants
-            .into_iter()
-            .filter(|grant| {
-                grant.matches_permission(&query.permission, self.config.enable_wildcard)
-            })
-            .map(|grant| grant.scope);
-        let scope = AccessScope::merge(tenant, matched_scopes);
+    /// 读取分配角色后获得的全部权限，启用角色继承时包括父角色的权限。
+    async fn assignment_permissions(
+        &self,
+        tenant: &TenantId,
+        role: &RoleId,
+    ) -> Result<BTreeSet<Permission>> {
+        let roles = if self.config.enable_role_hierarchy {
+            let hierarchy = TenantRoleHierarchy {
+                engine: self,
+                tenant,
+            };
+            expand_roles(&hierarchy, role.clone()).await?
+        } else {
+            vec![ExpandedRole {
+                role: role.clone(),
+                via: Vec::new(),
+            }]
+        };
+        let mut permissions = BTreeSet::new();
+        for ExpandedRole { role, .. } in roles {
+            permissions.extend(self.source.role_permissions(tenant, &role).await?);
+        }
+        Ok(permissions)
+    }
+
+    /// 读取租户当前的策略版本标记，未配置版本来源时为空字符串。
+    async fn policy_version(&self, tenant: &TenantId) -> Result<String> {
+        let Some(versions) = &self.policy_versions else {
+            return Ok(String::new());
+        };
+        let global = versions.global_version().await?;
+        let tenant = versions.tenant_version(tenant).await?;
+        Ok(format!("g{global}.t{tenant}"))
+    }
+
+    /// 检查租户状态和成员状态，返回非活跃时的拒绝原因。
+    async fn inactive_reason(
+        &self,
+        subject: &AuthSubject,
+        policy_version: &str,
+    ) -> Result<Option<DenyReason>> {
+        if self.tenant_status(&subject.tenant, policy_version).await? != TenantStatus::Active {
+            return Ok(Some(DenyReason::TenantInactive));
+        }
+        if self.membership_status(subject, policy_version).await? != MembershipStatus::Active {
+            return Ok(Some(DenyReason::PrincipalInactive));
+        }
+        Ok(None)
+    }
+
+    /// 读取租户状态，优先使用缓存。
+    async fn tenant_status(&self, tenant: &TenantId, policy_version: &str) -> Result<TenantStatus> {
+        if let Some(status) = self.cache.get_tenant_status(tenant, policy_version).await {
+            return Ok(status);
+        }
+        let status = self.source.tenant_status(tenant).await?;
+        self.cache
+            .set_tenant_status(tenant, policy_version, status)
+            .await;
+        Ok(status)
+    }
+
+    /// 读取主体成员状态，优先使用缓存。
+    async fn membership_status(
+        &self,
+        subject: &AuthSubject,
+        policy_version: &str,
+    ) -> Result<MembershipStatus> {
+        if let Some(status) = self
+            .cache
+            .get_membership_status(&subject.tenant, &subject.principal, policy_version)
+            .await
+        {
+            return Ok(status);
+        }
+        let status = self.source.membership_status(subject).await?;
+        self.cache
+            .set_membership_status(&subject.tenant, &subject.principal, policy_version, status)
+            .await;
+        Ok(status)
+    }
+
+    /// 根据已解析的有效授权计算某个权限的访问范围。
+    fn scope_from_grants(
+        &self,
+        tenant: &TenantId,
+        grants: &[EffectiveGrant],
+        permission: &Permission,
+        conditions: ConditionMode<'_>,
+    ) -> (AccessScope, Option<DenyReason>) {
+        let mut allowed = Vec::new();
+        let mut denied = Vec::new();
+        let mut condition_failed = false;
+        for grant in grants
+            .iter()
+            .filter(|grant| grant.matches_permission(permission, self.config.matcher()))
+        {
+            let applies = conditions.applies(grant);
+            match grant.effect {
+                Effect::Allow if applies => allowed.push(grant.scope.clone()),
+                Effect::Allow => condition_failed = true,
+                Effect::Deny if applies => denied.push(grant.scope.clone()),
+                Effect::Deny => {}
+            }
+        }
+        let scope = AccessScope::merge(tenant.clone(), allowed);
+        if scope == AccessScope::None {
+            let reason = if condition_failed {
+                DenyReason::ConditionFailed
+            } else {
+                DenyReason::PermissionMissing
+            };
+            return (scope, Some(reason));
+        }
+        let scope = scope.subtract(denied);
         let reason = match scope {
-            AccessScope::None => Some(DenyReason::PermissionMissing),
+            AccessScope::None => Some(DenyReason::ExplicitDeny),
             _ => None,
         };
-        Ok((scope, reason))
+        (scope, reason)
     }
 
-    /// 读取或计算主体在当前引擎配置下的有效授权。
-    async fn effective_grants(&self, subject: &AuthSubject) -> Result<Vec<EffectiveGrant>> {
-        if let Some(grants) = self
+    /// 读取或计算主体在当前引擎配置下的有效授权，并发的缓存未命中只计算一次。
+    ///
+    /// 只有处于有效期内的角色分配参与计算；缓存条目的存活时间不会超过
+    /// 下一次角色分配生效或失效的时间。
+    async fn effective_grants(
+        &self,
+        subject: &AuthSubject,
+        policy_version: &str,
+    ) -> Result<Vec<EffectiveGrant>> {
+        let signature = if policy_version.is_empty() {
+            Cow::Borrowed(self.config_signature.as_str())
+        } else {
+            Cow::Owned(format!("{};pv={policy_version}", self.config_signature))
+        };
+        if let Some((grants, refresh)) = self
             .cache
-            .get_effective_grants(&subject.tenant, &subject.principal, &self.config_signature)
+            .get_effective_grants_with_refresh(&subject.tenant, &subject.principal, &signature)
             .await
         {
+            if let Some(queue) = self.refresh.as_ref().filter(|_| refresh) {
+                queue.push(subject, &signature);
+            }
             return Ok(grants);
         }
 
+        // 按租户主体和缓存签名合并并发的缓存未命中，版本变化后不会复用旧计算。
+        let key = (
+            subject.tenant.clone(),
+            subject.principal.clone(),
+            signature.to_string(),
+        );
+        self.inflight
+            .run(key, || self.load_effective_grants(subject, &signature))
+            .await
+    }
+
+    /// 跳过缓存重新计算有效授权，与同一键上的并发计算合并。
+    async fn refresh_grants(&self, subject: &AuthSubject, signature: String) -> Result<()> {
+        let key = (
+            subject.tenant.clone(),
+            subject.principal.clone(),
+            signature.clone(),
+        );
+        self.inflight
+            .run(key, || self.load_effective_grants(subject, &signature))
+            .await
+            .map(|_| ())
+    }
+
+    /// 从数据源计算有效授权，并按缓存签名写入缓存。
+    async fn load_effective_grants(
+        &self,
+        subject: &AuthSubject,
+        signature: &str,
+    ) -> Result<Vec<EffectiveGrant>> {
+        let now = self.clock.now();
         let assignments = self.source.role_assignments(subject).await?;
+        let mut next_change: Option<SystemTime> = None;
         let mut grants = Vec::new();
+        let mut involved_roles = BTreeSet::new();
         for assignment in assignments {
+            if let Some(at) = assignment.next_change_after(now) {
+                next_change = Some(next_change.map_or(at, |current| current.min(at)));
+            }
+            if !assignment.is_active_at(now) {
+                continue;
+            }
+
             let roles = if self.config.enable_role_hierarchy {
                 let hierarchy = TenantRoleHierarchy {
                     engine: self,
@@ -257,29 +828,142 @@ where
                 };
                 expand_roles(&hierarchy, assignment.role.clone()).await?
             } else {
-                vec![assignment.role]
+                vec![ExpandedRole {
+                    role: assignment.role,
+                    via: Vec::new(),
+                }]
             };
 
-            for role in roles {
+            for ExpandedRole { role, via } in roles {
+                involved_roles.insert(role.clone());
                 let permissions = self.source.role_permissions(&subject.tenant, &role).await?;
-                grants.extend(permissions.into_iter().map(|permission| {
-                    EffectiveGrant::new(role.clone(), permission, assignment.scope.clone())
+                let conditions = self
+                    .source
+                    .role_permission_conditions(&subject.tenant, &role)
+                    .await?;
+                grants.extend(permissions.into_iter().filter_map(|permission| {
+                    let condition = Condition::all(
+                        conditions
+                            .iter()
+                            .filter(|entry| entry.permission == permission)
+                            .map(|entry| entry.condition.clone()),
+                    );
+                    let grant =
+                        EffectiveGrant::new(role.clone(), permission, assignment.scope.clone())
+                            .with_effect(assignment.effect)
+                            .with_via(via.clone());
+                    match (condition, self.config.enable_conditions) {
+                        (condition, true) => Some(grant.with_condition(condition)),
+                        // 未启用条件时不能无视条件：有条件的允许授权整条跳过，
+                        // 有条件的拒绝授权按无条件生效。
+                        (Some(_), false) if assignment.effect == Effect::Allow => None,
+                        (_, false) => Some(grant),
+                    }
                 }));
             }
         }
 
+        let meta = CacheEntryMeta {
+            max_ttl: next_change.map(|at| at.duration_since(now).unwrap_or_default()),
+            roles: Some(involved_roles.into_iter().collect()),
+        };
         self.cache
-            .set_effective_grants(
+            .set_effective_grants_with_meta(
                 &subject.tenant,
                 &subject.principal,
-                &self.config_signature,
+                signature,
                 grants.clone(),
+                meta,
             )
             .await;
         Ok(grants)
     }
 }
 
+/// 根据访问范围解释目标路径访问结果。
+fn explain_target(
+    scope: AccessScope,
+    reason: Option<DenyReason>,
+    target: &ScopePath,
+) -> AccessExplanation {
+    let (decision, reason) = match &scope {
+        AccessScope::None => (
+            AccessDecision::Deny,
+            reason.or(Some(DenyReason::PermissionMissing)),
+        ),
+        _ if scope.allows_path(target) => (AccessDecision::Allow, None),
+        _ if scope.excludes_path(target) => (AccessDecision::Deny, Some(DenyReason::ExplicitDeny)),
+        _ => (AccessDecision::Deny, Some(DenyReason::ScopeDenied)),
+    };
+    AccessExplanation {
+        decision,
+        reason,
+        scope,
+        trace: None,
+    }
+}
+
+/// 构造拒绝的委托校验结果。
+fn deny_delegation(
+    reason: DelegationDenyReason,
+    missing: Vec<Permission>,
+) -> DelegationExplanation {
+    DelegationExplanation {
+        decision: AccessDecision::Deny,
+        reason: Some(reason),
+        missing,
+    }
+}
+
+/// 根据访问范围解释租户级访问结果。
+fn explain_tenant_level(scope: AccessScope, reason: Option<DenyReason>) -> AccessExplanation {
+    let (decision, reason) = match &scope {
+        AccessScope::Tenant { .. } => (AccessDecision::Allow, None),
+        AccessScope::Restricted(restricted) if restricted.is_tenant_wide() => {
+            (AccessDecision::Deny, Some(DenyReason::ExplicitDeny))
+        }
+        AccessScope::Paths { .. } | AccessScope::Restricted(_) => {
+            (AccessDecision::Deny, Some(DenyReason::TargetScopeRequired))
+        }
+        AccessScope::None => (
+            AccessDecision::Deny,
+            reason.or(Some(DenyReason::PermissionMissing)),
+        ),
+    };
+    AccessExplanation {
+        decision,
+        reason,
+        scope,
+        trace: None,
+    }
+}
+
+/// 计算访问范围时处理权限条件的方式。
+#[derive(Clone, Copy)]
+enum ConditionMode<'a> {
+    /// 使用请求上下文求值。
+    Evaluate(&'a RequestContext),
+    /// 不求值：带条件的允许授权视为满足，带条件的拒绝授权不参与扣除。
+    Optimistic,
+}
+
+impl ConditionMode<'_> {
+    /// 返回授权是否参与范围计算。
+    ///
+    /// 条件无法判定时按失败关闭处理：允许授权不生效，拒绝授权照常生效。
+    fn applies(self, grant: &EffectiveGrant) -> bool {
+        let Some(condition) = &grant.condition else {
+            return true;
+        };
+        match (self, grant.effect) {
+            (Self::Optimistic, Effect::Allow) => true,
+            (Self::Optimistic, Effect::Deny) => false,
+            (Self::Evaluate(context), Effect::Allow) => condition.evaluate(context) == Some(true),
+            (Self::Evaluate(context), Effect::Deny) => condition.evaluate(context) != Some(false),
+        }
+    }
+}
+
 struct TenantRoleHierarchy<'a, S, C> {
     engine: &'a Engine<S, C>,
     tenant: &'a TenantId,
@@ -325,8 +1009,112 @@ where
 mod tests {
     use super::*;
     use crate::memory_source::MemorySource;
-    use crate::{GrantScope, MembershipStatus, Permission, ScopePath, TenantStatus};
+    use crate::{Action, AttributeValue, GrantScope, RoleAssignment, SourceError};
     use futures::executor::block_on;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::time::Duration;
+
+    /// 固定返回同一时间点的测试时钟。
+    #[derive(Debug)]
+    struct FixedClock(SystemTime);
+
+    impl Clock for FixedClock {
+        fn now(&self) -> SystemTime {
+            self.0
+        }
+    }
+
+    /// 统计状态和角色分配读取次数的测试数据源。
+    struct CountingSource {
+        inner: MemorySource,
+        status_reads: AtomicUsize,
+        membership_reads: AtomicUsize,
+        assignment_reads: AtomicUsize,
+        yield_assignments: bool,
+    }
+
+    impl CountingSource {
+        fn new(inner: MemorySource) -> Self {
+            Self {
+                inner,
+                status_reads: AtomicUsize::new(0),
+                membership_reads: AtomicUsize::new(0),
+                assignment_reads: AtomicUsize::new(0),
+                yield_assignments: false,
+            }
+        }
+
+        /// 读取角色分配前先让出一次执行权，模拟真实的异步 IO。
+        fn yielding(mut self) -> Self {
+            self.yield_assignments = true;
+            self
+        }
+    }
+
+    /// 第一次轮询返回 `Pending` 并立即唤醒自身的 future。
+    struct YieldNow(bool);
+
+    impl std::future::Future for YieldNow {
+        type Output = ();
+
+        fn poll(
+            mut self: std::pin::Pin<&mut Self>,
+            cx: &mut std::task::Context<'_>,
+        ) -> std::task::Poll<()> {
+            if self.0 {
+                return std::task::Poll::Ready(());
+            }
+            self.0 = true;
+            cx.waker().wake_by_ref();
+            std::task::Poll::Pending
+        }
+    }
+
+    #[async_trait]
+    impl AuthorizationSource for CountingSource {
+        async fn tenant_status(
+            &self,
+            tenant: &TenantId,
+        ) -> std::result::Result<TenantStatus, SourceError> {
+            self.status_reads.fetch_add(1, Ordering::SeqCst);
+            self.inner.tenant_status(tenant).await
+        }
+
+        async fn membership_status(
+            &self,
+            subject: &AuthSubject,
+        ) -> std::result::Result<MembershipStatus, SourceError> {
+            self.membership_reads.fetch_add(1, Ordering::SeqCst);
+            self.inner.membership_status(subject).await
+        }
+
+        async fn role_assignments(
+            &self,
+            subject: &AuthSubject,
+        ) -> std::result::Result<Vec<RoleAssignment>, SourceError> {
+            self.assignment_reads.fetch_add(1, Ordering::SeqCst);
+            if self.yield_assignments {
+                YieldNow(false).await;
+            }
+            self.inner.role_assignments(subject).await
+        }
+
+        async fn role_permissions(
+            &self,
+            tenant: &TenantId,
+            role: &RoleId,
+        ) -> std::result::Result<Vec<Permission>, SourceError> {
+            self.inner.role_permissions(tenant, role).await
+        }
+
+        async fn parent_roles(
+            &self,
+            tenant: &TenantId,
+            role: &RoleId,
+        ) -> std::result::Result<Vec<RoleId>, SourceError> {
+            self.inner.parent_roles(tenant, role).await
+        }
+    }
 
     /// 构造一组通用测试标识符。
     fn ids() -> (TenantId, PrincipalId, RoleId) {
@@ -376,6 +1164,7 @@ mod tests {
         let explanation = block_on(engine.explain_tenant(TenantAccessRequest {
             subject,
             permission: Permission::parse("invoice:read").expect("permission"),
+            context: Default::default(),
         }))
         .expect("explanation");
 
@@ -396,6 +1185,7 @@ mod tests {
             subject,
             permission: Permission::parse("invoice:read").expect("permission"),
             target,
+            context: Default::default(),
         }))
         .expect("decision");
 
@@ -410,6 +1200,7 @@ mod tests {
         let request = TenantAccessRequest {
             subject,
             permission: Permission::parse("invoice:read").expect("permission"),
+            context: Default::default(),
         };
 
         let strict = block_on(strict_engine.can_tenant(request.clone())).expect("decision");
@@ -420,53 +1211,640 @@ mod tests {
     }
 
     #[test]
-    fn role_hierarchy_should_use_assignment_scope() {
-        let (tenant, principal, child) = ids();
-        let parent = RoleId::parse("parent").expect("role");
-        let root = ScopePath::parse("agent/1").expect("scope path");
-        let source = MemorySource::new();
-        source.set_tenant_status(tenant.clone(), TenantStatus::Active);
-        source.set_membership_status(tenant.clone(), principal.clone(), MembershipStatus::Active);
-        source.add_role_assignment(
-            tenant.clone(),
-            principal.clone(),
-            child.clone(),
-            GrantScope::paths(vec![root.clone()]).expect("grant scope"),
-        );
-        source.add_parent_role(tenant.clone(), child, parent.clone());
-        source.add_role_permission(
-            tenant.clone(),
-            parent,
-            Permission::parse("invoice:read").expect("permission"),
-        );
+    fn resource_prefix_wildcard_should_require_its_own_config_flag() {
+        let (source, subject) = active_source(GrantScope::tenant(), "billing/*:read");
+        let wildcard_engine = EngineBuilder::new(source.clone())
+            .enable_wildcard(true)
+            .build();
+        let prefix_engine = EngineBuilder::new(source)
+            .enable_resource_prefix_wildcard(true)
+            .build();
+        let request = |permission: &str| TenantAccessRequest {
+            subject: subject.clone(),
+            permission: Permission::parse(permission).expect("permission"),
+            context: Default::default(),
+        };
+
+        let wildcard = block_on(wildcard_engine.can_tenant(request("billing/invoice:read")))
+            .expect("decision");
+        let nested = block_on(prefix_engine.can_tenant(request("billing/refund/line:read")))
+            .expect("decision");
+        let other_action = block_on(prefix_engine.can_tenant(request("billing/invoice:delete")))
+            .expect("decision");
 
+        assert_eq!(wildcard, AccessDecision::Deny);
+        assert_eq!(nested, AccessDecision::Allow);
+        assert_eq!(other_action, AccessDecision::Deny);
+    }
+
+    #[test]
+    fn action_implications_should_let_manage_cover_implied_actions() {
+        let (source, subject) = active_source(GrantScope::tenant(), "invoice:manage");
+        let implications = ActionImplications::new()
+            .imply(
+                Action::parse("manage").expect("action"),
+                [
+                    Action::parse("read").expect("action"),
+                    Action::parse("delete").expect("action"),
+                ],
+            )
+            .expect("implications");
+        let strict_engine = EngineBuilder::new(source.clone()).build();
         let engine = EngineBuilder::new(source)
-            .enable_role_hierarchy(true)
+            .action_implications(implications)
             .build();
-        let scope = block_on(engine.accessible_scope(ScopeQuery {
-            subject: AuthSubject::new(tenant, principal),
-            permission: Permission::parse("invoice:read").expect("permission"),
-        }))
-        .expect("scope");
+        let request = |permission: &str| TenantAccessRequest {
+            subject: subject.clone(),
+            permission: Permission::parse(permission).expect("permission"),
+            context: Default::default(),
+        };
+
+        let strict = block_on(strict_engine.can_tenant(request("invoice:read"))).expect("decision");
+        let read = block_on(engine.can_tenant(request("invoice:read"))).expect("decision");
+        let export = block_on(engine.can_tenant(request("invoice:export"))).expect("decision");
+        let snapshot = block_on(engine.subject_permissions(&subject)).expect("snapshot");
 
+        assert_eq!(strict, AccessDecision::Deny);
+        assert_eq!(read, AccessDecision::Allow);
+        assert_eq!(export, AccessDecision::Deny);
         assert_eq!(
-            scope,
-            AccessScope::Paths {
-                tenant: TenantId::parse("tenant_1").expect("tenant"),
-                roots: vec![root],
-            }
+            snapshot
+                .permissions
+                .iter()
+                .map(|granted| granted.permission.to_string())
+                .collect::<Vec<_>>(),
+            vec!["invoice:delete", "invoice:manage", "invoice:read"]
         );
     }
 
+    #[cfg(feature = "memory-cache")]
     #[test]
-    fn inactive_tenant_should_return_none_with_reason() {
-        let (tenant, principal, _) = ids();
-        let source = MemorySource::new();
-        source.set_tenant_status(tenant.clone(), TenantStatus::Inactive);
+    fn warm_cache_should_skip_status_and_grant_reads() {
+        let (source, subject) = active_source(GrantScope::tenant(), "invoice:read");
+        let engine = EngineBuilder::new(CountingSource::new(source))
+            .cache(crate::MemoryCache::new(16).with_status_ttl(Duration::from_secs(60)))
+            .build();
+        let check = || {
+            block_on(engine.explain_tenant(TenantAccessRequest {
+                subject: subject.clone(),
+                permission: Permission::parse("invoice:read").expect("permission"),
+                context: Default::default(),
+            }))
+            .expect("explanation")
+        };
+        let reads = || {
+            (
+                engine.source.status_reads.load(Ordering::SeqCst),
+                engine.source.membership_reads.load(Ordering::SeqCst),
+                engine.source.assignment_reads.load(Ordering::SeqCst),
+            )
+        };
+
+        assert_eq!(check().decision, AccessDecision::Allow);
+        assert_eq!(check().decision, AccessDecision::Allow);
+        assert_eq!(reads(), (1, 1, 1));
+
+        // 成员状态变化后，只有失效成员状态才会重新读取，非活跃结果同样进入缓存。
+        engine.source.inner.set_membership_status(
+            subject.tenant.clone(),
+            subject.principal.clone(),
+            MembershipStatus::Inactive,
+        );
+        assert_eq!(check().decision, AccessDecision::Allow);
+        block_on(engine.invalidate_membership(&subject.tenant, &subject.principal));
+        assert_eq!(check().reason, Some(DenyReason::PrincipalInactive));
+        assert_eq!(check().reason, Some(DenyReason::PrincipalInactive));
+        assert_eq!(reads(), (1, 2, 1));
+    }
+
+    #[cfg(feature = "memory-cache")]
+    #[test]
+    fn cache_without_ttl_should_read_statuses_on_every_check() {
+        let (source, subject) = active_source(GrantScope::tenant(), "invoice:read");
+        let engine = EngineBuilder::new(CountingSource::new(source))
+            .cache(crate::MemoryCache::new(16))
+            .build();
+        let check = || {
+            block_on(engine.explain_tenant(TenantAccessRequest {
+                subject: subject.clone(),
+                permission: Permission::parse("invoice:read").expect("permission"),
+                context: Default::default(),
+            }))
+            .expect("explanation")
+        };
+
+        assert_eq!(check().decision, AccessDecision::Allow);
+
+        // 未配置存活时间时不缓存状态，停用成员无需失效即在下一次检查生效。
+        engine.source.inner.set_membership_status(
+            subject.tenant.clone(),
+            subject.principal.clone(),
+            MembershipStatus::Inactive,
+        );
+        assert_eq!(check().reason, Some(DenyReason::PrincipalInactive));
+        assert_eq!(engine.source.membership_reads.load(Ordering::SeqCst), 2);
+        assert_eq!(engine.source.assignment_reads.load(Ordering::SeqCst), 1);
+    }
+
+    #[cfg(feature = "memory-cache")]
+    #[test]
+    fn invalidate_role_should_only_reload_principals_using_the_role() {
+        let (source, subject) = active_source(GrantScope::tenant(), "invoice:read");
+        let (tenant, _, reader) = ids();
+        let base = RoleId::parse("base").expect("role");
+        let writer = RoleId::parse("writer").expect("role");
+        let other = PrincipalId::parse("user_2").expect("principal");
+        source.add_parent_role(tenant.clone(), reader, base.clone());
+        source.set_membership_status(tenant.clone(), other.clone(), MembershipStatus::Active);
+        source.add_role_assignment(
+            tenant.clone(),
+            other.clone(),
+            writer.clone(),
+            GrantScope::tenant(),
+        );
+        let other = AuthSubject::new(tenant.clone(), other);
+        let engine = EngineBuilder::new(CountingSource::new(source))
+            .cache(crate::MemoryCache::new(16))
+            .enable_role_hierarchy(true)
+            .build();
+        let warm = || {
+            for subject in [&subject, &other] {
+                block_on(engine.subject_permissions(subject)).expect("snapshot");
+            }
+            engine.source.assignment_reads.load(Ordering::SeqCst)
+        };
+
+        assert_eq!(warm(), 2);
+        // 继承得到的角色同样进入反向索引，只有依赖它的主体会重新加载。
+        block_on(engine.invalidate_role(&tenant, &base));
+        assert_eq!(warm(), 3);
+        block_on(engine.invalidate_role(&tenant, &writer));
+        assert_eq!(warm(), 4);
+        block_on(engine.invalidate_role(&tenant, &RoleId::parse("unused").expect("role")));
+        assert_eq!(warm(), 4);
+    }
+
+    #[cfg(feature = "memory-cache")]
+    #[test]
+    fn follow_invalidations_should_apply_published_events() {
+        use crate::{InvalidationBus, MemoryInvalidationBus};
+
+        let (source, subject) = active_source(GrantScope::tenant(), "invoice:read");
+        let engine = EngineBuilder::new(CountingSource::new(source))
+            .cache(crate::MemoryCache::new(16))
+            .build();
+        let bus = MemoryInvalidationBus::new();
+        let subscription = block_on(bus.subscribe()).expect("subscription");
+        let warm = || {
+            block_on(engine.subject_permissions(&subject)).expect("snapshot");
+            engine.source.assignment_reads.load(Ordering::SeqCst)
+        };
+
+        assert_eq!(warm(), 1);
+        block_on(bus.publish(InvalidationEvent::Principal {
+            tenant: subject.tenant.clone(),
+            principal: subject.principal.clone(),
+        }))
+        .expect("publish");
+        // 总线关闭后订阅读完剩余事件即结束，监听 future 随之返回。
+        drop(bus);
+        block_on(engine.follow_invalidations(subscription));
+
+        assert_eq!(warm(), 2);
+        assert_eq!(warm(), 2);
+    }
+
+    #[cfg(feature = "memory-cache")]
+    #[test]
+    fn policy_version_bump_should_bypass_stale_cache_entries() {
+        let (source, subject) = active_source(GrantScope::tenant(), "invoice:read");
+        let versions = crate::MemoryPolicyVersions::new();
+        let engine = EngineBuilder::new(CountingSource::new(source))
+            .cache(crate::MemoryCache::new(16).with_status_ttl(Duration::from_secs(60)))
+            .policy_versions(versions.clone())
+            .build();
+        let check = || {
+            block_on(engine.explain_tenant(TenantAccessRequest {
+                subject: subject.clone(),
+                permission: Permission::parse("invoice:read").expect("permission"),
+                context: Default::default(),
+            }))
+            .expect("explanation")
+        };
+        let reads = || {
+            (
+                engine.source.status_reads.load(Ordering::SeqCst),
+                engine.source.assignment_reads.load(Ordering::SeqCst),
+            )
+        };
+
+        assert_eq!(check().decision, AccessDecision::Allow);
+        assert_eq!(check().decision, AccessDecision::Allow);
+        assert_eq!(reads(), (1, 1));
+
+        // 其他节点修改数据后只递增版本，本节点没有收到任何失效调用。
+        engine
+            .source
+            .inner
+            .set_tenant_status(subject.tenant.clone(), TenantStatus::Inactive);
+        versions.bump_tenant(&subject.tenant);
+        assert_eq!(check().reason, Some(DenyReason::TenantInactive));
+        assert_eq!(reads(), (2, 1));
+
+        engine
+            .source
+            .inner
+            .set_tenant_status(subject.tenant.clone(), TenantStatus::Active);
+        versions.bump_global();
+        assert_eq!(check().decision, AccessDecision::Allow);
+        assert_eq!(check().decision, AccessDecision::Allow);
+        assert_eq!(reads(), (3, 2));
+    }
+
+    #[cfg(feature = "memory-cache")]
+    #[test]
+    fn refresh_ahead_should_recompute_outside_the_request_path() {
+        let (source, subject) = active_source(GrantScope::tenant(), "invoice:read");
+        let cache = crate::MemoryCache::new(16)
+            .with_ttl(Duration::from_secs(60))
+            .with_refresh_ahead(Duration::from_secs(61));
+        let engine = EngineBuilder::new(CountingSource::new(source))
+            .cache(cache)
+            .refresh_ahead(true)
+            .build();
+        let check = || {
+            block_on(engine.can_tenant(TenantAccessRequest {
+                subject: subject.clone(),
+                permission: Permission::parse("invoice:read").expect("permission"),
+                context: Default::default(),
+            }))
+            .expect("decision")
+        };
+        let reads = || engine.source.assignment_reads.load(Ordering::SeqCst);
+
+        assert_eq!(check(), AccessDecision::Allow);
+        assert_eq!(reads(), 1);
+        // 命中临近过期的条目：请求直接使用缓存值，刷新进入队列。
+        assert_eq!(check(), AccessDecision::Allow);
+        assert_eq!(check(), AccessDecision::Allow);
+        assert_eq!(reads(), 1);
+
+        assert_eq!(block_on(engine.refresh_pending()), 1);
+        assert_eq!(reads(), 2);
+        assert_eq!(block_on(engine.refresh_pending()), 0);
+        assert_eq!(check(), AccessDecision::Allow);
+        assert_eq!(reads(), 2);
+    }
+
+    #[test]
+    fn concurrent_cache_misses_should_share_one_computation() {
+        let (source, subject) = active_source(GrantScope::tenant(), "invoice:read");
+        let engine = EngineBuilder::new(CountingSource::new(source).yielding()).build();
+        let request = TenantAccessRequest {
+            subject,
+            permission: Permission::parse("invoice:read").expect("permission"),
+            context: Default::default(),
+        };
+
+        let decisions = block_on(futures::future::join_all(
+            (0..8).map(|_| engine.can_tenant(request.clone())),
+        ));
+
+        assert!(
+            decisions
+                .into_iter()
+                .all(|decision| decision.expect("decision") == AccessDecision::Allow)
+        );
+        assert_eq!(engine.source.assignment_reads.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn dropped_leader_should_let_waiter_retry() {
+        use std::future::Future;
+        use std::task::{Context, Poll};
+
+        let (source, subject) = active_source(GrantScope::tenant(), "invoice:read");
+        let engine = EngineBuilder::new(CountingSource::new(source).yielding()).build();
+        let request = TenantAccessRequest {
+            subject,
+            permission: Permission::parse("invoice:read").expect("permission"),
+            context: Default::default(),
+        };
+        let waker = futures::task::noop_waker();
+        let mut cx = Context::from_waker(&waker);
+        let mut leader = Box::pin(engine.can_tenant(request.clone()));
+        let mut waiter = Box::pin(engine.can_tenant(request));
+
+        assert!(matches!(leader.as_mut().poll(&mut cx), Poll::Pending));
+        assert!(matches!(waiter.as_mut().poll(&mut cx), Poll::Pending));
+        drop(leader);
+
+        assert_eq!(block_on(waiter).expect("decision"), AccessDecision::Allow);
+        assert_eq!(engine.source.assignment_reads.load(Ordering::SeqCst), 2);
+    }
+
+    #[test]
+    fn role_hierarchy_should_use_assignment_scope() {
+        let (tenant, principal, child) = ids();
+        let parent = RoleId::parse("parent").expect("role");
+        let root = ScopePath::parse("agent/1").expect("scope path");
+        let source = MemorySource::new();
+        source.set_tenant_status(tenant.clone(), TenantStatus::Active);
+        source.set_membership_status(tenant.clone(), principal.clone(), MembershipStatus::Active);
+        source.add_role_assignment(
+            tenant.clone(),
+            principal.clone(),
+            child.clone(),
+            GrantScope::paths(vec![root.clone()]).expect("grant scope"),
+        );
+        source.add_parent_role(tenant.clone(), child, parent.clone());
+        source.add_role_permission(
+            tenant.clone(),
+            parent,
+            Permission::parse("invoice:read").expect("permission"),
+        );
+
+        let engine = EngineBuilder::new(source)
+            .enable_role_hierarchy(true)
+            .build();
+        let scope = block_on(engine.accessible_scope(ScopeQuery {
+            subject: AuthSubject::new(tenant, principal),
+            permission: Permission::parse("invoice:read").expect("permission"),
+        }))
+        .expect("scope");
+
+        assert_eq!(
+            scope,
+            AccessScope::Paths {
+                tenant: TenantId::parse("tenant_1").expect("tenant"),
+                roots: vec![root],
+                patterns: Vec::new(),
+            }
+        );
+    }
+
+    #[test]
+    fn trace_access_scope_should_report_inheritance_path_of_matched_grant() {
+        let (tenant, principal, child) = ids();
+        let parent = RoleId::parse("parent").expect("role");
+        let grandparent = RoleId::parse("grandparent").expect("role");
+        let root = ScopePath::parse("agent/1").expect("scope path");
+        let source = MemorySource::new();
+        source.set_tenant_status(tenant.clone(), TenantStatus::Active);
+        source.set_membership_status(tenant.clone(), principal.clone(), MembershipStatus::Active);
+        source.add_role_assignment(
+            tenant.clone(),
+            principal.clone(),
+            child.clone(),
+            GrantScope::paths(vec![root.clone()]).expect("grant scope"),
+        );
+        source.add_parent_role(tenant.clone(), child.clone(), parent.clone());
+        source.add_parent_role(tenant.clone(), parent.clone(), grandparent.clone());
+        source.add_role_permission(
+            tenant.clone(),
+            grandparent.clone(),
+            Permission::parse("invoice:read").expect("permission"),
+        );
+        let engine = EngineBuilder::new(source)
+            .enable_role_hierarchy(true)
+            .build();
+
+        let explanation = block_on(engine.trace_access_scope(ScopedAccessRequest {
+            subject: AuthSubject::new(tenant, principal),
+            permission: Permission::parse("invoice:read").expect("permission"),
+            target: ScopePath::parse("agent/1/store/456").expect("scope path"),
+            context: Default::default(),
+        }))
+        .expect("explanation");
+        let trace = explanation.trace.expect("trace");
+
+        assert_eq!(explanation.decision, AccessDecision::Allow);
+        assert_eq!(trace.matched.len(), 1);
+        assert_eq!(trace.matched[0].role, grandparent);
+        assert_eq!(trace.matched[0].via, vec![child.clone(), parent]);
+        assert_eq!(trace.matched[0].assigned_role(), &child);
+        assert_eq!(
+            trace.matched[0].scope,
+            GrantScope::paths(vec![root]).expect("grant scope")
+        );
+        assert!(trace.closest.is_empty());
+    }
+
+    #[test]
+    fn trace_access_scope_should_report_closest_grants_on_denial() {
+        let (source, subject) = active_source(
+            GrantScope::paths(vec![ScopePath::parse("agent/1").expect("scope path")])
+                .expect("grant scope"),
+            "invoice:read",
+        );
+        let (tenant, _, role) = ids();
+        source.add_role_permission(
+            tenant,
+            role,
+            Permission::parse("invoice:write").expect("permission"),
+        );
+        let engine = EngineBuilder::new(source).build();
+        let request = ScopedAccessRequest {
+            subject,
+            permission: Permission::parse("invoice:read").expect("permission"),
+            target: ScopePath::parse("agent/2").expect("scope path"),
+            context: Default::default(),
+        };
+
+        let plain = block_on(engine.explain_access_scope(request.clone())).expect("explanation");
+        let traced = block_on(engine.trace_access_scope(request)).expect("explanation");
+        let trace = traced.trace.expect("trace");
+        let closest: Vec<_> = trace
+            .closest
+            .iter()
+            .map(|grant| grant.permission.to_string())
+            .collect();
+
+        assert_eq!(plain.trace, None);
+        assert_eq!(traced.reason, Some(DenyReason::ScopeDenied));
+        assert!(trace.matched.is_empty());
+        assert_eq!(closest, vec!["invoice:read", "invoice:write"]);
+    }
+
+    #[test]
+    fn conditional_permission_should_follow_request_context() {
+        let (source, subject) = active_source(GrantScope::tenant(), "invoice:approve");
+        let (tenant, _, role) = ids();
+        source.add_permission_condition(
+            tenant,
+            role,
+            Permission::parse("invoice:approve").expect("permission"),
+            Condition::parse("amount < 10000 and currency in ['CNY', 'USD']").expect("condition"),
+        );
+        let engine = EngineBuilder::new(source).enable_conditions(true).build();
+        let request = |context: &[(&str, AttributeValue)]| ScopedAccessRequest {
+            subject: subject.clone(),
+            permission: Permission::parse("invoice:approve").expect("permission"),
+            target: ScopePath::parse("agent/1").expect("scope path"),
+            context: context
+                .iter()
+                .map(|(key, value)| (key.to_string(), value.clone()))
+                .collect(),
+        };
+
+        let small = block_on(engine.explain_access_scope(request(&[
+            ("amount", AttributeValue::Int(9_000)),
+            ("currency", "USD".into()),
+        ])))
+        .expect("explanation");
+        let large = block_on(engine.explain_access_scope(request(&[
+            ("amount", AttributeValue::Int(12_000)),
+            ("currency", "USD".into()),
+        ])))
+        .expect("explanation");
+        let missing = block_on(engine.explain_access_scope(request(&[]))).expect("explanation");
+
+        assert_eq!(small.decision, AccessDecision::Allow);
+        assert_eq!(large.reason, Some(DenyReason::ConditionFailed));
+        assert_eq!(missing.reason, Some(DenyReason::ConditionFailed));
+    }
+
+    #[test]
+    fn check_many_should_evaluate_conditions_like_single_checks() {
+        let (source, subject) = active_source(GrantScope::tenant(), "invoice:approve");
+        let (tenant, _, role) = ids();
+        let approve = Permission::parse("invoice:approve").expect("permission");
+        source.add_permission_condition(
+            tenant,
+            role,
+            approve.clone(),
+            Condition::parse("amount < 10000").expect("condition"),
+        );
+        let engine = EngineBuilder::new(source).enable_conditions(true).build();
+        let target = ScopePath::parse("agent/1").expect("scope path");
+
+        for (amount, expected) in [
+            (9_000, AccessDecision::Allow),
+            (12_000, AccessDecision::Deny),
+        ] {
+            let context: RequestContext = [("amount".to_string(), AttributeValue::Int(amount))]
+                .into_iter()
+                .collect();
+            let single = block_on(engine.can_access_scope(ScopedAccessRequest {
+                subject: subject.clone(),
+                permission: approve.clone(),
+                target: target.clone(),
+                context: context.clone(),
+            }))
+            .expect("decision");
+            let batch = block_on(engine.check_many(
+                subject.clone(),
+                vec![(approve.clone(), Some(target.clone()))],
+                &context,
+            ))
+            .expect("decisions");
+
+            assert_eq!(single, expected);
+            assert_eq!(batch, vec![single]);
+        }
+    }
+
+    #[test]
+    fn conditional_deny_should_apply_unless_condition_is_false() {
+        let (source, subject) = active_source(GrantScope::tenant(), "invoice:read");
+        let (tenant, principal, _) = ids();
+        let restricted = RoleId::parse("restricted").expect("role");
+        source.insert_role_assignment(
+            tenant.clone(),
+            principal,
+            RoleAssignment::deny(restricted.clone(), GrantScope::tenant()),
+        );
+        source.add_role_permission(
+            tenant.clone(),
+            restricted.clone(),
+            Permission::parse("invoice:read").expect("permission"),
+        );
+        source.add_permission_condition(
+            tenant,
+            restricted,
+            Permission::parse("invoice:read").expect("permission"),
+            Condition::parse("external").expect("condition"),
+        );
+        let engine = EngineBuilder::new(source).enable_conditions(true).build();
+        let decide = |external: Option<bool>| {
+            block_on(
+                engine.can_tenant(TenantAccessRequest {
+                    subject: subject.clone(),
+                    permission: Permission::parse("invoice:read").expect("permission"),
+                    context: external
+                        .map(|value| ("external".to_string(), AttributeValue::Bool(value)))
+                        .into_iter()
+                        .collect(),
+                }),
+            )
+            .expect("decision")
+        };
+
+        assert_eq!(decide(Some(false)), AccessDecision::Allow);
+        assert_eq!(decide(Some(true)), AccessDecision::Deny);
+        assert_eq!(decide(None), AccessDecision::Deny);
+    }
+
+    #[test]
+    fn configured_conditions_should_fail_closed_when_disabled() {
+        let (source, subject) = active_source(GrantScope::tenant(), "invoice:approve");
+        let (tenant, principal, role) = ids();
+        source.add_role_permission(
+            tenant.clone(),
+            role.clone(),
+            Permission::parse("invoice:read").expect("permission"),
+        );
+        source.add_permission_condition(
+            tenant.clone(),
+            role,
+            Permission::parse("invoice:approve").expect("permission"),
+            Condition::parse("amount < 10000").expect("condition"),
+        );
+        let restricted = RoleId::parse("restricted").expect("role");
+        source.insert_role_assignment(
+            tenant.clone(),
+            principal,
+            RoleAssignment::deny(restricted.clone(), GrantScope::tenant()),
+        );
+        source.add_role_permission(
+            tenant.clone(),
+            restricted.clone(),
+            Permission::parse("invoice:read").expect("permission"),
+        );
+        source.add_permission_condition(
+            tenant,
+            restricted,
+            Permission::parse("invoice:read").expect("permission"),
+            Condition::parse("external").expect("condition"),
+        );
+        let engine = EngineBuilder::new(source).build();
+        let decide = |permission: &str| {
+            block_on(
+                engine.can_tenant(TenantAccessRequest {
+                    subject: subject.clone(),
+                    permission: Permission::parse(permission).expect("permission"),
+                    context: [("amount".to_string(), AttributeValue::Int(1))]
+                        .into_iter()
+                        .collect(),
+                }),
+            )
+            .expect("decision")
+        };
+
+        assert_eq!(decide("invoice:approve"), AccessDecision::Deny);
+        assert_eq!(decide("invoice:read"), AccessDecision::Deny);
+    }
+
+    #[test]
+    fn inactive_tenant_should_return_none_with_reason() {
+        let (tenant, principal, _) = ids();
+        let source = MemorySource::new();
+        source.set_tenant_status(tenant.clone(), TenantStatus::Inactive);
         let engine = EngineBuilder::new(source).build();
         let explanation = block_on(engine.explain_tenant(TenantAccessRequest {
             subject: AuthSubject::new(tenant, principal),
             permission: Permission::parse("invoice:read").expect("permission"),
+            context: Default::default(),
         }))
         .expect("explanation");
 
@@ -500,6 +1878,7 @@ mod tests {
         let explanation = block_on(engine.explain_tenant(TenantAccessRequest {
             subject: AuthSubject::new(tenant, principal),
             permission: Permission::parse("invoice:read").expect("permission"),
+            context: Default::default(),
         }))
         .expect("explanation");
 
@@ -518,6 +1897,7 @@ mod tests {
         let explanation = block_on(engine.explain_tenant(TenantAccessRequest {
             subject: AuthSubject::new(tenant, principal),
             permission: Permission::parse("invoice:read").expect("permission"),
+            context: Default::default(),
         }))
         .expect("explanation");
 
@@ -533,6 +1913,7 @@ mod tests {
         let explanation = block_on(engine.explain_tenant(TenantAccessRequest {
             subject,
             permission: Permission::parse("invoice:update").expect("permission"),
+            context: Default::default(),
         }))
         .expect("explanation");
 
@@ -554,6 +1935,7 @@ mod tests {
             subject,
             permission: Permission::parse("invoice:read").expect("permission"),
             target,
+            context: Default::default(),
         }))
         .expect("explanation");
 
@@ -569,6 +1951,7 @@ mod tests {
         let decision = block_on(engine.can_tenant(TenantAccessRequest {
             subject,
             permission: Permission::parse("invoice:read").expect("permission"),
+            context: Default::default(),
         }))
         .expect("decision");
 
@@ -618,10 +2001,410 @@ mod tests {
                     ScopePath::parse("agent/1").expect("scope path"),
                     ScopePath::parse("agent/2").expect("scope path"),
                 ],
+                patterns: Vec::new(),
             }
         );
     }
 
+    #[test]
+    fn deny_assignment_should_exclude_subtree_from_tenant_grant() {
+        let (source, subject) = active_source(GrantScope::tenant(), "invoice:read");
+        let (tenant, principal, role) = ids();
+        let carve_out = ScopePath::parse("agent/7").expect("scope path");
+        source.insert_role_assignment(
+            tenant.clone(),
+            principal,
+            RoleAssignment::deny(
+                role,
+                GrantScope::paths(vec![carve_out.clone()]).expect("grant scope"),
+            ),
+        );
+        let engine = EngineBuilder::new(source).build();
+        let permission = Permission::parse("invoice:read").expect("permission");
+
+        let scope = block_on(engine.accessible_scope(ScopeQuery {
+            subject: subject.clone(),
+            permission: permission.clone(),
+        }))
+        .expect("scope");
+        assert_eq!(
+            scope,
+            AccessScope::Tenant { tenant }
+                .subtract([GrantScope::paths(vec![carve_out.clone()]).expect("grant scope")])
+        );
+        assert!(
+            matches!(&scope, AccessScope::Restricted(restricted) if restricted.is_tenant_wide())
+        );
+        assert_eq!(scope.excluded(), [carve_out]);
+
+        let denied = block_on(engine.explain_access_scope(ScopedAccessRequest {
+            subject: subject.clone(),
+            permission: permission.clone(),
+            target: ScopePath::parse("agent/7/store/1").expect("scope path"),
+            context: Default::default(),
+        }))
+        .expect("explanation");
+        assert_eq!(denied.decision, AccessDecision::Deny);
+        assert_eq!(denied.reason, Some(DenyReason::ExplicitDeny));
+
+        let allowed = block_on(engine.can_access_scope(ScopedAccessRequest {
+            subject: subject.clone(),
+            permission: permission.clone(),
+            target: ScopePath::parse("agent/8/store/1").expect("scope path"),
+            context: Default::default(),
+        }))
+        .expect("decision");
+        assert_eq!(allowed, AccessDecision::Allow);
+
+        let tenant_level = block_on(engine.explain_tenant(TenantAccessRequest {
+            subject,
+            permission,
+            context: Default::default(),
+        }))
+        .expect("explanation");
+        assert_eq!(tenant_level.reason, Some(DenyReason::ExplicitDeny));
+    }
+
+    #[test]
+    fn explain_many_should_resolve_grants_once_and_keep_input_order() {
+        let (source, subject) = active_source(
+            GrantScope::paths(vec![ScopePath::parse("agent/1").expect("scope path")])
+                .expect("grant scope"),
+            "invoice:read",
+        );
+        let (tenant, principal, _) = ids();
+        let writer = RoleId::parse("writer").expect("role");
+        source.add_role_assignment(
+            tenant.clone(),
+            principal,
+            writer.clone(),
+            GrantScope::tenant(),
+        );
+        source.add_role_permission(
+            tenant,
+            writer,
+            Permission::parse("invoice:write").expect("permission"),
+        );
+        let source = CountingSource::new(source);
+        let engine = EngineBuilder::new(source).build();
+        let read = Permission::parse("invoice:read").expect("permission");
+        let write = Permission::parse("invoice:write").expect("permission");
+
+        let explanations = block_on(engine.explain_many(
+            subject,
+            vec![
+                (
+                    read.clone(),
+                    Some(ScopePath::parse("agent/1/store/2").expect("scope path")),
+                ),
+                (
+                    read.clone(),
+                    Some(ScopePath::parse("agent/2").expect("scope path")),
+                ),
+                (read, None),
+                (write, None),
+                (
+                    Permission::parse("invoice:delete").expect("permission"),
+                    None,
+                ),
+            ],
+            &RequestContext::new(),
+        ))
+        .expect("explanations");
+        let decisions: Vec<_> = explanations
+            .iter()
+            .map(|explanation| (explanation.decision, explanation.reason))
+            .collect();
+
+        assert_eq!(
+            decisions,
+            vec![
+                (AccessDecision::Allow, None),
+                (AccessDecision::Deny, Some(DenyReason::ScopeDenied)),
+                (AccessDecision::Deny, Some(DenyReason::TargetScopeRequired)),
+                (AccessDecision::Allow, None),
+                (AccessDecision::Deny, Some(DenyReason::PermissionMissing)),
+            ]
+        );
+        assert_eq!(engine.source.status_reads.load(Ordering::SeqCst), 1);
+        assert_eq!(engine.source.assignment_reads.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn explain_many_should_deny_all_for_inactive_tenant() {
+        let (source, subject) = active_source(GrantScope::tenant(), "invoice:read");
+        let (tenant, _, _) = ids();
+        source.set_tenant_status(tenant, TenantStatus::Inactive);
+        let engine = EngineBuilder::new(source).build();
+        let read = Permission::parse("invoice:read").expect("permission");
+
+        let explanations = block_on(engine.explain_many(
+            subject,
+            vec![
+                (read.clone(), None),
+                (read, Some(ScopePath::parse("agent/1").expect("scope path"))),
+            ],
+            &RequestContext::new(),
+        ))
+        .expect("explanations");
+
+        assert_eq!(explanations.len(), 2);
+        assert!(explanations.iter().all(|explanation| {
+            explanation.decision == AccessDecision::Deny
+                && explanation.reason == Some(DenyReason::TenantInactive)
+        }));
+    }
+
+    #[test]
+    fn subject_permissions_should_list_each_permission_with_scope_and_roles() {
+        let agent = ScopePath::parse("agent/1").expect("scope path");
+        let (source, subject) = active_source(
+            GrantScope::paths(vec![agent.clone()]).expect("grant scope"),
+            "invoice:read",
+        );
+        let (tenant, principal, reader) = ids();
+        let manager = RoleId::parse("manager").expect("role");
+        let auditor = RoleId::parse("auditor").expect("role");
+        source.add_role_assignment(
+            tenant.clone(),
+            principal.clone(),
+            manager.clone(),
+            GrantScope::paths(vec![ScopePath::parse("agent/2").expect("scope path")])
+                .expect("grant scope"),
+        );
+        source.add_role_assignment(
+            tenant.clone(),
+            principal.clone(),
+            auditor.clone(),
+            GrantScope::tenant(),
+        );
+        source.insert_role_assignment(
+            tenant.clone(),
+            principal,
+            RoleAssignment::deny(auditor.clone(), GrantScope::tenant()),
+        );
+        for permission in ["invoice:read", "invoice:approve"] {
+            source.add_role_permission(
+                tenant.clone(),
+                manager.clone(),
+                Permission::parse(permission).expect("permission"),
+            );
+        }
+        source.add_role_permission(
+            tenant.clone(),
+            auditor,
+            Permission::parse("invoice:export").expect("permission"),
+        );
+        source.add_role_permission(
+            tenant.clone(),
+            reader.clone(),
+            Permission::parse("invoice:*").expect("permission"),
+        );
+        let engine = EngineBuilder::new(source).build();
+
+        let snapshot = block_on(engine.subject_permissions(&subject)).expect("snapshot");
+        let agent_2 = ScopePath::parse("agent/2").expect("scope path");
+
+        assert_eq!(snapshot.subject, subject);
+        assert_eq!(
+            snapshot.permissions,
+            vec![
+                GrantedPermission {
+                    permission: Permission::parse("invoice:approve").expect("permission"),
+                    scope: AccessScope::Paths {
+                        tenant: tenant.clone(),
+                        roots: vec![agent_2.clone()],
+                        patterns: Vec::new(),
+                    },
+                    roles: vec![manager.clone()],
+                    conditional: false,
+                },
+                GrantedPermission {
+                    permission: Permission::parse("invoice:read").expect("permission"),
+                    scope: AccessScope::Paths {
+                        tenant,
+                        roots: vec![agent, agent_2],
+                        patterns: Vec::new(),
+                    },
+                    roles: vec![manager, reader],
+                    conditional: false,
+                },
+            ]
+        );
+        assert!(
+            !snapshot.contains_exact(&Permission::parse("invoice:export").expect("permission"))
+        );
+    }
+
+    #[test]
+    fn deny_assignment_covering_all_roots_should_return_none_with_reason() {
+        let root = ScopePath::parse("agent/1").expect("scope path");
+        let (source, subject) = active_source(
+            GrantScope::paths(vec![root.clone()]).expect("grant scope"),
+            "invoice:read",
+        );
+        let (tenant, principal, role) = ids();
+        source.insert_role_assignment(
+            tenant,
+            principal,
+            RoleAssignment::deny(role, GrantScope::tenant()),
+        );
+        let engine = EngineBuilder::new(source).build();
+
+        let explanation = block_on(engine.explain_access_scope(ScopedAccessRequest {
+            subject,
+            permission: Permission::parse("invoice:read").expect("permission"),
+            target: root,
+            context: Default::default(),
+        }))
+        .expect("explanation");
+
+        assert_eq!(explanation.scope, AccessScope::None);
+        assert_eq!(explanation.reason, Some(DenyReason::ExplicitDeny));
+    }
+
+    #[test]
+    fn assignment_validity_window_should_follow_engine_clock() {
+        let (tenant, principal, role) = ids();
+        let start = SystemTime::UNIX_EPOCH + Duration::from_secs(1_000);
+        let end = start + Duration::from_secs(60);
+        let source = MemorySource::new();
+        source.set_tenant_status(tenant.clone(), TenantStatus::Active);
+        source.set_membership_status(tenant.clone(), principal.clone(), MembershipStatus::Active);
+        source.insert_role_assignment(
+            tenant.clone(),
+            principal.clone(),
+            RoleAssignment::new(role.clone(), GrantScope::tenant())
+                .with_not_before(start)
+                .with_expires_at(end),
+        );
+        source.add_role_permission(
+            tenant.clone(),
+            role,
+            Permission::parse("invoice:read").expect("permission"),
+        );
+        let request = TenantAccessRequest {
+            subject: AuthSubject::new(tenant, principal),
+            permission: Permission::parse("invoice:read").expect("permission"),
+            context: Default::default(),
+        };
+
+        let decision_at = |now: SystemTime| {
+            let engine = EngineBuilder::new(source.clone())
+                .clock(FixedClock(now))
+                .build();
+            block_on(engine.can_tenant(request.clone())).expect("decision")
+        };
+
+        assert_eq!(
+            decision_at(start - Duration::from_secs(1)),
+            AccessDecision::Deny
+        );
+        assert_eq!(decision_at(start), AccessDecision::Allow);
+        assert_eq!(decision_at(end), AccessDecision::Deny);
+    }
+
+    /// 构造在 `agent/12` 下拥有委托权限和 `invoice:read` 的组长，以及待委托的角色：
+    /// `reader` 只有 `invoice:read`，`auditor` 额外拥有 `invoice:export`，`junior` 继承 `auditor`。
+    fn delegation_source() -> (MemorySource, AuthSubject) {
+        let (tenant, principal, _) = ids();
+        let role = |value: &str| RoleId::parse(value).expect("role");
+        let permission = |value: &str| Permission::parse(value).expect("permission");
+        let source = MemorySource::new();
+        source.set_tenant_status(tenant.clone(), TenantStatus::Active);
+        source.set_membership_status(tenant.clone(), principal.clone(), MembershipStatus::Active);
+        source.add_role_assignment(
+            tenant.clone(),
+            principal.clone(),
+            role("lead"),
+            GrantScope::paths(vec![ScopePath::parse("agent/12").expect("scope path")])
+                .expect("grant scope"),
+        );
+        for value in ["role/assignment:delegate", "invoice:read"] {
+            source.add_role_permission(tenant.clone(), role("lead"), permission(value));
+        }
+        source.add_role_permission(tenant.clone(), role("reader"), permission("invoice:read"));
+        for value in ["invoice:read", "invoice:export"] {
+            source.add_role_permission(tenant.clone(), role("auditor"), permission(value));
+        }
+        source.add_parent_role(tenant.clone(), role("junior"), role("auditor"));
+        (source, AuthSubject::new(tenant, principal))
+    }
+
+    /// 构造委托请求。
+    fn delegation(subject: &AuthSubject, role: &str, root: &str) -> DelegationRequest {
+        DelegationRequest {
+            delegator: subject.clone(),
+            permission: Permission::parse("role/assignment:delegate").expect("permission"),
+            assignment: RoleAssignment::new(
+                RoleId::parse(role).expect("role"),
+                GrantScope::paths(vec![ScopePath::parse(root).expect("scope path")])
+                    .expect("grant scope"),
+            ),
+        }
+    }
+
+    #[test]
+    fn explain_delegation_should_require_role_permissions_within_own_scope() {
+        let (source, subject) = delegation_source();
+        let engine = EngineBuilder::new(source).build();
+        let explain = |request| block_on(engine.explain_delegation(request)).expect("explanation");
+
+        assert_eq!(
+            block_on(engine.can_delegate(delegation(&subject, "reader", "agent/12/store/1")))
+                .expect("decision"),
+            AccessDecision::Allow
+        );
+        assert_eq!(
+            explain(delegation(&subject, "reader", "agent/13")).reason,
+            Some(DelegationDenyReason::Delegator(DenyReason::ScopeDenied))
+        );
+        let auditor = explain(delegation(&subject, "auditor", "agent/12"));
+        assert_eq!(
+            auditor.reason,
+            Some(DelegationDenyReason::PermissionNotHeld)
+        );
+        assert_eq!(
+            auditor.missing,
+            vec![Permission::parse("invoice:export").expect("permission")]
+        );
+
+        let mut request = delegation(&subject, "reader", "agent/12");
+        request.permission = Permission::parse("role/assignment:grant").expect("permission");
+        assert_eq!(
+            explain(request).reason,
+            Some(DelegationDenyReason::Delegator(
+                DenyReason::PermissionMissing
+            ))
+        );
+    }
+
+    #[test]
+    fn explain_delegation_should_include_inherited_role_permissions() {
+        let (source, subject) = delegation_source();
+        let flat = EngineBuilder::new(source.clone()).build();
+        let hierarchical = EngineBuilder::new(source)
+            .enable_role_hierarchy(true)
+            .build();
+
+        assert_eq!(
+            block_on(flat.can_delegate(delegation(&subject, "junior", "agent/12")))
+                .expect("decision"),
+            AccessDecision::Allow
+        );
+        let explanation =
+            block_on(hierarchical.explain_delegation(delegation(&subject, "junior", "agent/12")))
+                .expect("explanation");
+        assert_eq!(
+            explanation.reason,
+            Some(DelegationDenyReason::PermissionNotHeld)
+        );
+        assert_eq!(
+            explanation.missing,
+            vec![Permission::parse("invoice:export").expect("permission")]
+        );
+    }
+
     #[test]
     fn role_cycle_should_return_error() {
         let (tenant, principal, child) = ids();
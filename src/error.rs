@@ -1,4 +1,5 @@
 use crate::ids::{RoleId, TenantId};
+use crate::permission::Action;
 #[cfg(feature = "platform")]
 use crate::platform::PlatformRoleId;
 use thiserror::Error;
@@ -24,6 +25,15 @@ pub enum Error {
     /// 范围输入非法。
     #[error("invalid scope: {0}")]
     InvalidScope(String),
+    /// 检测到动作蕴含环。
+    #[error("action implication cycle detected at action {action}")]
+    ActionImplicationCycle { action: Action },
+    /// 条件表达式非法。
+    #[error("invalid condition: {0}")]
+    InvalidCondition(String),
+    /// 缓存条目编码非法或已损坏。
+    #[error("invalid cache entry: {0}")]
+    InvalidCacheEntry(String),
     /// 检测到角色继承环。
     #[error("role cycle detected for tenant {tenant} at role {role}")]
     RoleCycleDetected { tenant: TenantId, role: RoleId },
@@ -1,7 +1,22 @@
 use crate::Permission;
+use crate::condition::Condition;
+use crate::permission::PermissionMatcher;
+
+/// 角色分配的授权效果。
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum Effect {
+    /// 授予分配范围内的访问权。
+    #[default]
+    Allow,
+    /// 从已授予的访问范围中扣除分配范围。
+    Deny,
+}
 
 /// 角色分配、角色权限和分配范围合成后的有效授权。
 #[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ScopedGrant<R, S> {
     /// 读取到权限的来源角色。
     pub role: R,
@@ -9,20 +24,130 @@ pub struct ScopedGrant<R, S> {
     pub permission: Permission,
     /// 原始角色分配附带的范围。
     pub scope: S,
+    /// 原始角色分配的授权效果。
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub effect: Effect,
+    /// 从分配角色到来源角色的继承路径，不含来源角色；直接分配时为空。
+    #[cfg_attr(
+        feature = "serde",
+        serde(default = "Vec::new", skip_serializing_if = "Vec::is_empty")
+    )]
+    pub via: Vec<R>,
+    /// 授权生效前必须满足的条件。
+    #[cfg_attr(
+        feature = "serde",
+        serde(
+            default,
+            deserialize_with = "crate::condition::deserialize_combined",
+            skip_serializing_if = "Option::is_none"
+        )
+    )]
+    pub condition: Option<Condition>,
 }
 
 impl<R, S> ScopedGrant<R, S> {
-    /// 创建有效授权。
+    /// 创建允许效果的有效授权。
     pub fn new(role: R, permission: Permission, scope: S) -> Self {
         Self {
             role,
             permission,
             scope,
+            effect: Effect::Allow,
+            via: Vec::new(),
+            condition: None,
         }
     }
 
+    /// 设置授权效果。
+    pub fn with_effect(mut self, effect: Effect) -> Self {
+        self.effect = effect;
+        self
+    }
+
+    /// 设置从分配角色到来源角色的继承路径。
+    pub fn with_via(mut self, via: Vec<R>) -> Self {
+        self.via = via;
+        self
+    }
+
+    /// 设置授权生效前必须满足的条件。
+    pub fn with_condition(mut self, condition: Option<Condition>) -> Self {
+        self.condition = condition;
+        self
+    }
+
+    /// 返回原始角色分配中的角色。
+    pub fn assigned_role(&self) -> &R {
+        self.via.first().unwrap_or(&self.role)
+    }
+
     /// 返回该授权是否匹配所需权限。
-    pub(crate) fn matches_permission(&self, required: &Permission, wildcard: bool) -> bool {
-        self.permission.matches(required, wildcard)
+    pub(crate) fn matches_permission(
+        &self,
+        required: &Permission,
+        matcher: PermissionMatcher<'_>,
+    ) -> bool {
+        matcher.matches(&self.permission, required)
+    }
+}
+
+#[cfg(all(test, feature = "serde"))]
+mod tests {
+    use super::*;
+    use crate::{GrantScope, RoleId, ScopePath};
+
+    #[test]
+    fn scoped_grant_should_roundtrip_through_serde_and_omit_defaults() {
+        let direct = ScopedGrant::new(
+            RoleId::parse("reader").expect("role"),
+            Permission::parse("invoice:read").expect("permission"),
+            GrantScope::tenant(),
+        );
+        let inherited = ScopedGrant::new(
+            RoleId::parse("base").expect("role"),
+            Permission::parse("invoice:read").expect("permission"),
+            GrantScope::paths(vec![ScopePath::parse("agent/1").expect("scope path")])
+                .expect("scope"),
+        )
+        .with_effect(Effect::Deny)
+        .with_via(vec![RoleId::parse("manager").expect("role")])
+        .with_condition(Some(Condition::parse("amount < 10").expect("condition")));
+
+        let json = serde_json::to_value(&direct).expect("serialize");
+
+        assert_eq!(
+            json,
+            serde_json::json!({
+                "role": "reader",
+                "permission": "invoice:read",
+                "scope": { "type": "tenant" },
+                "effect": "allow",
+            })
+        );
+        for grant in [direct, inherited] {
+            let text = serde_json::to_string(&grant).expect("serialize");
+            let decoded: ScopedGrant<RoleId, GrantScope> =
+                serde_json::from_str(&text).expect("deserialize");
+            assert_eq!(decoded, grant);
+        }
+    }
+
+    #[test]
+    fn scoped_grant_should_roundtrip_many_combined_conditions() {
+        let conditions = (0..64).map(|index| {
+            Condition::parse(format!("amount < {index} or external")).expect("condition")
+        });
+        let grant = ScopedGrant::new(
+            RoleId::parse("reader").expect("role"),
+            Permission::parse("invoice:read").expect("permission"),
+            GrantScope::tenant(),
+        )
+        .with_condition(Condition::all(conditions));
+
+        let text = serde_json::to_string(&grant).expect("serialize");
+        let decoded: ScopedGrant<RoleId, GrantScope> =
+            serde_json::from_str(&text).expect("deserialize");
+
+        assert_eq!(decoded, grant);
     }
 }
@@ -0,0 +1,294 @@
+use crate::cache::EffectiveGrant;
+use crate::condition::Condition;
+use crate::error::{Error, Result};
+use crate::grant::Effect;
+use crate::ids::RoleId;
+use crate::permission::{Action, Permission, Resource};
+use crate::scope::{GrantScope, ScopePath, ScopePattern};
+
+/// 有效授权二进制编码的格式魔数。
+const MAGIC: &[u8; 4] = b"RTEG";
+
+/// 当前二进制编码格式版本，格式不兼容变化时递增。
+pub const GRANT_CODEC_VERSION: u8 = 1;
+
+/// 结尾校验和的字节数。
+const CHECKSUM_LEN: usize = 8;
+
+/// 把有效授权编码为紧凑的二进制格式，供进程外缓存存储。
+///
+/// 格式为魔数、版本号、按 LEB128 编码长度的字段，结尾附带 FNV-1a 校验和。
+pub fn encode_effective_grants(grants: &[EffectiveGrant]) -> Vec<u8> {
+    let mut out = Vec::with_capacity(16 + grants.len() * 48);
+    out.extend_from_slice(MAGIC);
+    out.push(GRANT_CODEC_VERSION);
+    write_len(&mut out, grants.len());
+    for grant in grants {
+        write_str(&mut out, grant.role.as_str());
+        write_str(&mut out, grant.permission.resource().as_str());
+        write_str(&mut out, grant.permission.action().as_str());
+        match &grant.scope {
+            GrantScope::Tenant => out.push(0),
+            GrantScope::Paths(roots) => {
+                out.push(1);
+                write_len(&mut out, roots.as_slice().len());
+                for root in roots.as_slice() {
+                    write_str(&mut out, root.as_str());
+                }
+                write_len(&mut out, roots.patterns().len());
+                for pattern in roots.patterns() {
+                    write_str(&mut out, pattern.as_str());
+                }
+            }
+        }
+        out.push(match grant.effect {
+            Effect::Allow => 0,
+            Effect::Deny => 1,
+        });
+        write_len(&mut out, grant.via.len());
+        for role in &grant.via {
+            write_str(&mut out, role.as_str());
+        }
+        match &grant.condition {
+            None => out.push(0),
+            Some(condition) => {
+                out.push(1);
+                write_str(&mut out, condition.as_str());
+            }
+        }
+    }
+    let checksum = fnv1a(&out);
+    out.extend_from_slice(&checksum.to_le_bytes());
+    out
+}
+
+/// 解码 [`encode_effective_grants`] 生成的数据。
+///
+/// 魔数、版本、校验和不匹配或内容被截断时返回错误；所有标识符、权限、
+/// 范围和条件都会重新校验，调用方可以把错误视为缓存未命中。
+pub fn decode_effective_grants(bytes: &[u8]) -> Result<Vec<EffectiveGrant>> {
+    if bytes.len() < MAGIC.len() + 1 + CHECKSUM_LEN || &bytes[..MAGIC.len()] != MAGIC {
+        return Err(invalid("missing format header"));
+    }
+    let version = bytes[MAGIC.len()];
+    if version != GRANT_CODEC_VERSION {
+        return Err(invalid(format!("unsupported format version {version}")));
+    }
+    let (body, checksum) = bytes.split_at(bytes.len() - CHECKSUM_LEN);
+    let mut expected = [0; CHECKSUM_LEN];
+    expected.copy_from_slice(checksum);
+    if fnv1a(body) != u64::from_le_bytes(expected) {
+        return Err(invalid("checksum mismatch"));
+    }
+
+    let mut reader = Reader {
+        bytes: &body[MAGIC.len() + 1..],
+    };
+    let count = reader.len()?;
+    let mut grants = Vec::with_capacity(count.min(reader.bytes.len()));
+    for _ in 0..count {
+        let role = RoleId::parse(reader.str()?)?;
+        let resource = Resource::parse(reader.str()?)?;
+        let action = Action::parse(reader.str()?)?;
+        let scope = match reader.byte()? {
+            0 => GrantScope::Tenant,
+            1 => {
+                let roots = reader.list(ScopePath::parse)?;
+                let patterns = reader.list(ScopePattern::parse)?;
+                GrantScope::paths_with_patterns(roots, patterns)?
+            }
+            tag => return Err(invalid(format!("unknown scope tag {tag}"))),
+        };
+        let effect = match reader.byte()? {
+            0 => Effect::Allow,
+            1 => Effect::Deny,
+            tag => return Err(invalid(format!("unknown effect tag {tag}"))),
+        };
+        let via = reader.list(RoleId::parse)?;
+        let condition = match reader.byte()? {
+            0 => None,
+            1 => Some(Condition::parse_combined(reader.str()?)?),
+            tag => return Err(invalid(format!("unknown condition tag {tag}"))),
+        };
+        grants.push(
+            EffectiveGrant::new(role, Permission::new(resource, action), scope)
+                .with_effect(effect)
+                .with_via(via)
+                .with_condition(condition),
+        );
+    }
+    if !reader.bytes.is_empty() {
+        return Err(invalid("trailing bytes"));
+    }
+    Ok(grants)
+}
+
+/// 构造缓存条目格式错误。
+fn invalid(message: impl Into<String>) -> Error {
+    Error::InvalidCacheEntry(message.into())
+}
+
+/// 写入 LEB128 编码的长度。
+fn write_len(out: &mut Vec<u8>, mut value: usize) {
+    loop {
+        let byte = (value & 0x7f) as u8;
+        value >>= 7;
+        if value == 0 {
+            out.push(byte);
+            return;
+        }
+        out.push(byte | 0x80);
+    }
+}
+
+/// 写入带长度前缀的字符串。
+fn write_str(out: &mut Vec<u8>, value: &str) {
+    write_len(out, value.len());
+    out.extend_from_slice(value.as_bytes());
+}
+
+/// FNV-1a 64 位校验和。
+fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf2_9ce4_8422_2325, |hash, byte| {
+        (hash ^ u64::from(*byte)).wrapping_mul(0x0100_0000_01b3)
+    })
+}
+
+/// 顺序读取已校验过校验和的数据。
+struct Reader<'a> {
+    bytes: &'a [u8],
+}
+
+impl<'a> Reader<'a> {
+    /// 读取一个字节。
+    fn byte(&mut self) -> Result<u8> {
+        let (first, rest) = self
+            .bytes
+            .split_first()
+            .ok_or_else(|| invalid("unexpected end of data"))?;
+        self.bytes = rest;
+        Ok(*first)
+    }
+
+    /// 读取 LEB128 编码的长度。
+    fn len(&mut self) -> Result<usize> {
+        let mut value = 0usize;
+        for shift in (0..usize::BITS).step_by(7) {
+            let byte = self.byte()?;
+            value |= usize::from(byte & 0x7f)
+                .checked_shl(shift)
+                .ok_or_else(|| invalid("length overflow"))?;
+            if byte & 0x80 == 0 {
+                return Ok(value);
+            }
+        }
+        Err(invalid("length overflow"))
+    }
+
+    /// 读取带长度前缀的 UTF-8 字符串。
+    fn str(&mut self) -> Result<&'a str> {
+        let len = self.len()?;
+        if len > self.bytes.len() {
+            return Err(invalid("unexpected end of data"));
+        }
+        let (value, rest) = self.bytes.split_at(len);
+        self.bytes = rest;
+        std::str::from_utf8(value).map_err(|_| invalid("string is not valid utf-8"))
+    }
+
+    /// 读取带数量前缀的字符串列表，并逐项解析。
+    fn list<T>(&mut self, parse: impl Fn(&'a str) -> Result<T>) -> Result<Vec<T>> {
+        let count = self.len()?;
+        let mut items = Vec::with_capacity(count.min(self.bytes.len()));
+        for _ in 0..count {
+            items.push(parse(self.str()?)?);
+        }
+        Ok(items)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 构造覆盖全部字段的测试授权。
+    fn sample() -> Vec<EffectiveGrant> {
+        let role = |value: &str| RoleId::parse(value).expect("role");
+        vec![
+            EffectiveGrant::new(
+                role("reader"),
+                Permission::parse("invoice:read").expect("permission"),
+                GrantScope::tenant(),
+            ),
+            EffectiveGrant::new(
+                role("base"),
+                Permission::parse("order:*").expect("permission"),
+                GrantScope::paths_with_patterns(
+                    vec![ScopePath::parse("agent/1").expect("scope path")],
+                    vec![ScopePattern::parse("region/*/store/**").expect("scope pattern")],
+                )
+                .expect("scope"),
+            )
+            .with_effect(Effect::Deny)
+            .with_via(vec![role("manager"), role("lead")])
+            .with_condition(Some(
+                Condition::parse("amount < 1000 and region in ['cn']").expect("condition"),
+            )),
+        ]
+    }
+
+    #[test]
+    fn codec_should_roundtrip_grants() {
+        let grants = sample();
+
+        let bytes = encode_effective_grants(&grants);
+
+        assert_eq!(&bytes[..5], b"RTEG\x01");
+        assert_eq!(decode_effective_grants(&bytes).expect("decode"), grants);
+        assert_eq!(
+            decode_effective_grants(&encode_effective_grants(&[])).expect("decode"),
+            Vec::new()
+        );
+    }
+
+    #[test]
+    fn codec_should_roundtrip_many_combined_conditions() {
+        let conditions = (0..64).map(|index| {
+            Condition::parse(format!("amount < {index} or external")).expect("condition")
+        });
+        let grants = vec![
+            EffectiveGrant::new(
+                RoleId::parse("reader").expect("role"),
+                Permission::parse("invoice:read").expect("permission"),
+                GrantScope::tenant(),
+            )
+            .with_condition(Condition::all(conditions)),
+        ];
+
+        let bytes = encode_effective_grants(&grants);
+
+        assert_eq!(decode_effective_grants(&bytes).expect("decode"), grants);
+    }
+
+    #[test]
+    fn codec_should_reject_corrupted_or_foreign_data() {
+        let bytes = encode_effective_grants(&sample());
+        let mut corrupted = bytes.clone();
+        corrupted[10] ^= 0xff;
+        let mut future = bytes.clone();
+        future[4] = GRANT_CODEC_VERSION + 1;
+
+        for (input, message) in [
+            (&corrupted[..], "checksum"),
+            (&future[..], "version"),
+            (&bytes[..bytes.len() - 1], "checksum"),
+            (&b"{\"role\":\"reader\"}"[..], "header"),
+        ] {
+            let err = decode_effective_grants(input).expect_err("must reject");
+            assert!(
+                matches!(&err, Error::InvalidCacheEntry(reason) if reason.contains(message)),
+                "{err}"
+            );
+        }
+    }
+}
@@ -0,0 +1,147 @@
+use crate::error::{Error, Result};
+use crate::permission::Action;
+use std::collections::{BTreeMap, BTreeSet};
+
+/// 动作蕴含关系注册表，例如 `manage` 蕴含 `read`、`update` 和 `delete`。
+///
+/// 蕴含关系是传递的；注册时检测环，保存的是传递闭包。
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct ActionImplications {
+    closure: BTreeMap<Action, BTreeSet<Action>>,
+}
+
+impl ActionImplications {
+    /// 创建空注册表。
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 注册 `action` 蕴含的动作，形成环时返回错误。
+    pub fn imply(
+        mut self,
+        action: Action,
+        implied: impl IntoIterator<Item = Action>,
+    ) -> Result<Self> {
+        for target in implied {
+            if target == action || self.implies(&target, &action) {
+                return Err(Error::ActionImplicationCycle { action });
+            }
+            let mut added = self.closure.get(&target).cloned().unwrap_or_default();
+            added.insert(target);
+            for implied in self.closure.values_mut() {
+                if implied.contains(&action) {
+                    implied.extend(added.iter().cloned());
+                }
+            }
+            self.closure
+                .entry(action.clone())
+                .or_default()
+                .extend(added);
+        }
+        Ok(self)
+    }
+
+    /// 返回授予的动作是否蕴含所需动作（不含相等情况）。
+    pub fn implies(&self, granted: &Action, required: &Action) -> bool {
+        self.closure
+            .get(granted)
+            .is_some_and(|implied| implied.contains(required))
+    }
+
+    /// 返回授予的动作直接或间接蕴含的全部动作。
+    pub fn implied_by(&self, granted: &Action) -> impl Iterator<Item = &Action> {
+        self.closure.get(granted).into_iter().flatten()
+    }
+
+    /// 返回注册表是否为空。
+    pub fn is_empty(&self) -> bool {
+        self.closure.is_empty()
+    }
+
+    /// 生成用于缓存签名的稳定摘要。
+    pub(crate) fn signature(&self) -> String {
+        if self.is_empty() {
+            return "-".to_string();
+        }
+        // FNV-1a：跨进程稳定，外部缓存可以安全复用同一签名。
+        let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+        let mut write = |bytes: &[u8]| {
+            for byte in bytes {
+                hash ^= u64::from(*byte);
+                hash = hash.wrapping_mul(0x0100_0000_01b3);
+            }
+        };
+        for (action, implied) in &self.closure {
+            write(action.as_str().as_bytes());
+            write(b">");
+            for target in implied {
+                write(target.as_str().as_bytes());
+                write(b",");
+            }
+            write(b";");
+        }
+        format!("{hash:016x}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 解析测试动作。
+    fn action(value: &str) -> Action {
+        Action::parse(value).expect("action")
+    }
+
+    #[test]
+    fn implications_should_be_transitive() {
+        let implications = ActionImplications::new()
+            .imply(action("update"), [action("read")])
+            .expect("implications")
+            .imply(action("manage"), [action("update"), action("delete")])
+            .expect("implications");
+
+        assert!(implications.implies(&action("manage"), &action("read")));
+        assert!(implications.implies(&action("manage"), &action("delete")));
+        assert!(!implications.implies(&action("read"), &action("update")));
+        assert_eq!(
+            implications
+                .implied_by(&action("manage"))
+                .map(Action::as_str)
+                .collect::<Vec<_>>(),
+            vec!["delete", "read", "update"]
+        );
+    }
+
+    #[test]
+    fn implications_should_reject_cycles() {
+        let err = ActionImplications::new()
+            .imply(action("manage"), [action("update")])
+            .expect("implications")
+            .imply(action("update"), [action("read")])
+            .expect("implications")
+            .imply(action("read"), [action("manage")])
+            .expect_err("must detect cycle");
+
+        assert!(
+            matches!(err, Error::ActionImplicationCycle { action: cycle } if cycle.as_str() == "read")
+        );
+    }
+
+    #[test]
+    fn signature_should_depend_only_on_closure() {
+        let direct = ActionImplications::new()
+            .imply(action("update"), [action("read")])
+            .expect("implications")
+            .imply(action("manage"), [action("update"), action("read")])
+            .expect("implications");
+        let chained = ActionImplications::new()
+            .imply(action("manage"), [action("update")])
+            .expect("implications")
+            .imply(action("update"), [action("read")])
+            .expect("implications");
+
+        assert_eq!(direct.signature(), chained.signature());
+        assert_ne!(direct.signature(), ActionImplications::new().signature());
+    }
+}
@@ -0,0 +1,321 @@
+//! 缓存失效事件总线。
+//!
+//! 进程内实现 [`MemoryInvalidationBus`] 不依赖 [`MemoryCache`](crate::MemoryCache)，
+//! 因此不受任何 feature 控制，使用自定义 [`Cache`](crate::Cache) 时同样可以直接使用。
+
+use crate::error::SourceError;
+use crate::ids::{PrincipalId, RoleId, TenantId};
+use async_trait::async_trait;
+
+/// 跨组件或跨节点传播的缓存失效事件。
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(tag = "type", rename_all = "snake_case")
+)]
+pub enum InvalidationEvent {
+    /// 失效某个主体的缓存授权和成员状态。
+    Principal {
+        /// 主体所属租户。
+        tenant: TenantId,
+        /// 主体 ID。
+        principal: PrincipalId,
+    },
+    /// 失效依赖某个角色的缓存授权。
+    Role {
+        /// 角色所属租户。
+        tenant: TenantId,
+        /// 角色 ID。
+        role: RoleId,
+    },
+    /// 失效某个租户的全部缓存。
+    Tenant {
+        /// 租户 ID。
+        tenant: TenantId,
+    },
+    /// 失效全部缓存。
+    All,
+}
+
+/// 失效事件的发布订阅总线。
+///
+/// crate 只提供进程内实现；Redis pub/sub、PostgreSQL LISTEN/NOTIFY 等适配器可以在 crate 外实现。
+#[async_trait]
+pub trait InvalidationBus: Send + Sync {
+    /// 订阅句柄类型。
+    type Subscription: InvalidationSubscription;
+
+    /// 发布失效事件。
+    async fn publish(&self, event: InvalidationEvent) -> std::result::Result<(), SourceError>;
+
+    /// 订阅此后发布的失效事件。
+    async fn subscribe(&self) -> std::result::Result<Self::Subscription, SourceError>;
+}
+
+/// 失效事件订阅。
+#[async_trait]
+pub trait InvalidationSubscription: Send {
+    /// 等待下一条事件，总线关闭时返回 `None`。
+    ///
+    /// 无法确认是否遗漏事件时（例如订阅落后或连接重建），实现应返回
+    /// [`InvalidationEvent::All`]，宁可多清理也不能保留过期授权。
+    async fn recv(&mut self) -> Option<InvalidationEvent>;
+}
+
+pub use self::memory::{MemoryInvalidationBus, MemoryInvalidationSubscription};
+
+mod memory {
+    use super::{InvalidationBus, InvalidationEvent, InvalidationSubscription};
+    use crate::error::SourceError;
+    use crate::sync::lock;
+    use async_trait::async_trait;
+    use std::collections::VecDeque;
+    use std::future::Future;
+    use std::pin::Pin;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::{Arc, Mutex};
+    use std::task::{Context, Poll, Waker};
+
+    const DEFAULT_CAPACITY: usize = 1024;
+
+    /// 进程内广播失效总线，语义与有界广播通道相同。
+    ///
+    /// 每个订阅独立消费；订阅落后超过容量时丢弃积压事件并收到一次
+    /// [`InvalidationEvent::All`]。所有总线句柄被丢弃后，订阅在读完剩余事件后结束。
+    #[derive(Debug)]
+    pub struct MemoryInvalidationBus {
+        shared: Arc<Shared>,
+    }
+
+    /// 总线句柄和订阅共享的状态。
+    #[derive(Debug)]
+    struct Shared {
+        state: Mutex<BusState>,
+        capacity: usize,
+        senders: AtomicUsize,
+    }
+
+    /// 环形缓冲区及等待中的订阅。
+    #[derive(Debug, Default)]
+    struct BusState {
+        events: VecDeque<InvalidationEvent>,
+        /// 下一条事件的序号，`events` 中保存的是序号 `next_seq - events.len()` 起的事件。
+        next_seq: u64,
+        wakers: Vec<Waker>,
+        closed: bool,
+    }
+
+    impl MemoryInvalidationBus {
+        /// 使用默认容量创建总线。
+        pub fn new() -> Self {
+            Self::with_capacity(DEFAULT_CAPACITY)
+        }
+
+        /// 使用给定容量创建总线，容量至少为 1。
+        pub fn with_capacity(capacity: usize) -> Self {
+            Self {
+                shared: Arc::new(Shared {
+                    state: Mutex::new(BusState::default()),
+                    capacity: capacity.max(1),
+                    senders: AtomicUsize::new(1),
+                }),
+            }
+        }
+
+        /// 同步发布事件并唤醒所有订阅。
+        pub fn send(&self, event: InvalidationEvent) {
+            let wakers = {
+                let mut state = lock(&self.shared.state);
+                state.events.push_back(event);
+                if state.events.len() > self.shared.capacity {
+                    state.events.pop_front();
+                }
+                state.next_seq += 1;
+                std::mem::take(&mut state.wakers)
+            };
+            for waker in wakers {
+                waker.wake();
+            }
+        }
+
+        /// 创建从当前位置开始的订阅。
+        pub fn subscribe_now(&self) -> MemoryInvalidationSubscription {
+            let next_seq = lock(&self.shared.state).next_seq;
+            MemoryInvalidationSubscription {
+                shared: Arc::clone(&self.shared),
+                next_seq,
+            }
+        }
+    }
+
+    impl Default for MemoryInvalidationBus {
+        fn default() -> Self {
+            Self::new()
+        }
+    }
+
+    impl Clone for MemoryInvalidationBus {
+        fn clone(&self) -> Self {
+            self.shared.senders.fetch_add(1, Ordering::SeqCst);
+            Self {
+                shared: Arc::clone(&self.shared),
+            }
+        }
+    }
+
+    impl Drop for MemoryInvalidationBus {
+        fn drop(&mut self) {
+            if self.shared.senders.fetch_sub(1, Ordering::SeqCst) != 1 {
+                return;
+            }
+            let wakers = {
+                let mut state = lock(&self.shared.state);
+                state.closed = true;
+                std::mem::take(&mut state.wakers)
+            };
+            for waker in wakers {
+                waker.wake();
+            }
+        }
+    }
+
+    #[async_trait]
+    impl InvalidationBus for MemoryInvalidationBus {
+        type Subscription = MemoryInvalidationSubscription;
+
+        /// 发布事件，进程内发布不会失败。
+        async fn publish(&self, event: InvalidationEvent) -> std::result::Result<(), SourceError> {
+            self.send(event);
+            Ok(())
+        }
+
+        /// 订阅此后发布的事件。
+        async fn subscribe(&self) -> std::result::Result<Self::Subscription, SourceError> {
+            Ok(self.subscribe_now())
+        }
+    }
+
+    /// [`MemoryInvalidationBus`] 的订阅。
+    #[derive(Debug)]
+    pub struct MemoryInvalidationSubscription {
+        shared: Arc<Shared>,
+        next_seq: u64,
+    }
+
+    impl MemoryInvalidationSubscription {
+        /// 读取下一条事件，没有新事件时登记唤醒器。
+        fn poll_next(&mut self, cx: &mut Context<'_>) -> Poll<Option<InvalidationEvent>> {
+            let mut state = lock(&self.shared.state);
+            let oldest = state.next_seq - state.events.len() as u64;
+            if self.next_seq < oldest {
+                // 积压事件已被覆盖，无法得知遗漏了什么，只能全量失效。
+                self.next_seq = state.next_seq;
+                return Poll::Ready(Some(InvalidationEvent::All));
+            }
+            if self.next_seq < state.next_seq {
+                let event = state.events[(self.next_seq - oldest) as usize].clone();
+                self.next_seq += 1;
+                return Poll::Ready(Some(event));
+            }
+            if state.closed {
+                return Poll::Ready(None);
+            }
+            if !state.wakers.iter().any(|waker| waker.will_wake(cx.waker())) {
+                state.wakers.push(cx.waker().clone());
+            }
+            Poll::Pending
+        }
+    }
+
+    #[async_trait]
+    impl InvalidationSubscription for MemoryInvalidationSubscription {
+        /// 等待下一条事件。
+        async fn recv(&mut self) -> Option<InvalidationEvent> {
+            Recv { subscription: self }.await
+        }
+    }
+
+    /// 等待下一条事件的 future。
+    struct Recv<'a> {
+        subscription: &'a mut MemoryInvalidationSubscription,
+    }
+
+    impl Future for Recv<'_> {
+        type Output = Option<InvalidationEvent>;
+
+        fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+            self.subscription.poll_next(cx)
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+        use crate::TenantId;
+        use futures::executor::block_on;
+
+        /// 构造租户失效事件。
+        fn tenant_event(value: &str) -> InvalidationEvent {
+            InvalidationEvent::Tenant {
+                tenant: TenantId::parse(value).expect("tenant"),
+            }
+        }
+
+        #[test]
+        fn memory_bus_should_broadcast_to_every_subscription() {
+            let bus = MemoryInvalidationBus::new();
+            let mut first = block_on(bus.subscribe()).expect("subscription");
+            let mut second = block_on(bus.subscribe()).expect("subscription");
+
+            block_on(bus.publish(tenant_event("tenant_1"))).expect("publish");
+            block_on(bus.publish(InvalidationEvent::All)).expect("publish");
+
+            for subscription in [&mut first, &mut second] {
+                assert_eq!(
+                    block_on(subscription.recv()),
+                    Some(tenant_event("tenant_1"))
+                );
+                assert_eq!(block_on(subscription.recv()), Some(InvalidationEvent::All));
+            }
+        }
+
+        #[test]
+        fn lagging_subscription_should_receive_all() {
+            let bus = MemoryInvalidationBus::with_capacity(2);
+            let mut subscription = bus.subscribe_now();
+
+            for tenant in ["tenant_1", "tenant_2", "tenant_3", "tenant_4"] {
+                bus.send(tenant_event(tenant));
+            }
+
+            assert_eq!(block_on(subscription.recv()), Some(InvalidationEvent::All));
+            bus.send(tenant_event("tenant_5"));
+            assert_eq!(
+                block_on(subscription.recv()),
+                Some(tenant_event("tenant_5"))
+            );
+        }
+
+        #[test]
+        fn subscription_should_end_after_last_bus_handle_is_dropped() {
+            let bus = MemoryInvalidationBus::new();
+            let clone = bus.clone();
+            let mut subscription = bus.subscribe_now();
+
+            bus.send(InvalidationEvent::All);
+            drop(bus);
+            let waiter = std::thread::spawn(move || {
+                let first = block_on(subscription.recv());
+                let second = block_on(subscription.recv());
+                (first, second)
+            });
+            drop(clone);
+
+            assert_eq!(
+                waiter.join().expect("waiter"),
+                (Some(InvalidationEvent::All), None)
+            );
+        }
+    }
+}
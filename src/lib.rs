@@ -8,12 +8,17 @@
 #![forbid(unsafe_code)]
 
 mod cache;
+mod clock;
+mod condition;
 mod decision;
 mod engine;
 mod error;
 mod grant;
+mod grant_codec;
 mod id;
 mod ids;
+mod implication;
+mod invalidation;
 #[cfg(feature = "memory-cache")]
 mod memory_cache;
 #[cfg(feature = "memory-store")]
@@ -21,28 +26,58 @@ mod memory_source;
 mod permission;
 #[cfg(feature = "platform")]
 pub mod platform;
+mod policy_version;
+mod refresh;
 mod request;
 mod role;
 mod role_hierarchy;
 mod scope;
+mod single_flight;
+mod snapshot;
 mod source;
+mod sync;
+mod tiered_cache;
 
 #[cfg(feature = "axum")]
 pub mod axum;
 
-pub use crate::cache::{Cache, EffectiveGrant, NoCache};
-pub use crate::decision::{AccessDecision, AccessExplanation, DenyReason};
+pub use crate::cache::{Cache, CacheEntryMeta, EffectiveGrant, NoCache, StatusEntryMeta};
+pub use crate::clock::{Clock, SystemClock};
+pub use crate::condition::{AttributeValue, Condition, PermissionCondition, RequestContext};
+pub use crate::decision::{
+    AccessDecision, AccessExplanation, AccessTrace, DelegationDenyReason, DelegationExplanation,
+    DenyReason,
+};
 pub use crate::engine::{Engine, EngineBuilder, EngineConfig};
 pub use crate::error::{Error, Result, SourceError};
+pub use crate::grant::{Effect, ScopedGrant};
+pub use crate::grant_codec::{
+    GRANT_CODEC_VERSION, decode_effective_grants, encode_effective_grants,
+};
 pub use crate::ids::{PrincipalId, RoleId, TenantId};
+pub use crate::implication::ActionImplications;
+pub use crate::invalidation::{
+    InvalidationBus, InvalidationEvent, InvalidationSubscription, MemoryInvalidationBus,
+    MemoryInvalidationSubscription,
+};
 pub use crate::permission::{Action, Permission, Resource};
-pub use crate::request::{AuthSubject, ScopeQuery, ScopedAccessRequest, TenantAccessRequest};
+pub use crate::policy_version::PolicyVersionSource;
+pub use crate::request::{
+    AuthSubject, DelegationRequest, ScopeQuery, ScopedAccessRequest, TenantAccessRequest,
+};
 pub use crate::role::RoleAssignment;
-pub use crate::scope::{AccessScope, GrantScope, ScopePath, ScopeRoots};
+pub use crate::scope::sql;
+pub use crate::scope::{
+    AccessScope, GrantScope, RestrictedScope, ScopePath, ScopePattern, ScopeRoots,
+};
+pub use crate::snapshot::{GrantedPermission, SubjectPermissions};
 pub use crate::source::{AuthorizationSource, MembershipStatus, TenantStatus};
+pub use crate::tiered_cache::TieredCache;
 
 #[cfg(feature = "memory-store")]
 pub use crate::memory_source::MemorySource;
+#[cfg(feature = "memory-store")]
+pub use crate::policy_version::MemoryPolicyVersions;
 
 #[cfg(feature = "memory-cache")]
-pub use crate::memory_cache::MemoryCache;
+pub use crate::memory_cache::{CacheShardStats, CacheWeigher, MemoryCache, MemoryCacheStats};
@@ -1,24 +1,86 @@
-use std::collections::{HashMap, VecDeque};
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::future::Future;
 use std::hash::{Hash, Hasher};
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::{Arc, RwLock, RwLockReadGuard, RwLockWriteGuard};
 use std::time::{Duration, Instant};
 
 use async_trait::async_trait;
 
-use crate::cache::{Cache, EffectiveGrant};
+use crate::cache::{Cache, CacheEntryMeta, EffectiveGrant, StatusEntryMeta};
 use crate::ids::{PrincipalId, RoleId, TenantId};
+use crate::source::{MembershipStatus, TenantStatus};
 
 const SMALL_CACHE_SHARD_THRESHOLD: usize = 128;
 const MAX_DEFAULT_SHARDS: usize = 16;
 
-/// 有效授权的内存缓存。
+/// 有效授权、租户状态和成员状态的内存缓存。
+///
+/// 状态与有效授权分开存储，各自按分片容量淘汰；未配置存活时间时不缓存状态。
 #[derive(Debug, Clone)]
 pub struct MemoryCache {
     shards: Arc<Vec<RwLock<CacheState>>>,
+    counters: Arc<Vec<ShardCounters>>,
     shard_capacities: Arc<Vec<usize>>,
     shard_count: usize,
     capacity: usize,
+    weight_limit: Option<usize>,
+    weigher: CacheWeigher,
     ttl: Option<Duration>,
+    status_ttl: Option<Duration>,
+    refresh_ahead: Option<Duration>,
+}
+
+/// 有效授权条目的权重计算方式，用于 [`MemoryCache::with_weight_limit`]。
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Hash)]
+pub enum CacheWeigher {
+    /// 每个条目的权重为其中的授权数量，空条目按 1 计算。
+    #[default]
+    Grants,
+    /// 每个条目的权重为估算的堆内存字节数。
+    EstimatedBytes,
+}
+
+impl CacheWeigher {
+    /// 计算条目权重，至少为 1。
+    fn weigh(self, grants: &[EffectiveGrant]) -> usize {
+        match self {
+            Self::Grants => grants.len().max(1),
+            Self::EstimatedBytes => {
+                std::mem::size_of::<CacheEntry>()
+                    + grants.iter().map(estimated_grant_bytes).sum::<usize>()
+            }
+        }
+    }
+}
+
+/// 估算单条有效授权占用的字节数，只计入结构体本身和字符串内容。
+fn estimated_grant_bytes(grant: &EffectiveGrant) -> usize {
+    let strings = grant.role.as_str().len()
+        + grant.permission.resource().as_str().len()
+        + grant.permission.action().as_str().len()
+        + grant
+            .scope
+            .roots()
+            .iter()
+            .map(|root| std::mem::size_of_val(root) + root.as_str().len())
+            .sum::<usize>()
+        + grant
+            .scope
+            .patterns()
+            .iter()
+            .map(|pattern| std::mem::size_of_val(pattern) + pattern.as_str().len())
+            .sum::<usize>()
+        + grant
+            .via
+            .iter()
+            .map(|role| std::mem::size_of_val(role) + role.as_str().len())
+            .sum::<usize>()
+        + grant
+            .condition
+            .as_ref()
+            .map_or(0, |condition| condition.as_str().len() * 2);
+    std::mem::size_of::<EffectiveGrant>() + strings
 }
 
 /// 单个分片内的缓存状态。
@@ -26,6 +88,14 @@ pub struct MemoryCache {
 struct CacheState {
     entries: HashMap<CacheKey, CacheEntry>,
     order: VecDeque<CacheKey>,
+    /// 当前全部条目的权重之和。
+    weight: usize,
+    /// 从租户角色到依赖该角色的缓存键的反向索引。
+    role_index: HashMap<(TenantId, RoleId), HashSet<CacheKey>>,
+    /// 写入时没有角色信息的缓存键，角色级失效时保守清理。
+    untracked: HashSet<CacheKey>,
+    tenant_statuses: StatusMap<TenantId, TenantStatus>,
+    memberships: StatusMap<(TenantId, PrincipalId), MembershipStatus>,
 }
 
 /// 缓存条目的唯一键。
@@ -36,11 +106,357 @@ struct CacheKey {
     config_signature: String,
 }
 
-/// 缓存条目及其更新时间。
+/// 缓存条目及其过期时间。
 #[derive(Debug, Clone)]
 struct CacheEntry {
     grants: Vec<EffectiveGrant>,
-    updated_at: Instant,
+    expires_at: Option<Instant>,
+    roles: Option<Vec<RoleId>>,
+    /// 是否已经要求过提前刷新，每个条目只提示一次。
+    refresh_requested: bool,
+    /// 写入时按缓存的权重计算方式得到的权重。
+    weight: usize,
+}
+
+impl CacheEntry {
+    /// 判断缓存条目是否已经过期。
+    fn is_expired(&self, now: Instant) -> bool {
+        self.expires_at.is_some_and(|at| now > at)
+    }
+
+    /// 判断条目是否进入提前刷新窗口且尚未提示过。
+    fn wants_refresh(&self, now: Instant, window: Option<Duration>) -> bool {
+        !self.refresh_requested
+            && window
+                .zip(self.expires_at)
+                .is_some_and(|(window, at)| at.saturating_duration_since(now) <= window)
+    }
+
+    /// 返回以剩余存活时间为上限的条目元数据。
+    fn meta(&self, now: Instant) -> CacheEntryMeta {
+        CacheEntryMeta {
+            max_ttl: self.expires_at.map(|at| at.saturating_duration_since(now)),
+            roles: self.roles.clone(),
+        }
+    }
+}
+
+/// 单个分片的统计计数器，不受分片锁保护。
+#[derive(Debug, Default)]
+struct ShardCounters {
+    hits: AtomicU64,
+    misses: AtomicU64,
+    status_hits: AtomicU64,
+    status_misses: AtomicU64,
+    inserts: AtomicU64,
+    capacity_evictions: AtomicU64,
+    expirations: AtomicU64,
+    invalidations: AtomicU64,
+}
+
+impl ShardCounters {
+    /// 计数器加一。
+    fn incr(counter: &AtomicU64) {
+        Self::add(counter, 1);
+    }
+
+    /// 计数器增加 `n`。
+    fn add(counter: &AtomicU64, n: usize) {
+        if n > 0 {
+            counter.fetch_add(n as u64, Ordering::Relaxed);
+        }
+    }
+
+    /// 按状态读取结果记录命中或未命中。
+    fn record_status<T>(&self, status: Option<T>) -> Option<T> {
+        match status {
+            Some(_) => Self::incr(&self.status_hits),
+            None => Self::incr(&self.status_misses),
+        }
+        status
+    }
+
+    /// 读取当前计数。
+    fn snapshot(&self, entries: usize, capacity: usize) -> CacheShardStats {
+        CacheShardStats {
+            hits: self.hits.load(Ordering::Relaxed),
+            misses: self.misses.load(Ordering::Relaxed),
+            status_hits: self.status_hits.load(Ordering::Relaxed),
+            status_misses: self.status_misses.load(Ordering::Relaxed),
+            inserts: self.inserts.load(Ordering::Relaxed),
+            capacity_evictions: self.capacity_evictions.load(Ordering::Relaxed),
+            expirations: self.expirations.load(Ordering::Relaxed),
+            invalidations: self.invalidations.load(Ordering::Relaxed),
+            entries,
+            capacity,
+            ..CacheShardStats::default()
+        }
+    }
+
+    /// 把全部计数清零。
+    fn reset(&self) {
+        for counter in [
+            &self.hits,
+            &self.misses,
+            &self.status_hits,
+            &self.status_misses,
+            &self.inserts,
+            &self.capacity_evictions,
+            &self.expirations,
+            &self.invalidations,
+        ] {
+            counter.store(0, Ordering::Relaxed);
+        }
+    }
+}
+
+/// [`MemoryCache`] 单个分片的统计快照。
+///
+/// 除 `entries` 和 `capacity` 外均为自创建或上次 [`MemoryCache::reset_stats`] 以来的累计值。
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CacheShardStats {
+    /// 有效授权命中次数。
+    pub hits: u64,
+    /// 有效授权未命中次数，包括读到过期条目。
+    pub misses: u64,
+    /// 租户状态和成员状态命中次数。
+    pub status_hits: u64,
+    /// 租户状态和成员状态未命中次数。
+    pub status_misses: u64,
+    /// 有效授权写入次数。
+    pub inserts: u64,
+    /// 因分片容量或权重上限淘汰的有效授权条目数。
+    pub capacity_evictions: u64,
+    /// 因存活时间到期删除的有效授权条目数。
+    pub expirations: u64,
+    /// 因失效调用删除的有效授权条目数。
+    pub invalidations: u64,
+    /// 当前有效授权条目数，可能包含尚未清理的过期条目。
+    pub entries: usize,
+    /// 分片容量。
+    pub capacity: usize,
+    /// 当前有效授权条目的权重之和，按 [`MemoryCache::with_weight_limit`] 配置的方式计算。
+    pub weight: usize,
+    /// 分片权重上限，未开启权重限制时为 `None`。
+    pub weight_limit: Option<usize>,
+}
+
+impl CacheShardStats {
+    /// 返回有效授权命中率，没有读取时返回 `None`。
+    pub fn hit_rate(&self) -> Option<f64> {
+        let total = self.hits + self.misses;
+        (total > 0).then(|| self.hits as f64 / total as f64)
+    }
+
+    /// 累加另一个分片的统计。
+    fn accumulate(&mut self, other: &Self) {
+        self.hits += other.hits;
+        self.misses += other.misses;
+        self.status_hits += other.status_hits;
+        self.status_misses += other.status_misses;
+        self.inserts += other.inserts;
+        self.capacity_evictions += other.capacity_evictions;
+        self.expirations += other.expirations;
+        self.invalidations += other.invalidations;
+        self.entries += other.entries;
+        self.capacity += other.capacity;
+        self.weight += other.weight;
+        self.weight_limit = match (self.weight_limit, other.weight_limit) {
+            (Some(total), Some(limit)) => Some(total + limit),
+            (total, limit) => total.or(limit),
+        };
+    }
+}
+
+/// [`MemoryCache`] 的统计快照。
+#[derive(Debug, Clone, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MemoryCacheStats {
+    /// 全部分片的汇总。
+    pub total: CacheShardStats,
+    /// 按分片下标排列的统计。
+    pub shards: Vec<CacheShardStats>,
+}
+
+impl CacheState {
+    /// 写入缓存条目并维护角色索引。
+    fn insert_entry(&mut self, key: CacheKey, entry: CacheEntry) {
+        self.unindex(&key);
+        match &entry.roles {
+            Some(roles) => {
+                for role in roles {
+                    self.role_index
+                        .entry((key.tenant.clone(), role.clone()))
+                        .or_default()
+                        .insert(key.clone());
+                }
+            }
+            None => {
+                self.untracked.insert(key.clone());
+            }
+        }
+        self.weight += entry.weight;
+        if let Some(previous) = self.entries.insert(key, entry) {
+            self.weight -= previous.weight;
+        }
+    }
+
+    /// 删除缓存条目及其角色索引，不处理 LRU 顺序。
+    fn unlink(&mut self, key: &CacheKey) -> bool {
+        self.unindex(key);
+        match self.entries.remove(key) {
+            Some(entry) => {
+                self.weight -= entry.weight;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// 从角色索引中移除缓存键。
+    fn unindex(&mut self, key: &CacheKey) {
+        let Some(entry) = self.entries.get(key) else {
+            return;
+        };
+        match &entry.roles {
+            Some(roles) => {
+                for role in roles {
+                    let index_key = (key.tenant.clone(), role.clone());
+                    if let Some(keys) = self.role_index.get_mut(&index_key) {
+                        keys.remove(key);
+                        if keys.is_empty() {
+                            self.role_index.remove(&index_key);
+                        }
+                    }
+                }
+            }
+            None => {
+                self.untracked.remove(key);
+            }
+        }
+    }
+
+    /// 删除满足条件的缓存条目，并同步 LRU 顺序和角色索引，返回删除数量。
+    fn remove_entries_where(
+        &mut self,
+        predicate: impl Fn(&CacheKey, &CacheEntry) -> bool,
+    ) -> usize {
+        let keys: Vec<CacheKey> = self
+            .entries
+            .iter()
+            .filter(|(key, entry)| predicate(key, entry))
+            .map(|(key, _)| key.clone())
+            .collect();
+        self.remove_keys(keys)
+    }
+
+    /// 删除给定缓存键，并同步 LRU 顺序和角色索引，返回删除数量。
+    fn remove_keys(&mut self, keys: impl IntoIterator<Item = CacheKey>) -> usize {
+        let mut removed = 0;
+        for key in keys {
+            removed += usize::from(self.unlink(&key));
+        }
+        if removed > 0 {
+            self.order.retain(|key| self.entries.contains_key(key));
+        }
+        removed
+    }
+
+    /// 清空全部缓存条目和角色索引，返回删除数量。
+    fn clear_entries(&mut self) -> usize {
+        let removed = self.entries.len();
+        self.entries.clear();
+        self.weight = 0;
+        self.order.clear();
+        self.role_index.clear();
+        self.untracked.clear();
+        removed
+    }
+}
+
+/// 按首次写入顺序淘汰的状态缓存。
+///
+/// 每个键只保留最近写入的策略版本，旧版本的状态读取时视为未命中并在下次写入时被覆盖。
+/// `order` 中可能残留已删除的键，超出一定比例时才压缩。
+#[derive(Debug)]
+struct StatusMap<K, V> {
+    entries: HashMap<K, StatusEntry<V>>,
+    order: VecDeque<K>,
+}
+
+/// 状态缓存条目。
+#[derive(Debug)]
+struct StatusEntry<V> {
+    status: V,
+    policy_version: String,
+    expires_at: Instant,
+}
+
+impl<K, V> Default for StatusMap<K, V> {
+    fn default() -> Self {
+        Self {
+            entries: HashMap::new(),
+            order: VecDeque::new(),
+        }
+    }
+}
+
+impl<K: Clone + Eq + Hash, V: Copy> StatusMap<K, V> {
+    /// 读取策略版本一致且未过期的状态，元数据以剩余存活时间为上限。
+    fn get(&self, key: &K, policy_version: &str, now: Instant) -> Option<(V, StatusEntryMeta)> {
+        self.entries
+            .get(key)
+            .filter(|entry| entry.policy_version == policy_version && now <= entry.expires_at)
+            .map(|entry| {
+                let meta = StatusEntryMeta {
+                    max_ttl: Some(entry.expires_at.saturating_duration_since(now)),
+                };
+                (entry.status, meta)
+            })
+    }
+
+    /// 写入状态，覆盖已有键时保留其淘汰位置，超出容量时淘汰最早写入的条目。
+    ///
+    /// 写入不扫描过期条目，过期条目读取时视为未命中，由淘汰或 `sweep_expired` 删除。
+    fn insert(&mut self, key: K, entry: StatusEntry<V>, capacity: usize) {
+        if self.entries.insert(key.clone(), entry).is_none() {
+            self.order.push_back(key);
+        }
+        while self.entries.len() > capacity {
+            match self.order.pop_front() {
+                Some(key) => {
+                    self.entries.remove(&key);
+                }
+                None => break,
+            }
+        }
+    }
+
+    /// 删除单个状态，`order` 中的键留待淘汰或压缩时跳过。
+    fn remove(&mut self, key: &K) {
+        if self.entries.remove(key).is_some() && self.order.len() > self.entries.len() * 2 + 16 {
+            self.order.retain(|key| self.entries.contains_key(key));
+        }
+    }
+
+    /// 删除已过期的状态。
+    fn prune(&mut self, now: Instant) {
+        self.entries.retain(|_, entry| now <= entry.expires_at);
+        self.order.retain(|key| self.entries.contains_key(key));
+    }
+
+    /// 删除满足条件的状态。
+    fn remove_where(&mut self, predicate: impl Fn(&K) -> bool) {
+        self.entries.retain(|key, _| !predicate(key));
+        self.order.retain(|key| self.entries.contains_key(key));
+    }
+
+    /// 清空全部状态。
+    fn clear(&mut self) {
+        self.entries.clear();
+        self.order.clear();
+    }
 }
 
 impl MemoryCache {
@@ -56,25 +472,114 @@ impl MemoryCache {
     pub fn with_shards(mut self, shards: usize) -> Self {
         let shard_count = Self::normalize_shards(self.capacity, shards);
         self.shards = Arc::new(Self::new_shards(shard_count));
+        self.counters = Arc::new(Self::new_counters(shard_count));
         self.shard_capacities = Arc::new(Self::shard_capacities(self.capacity, shard_count));
         self.shard_count = shard_count;
         self
     }
 
+    /// 在条目数量容量之外，再按总权重限制有效授权，例如授权数量或估算字节数。
+    ///
+    /// 总权重按分片平均分配，每个分片至少为 1；写入后分片权重超限时按 LRU 淘汰。
+    /// 权重超过单个分片上限的条目不会被缓存，也不会挤出其他条目。租户状态和成员状态不计权重。
+    pub fn with_weight_limit(mut self, max_weight: usize, weigher: CacheWeigher) -> Self {
+        self.weight_limit = Some(max_weight);
+        self.weigher = weigher;
+        self
+    }
+
     /// 配置缓存条目的存活时间。
     pub fn with_ttl(mut self, ttl: Duration) -> Self {
         self.ttl = Some(ttl);
         self
     }
 
+    /// 单独配置租户状态和成员状态的存活时间，未配置时沿用 [`MemoryCache::with_ttl`]。
+    ///
+    /// 两者都未配置时不缓存状态，每次检查都从授权数据源读取，停用租户或成员后立即生效。
+    pub fn with_status_ttl(mut self, ttl: Duration) -> Self {
+        self.status_ttl = Some(ttl);
+        self
+    }
+
+    /// 开启提前刷新：命中剩余存活时间不超过 `window` 的条目时，
+    /// 照常返回缓存值，同时提示引擎在后台重新计算。
+    ///
+    /// 每个条目只提示一次；需要配合 [`Engine::run_refresh_ahead`](crate::Engine::run_refresh_ahead)。
+    pub fn with_refresh_ahead(mut self, window: Duration) -> Self {
+        self.refresh_ahead = Some(window);
+        self
+    }
+
+    /// 立即清理所有分片中已过期的有效授权和状态，返回清理的有效授权条目数。
+    pub fn sweep_expired(&self) -> usize {
+        let now = Instant::now();
+        let mut removed = 0;
+        for shard_index in 0..self.shard_count {
+            let mut guard = self.write_shard(shard_index);
+            let expired = Self::prune_expired(&mut guard, now);
+            guard.tenant_statuses.prune(now);
+            guard.memberships.prune(now);
+            drop(guard);
+            ShardCounters::add(&self.counters[shard_index].expirations, expired);
+            removed += expired;
+        }
+        removed
+    }
+
+    /// 按调用方提供的节拍定期清理过期条目，`tick` 返回 `false` 时结束。
+    ///
+    /// 不绑定异步运行时，例如在 tokio 中可以传入
+    /// `|| async { interval.tick().await; true }` 并放到后台任务执行。
+    pub async fn run_sweeper<F, Fut>(&self, mut tick: F)
+    where
+        F: FnMut() -> Fut,
+        Fut: Future<Output = bool>,
+    {
+        while tick().await {
+            self.sweep_expired();
+        }
+    }
+
+    /// 返回各分片及汇总的统计快照。
+    ///
+    /// 计数器独立于分片锁更新，快照中的各项不保证在同一时刻读取。
+    pub fn stats(&self) -> MemoryCacheStats {
+        let mut stats = MemoryCacheStats::default();
+        for (shard_index, counters) in self.counters.iter().enumerate() {
+            let (entries, weight) = {
+                let guard = self.read_shard(shard_index);
+                (guard.entries.len(), guard.weight)
+            };
+            let mut shard = counters.snapshot(entries, self.shard_capacities[shard_index]);
+            shard.weight = weight;
+            shard.weight_limit = self.shard_weight_limit(shard_index);
+            stats.total.accumulate(&shard);
+            stats.shards.push(shard);
+        }
+        stats
+    }
+
+    /// 清零全部统计计数，不影响缓存内容。
+    pub fn reset_stats(&self) {
+        for counters in self.counters.iter() {
+            counters.reset();
+        }
+    }
+
     /// 使用容量和分片数量构建缓存实例。
     fn build(capacity: usize, shard_count: usize) -> Self {
         Self {
             shards: Arc::new(Self::new_shards(shard_count)),
+            counters: Arc::new(Self::new_counters(shard_count)),
             shard_capacities: Arc::new(Self::shard_capacities(capacity, shard_count)),
             shard_count,
             capacity,
+            weight_limit: None,
+            weigher: CacheWeigher::default(),
             ttl: None,
+            status_ttl: None,
+            refresh_ahead: None,
         }
     }
 
@@ -105,6 +610,11 @@ impl MemoryCache {
             .collect()
     }
 
+    /// 创建指定数量的分片计数器。
+    fn new_counters(shard_count: usize) -> Vec<ShardCounters> {
+        (0..shard_count).map(|_| ShardCounters::default()).collect()
+    }
+
     /// 按总容量计算每个分片的容量。
     fn shard_capacities(capacity: usize, shard_count: usize) -> Vec<usize> {
         let base = capacity / shard_count;
@@ -124,7 +634,7 @@ impl MemoryCache {
     }
 
     /// 根据缓存键定位分片下标。
-    fn shard_index(&self, key: &CacheKey) -> usize {
+    fn shard_index(&self, key: &impl Hash) -> usize {
         let mut hasher = std::collections::hash_map::DefaultHasher::new();
         key.hash(&mut hasher);
         (hasher.finish() as usize) % self.shard_count
@@ -148,7 +658,7 @@ impl MemoryCache {
 
     /// 从状态中删除指定缓存键及其 LRU 顺序记录。
     fn remove_key(state: &mut CacheState, key: &CacheKey) {
-        if state.entries.remove(key).is_some() {
+        if state.unlink(key) {
             state.order.retain(|existing| existing != key);
         }
     }
@@ -162,51 +672,19 @@ impl MemoryCache {
         state.order.push_back(key.clone());
     }
 
-    /// 判断缓存条目是否超过存活时间。
-    fn is_expired(entry: &CacheEntry, ttl: Duration, now: Instant) -> bool {
-        now.saturating_duration_since(entry.updated_at) > ttl
-    }
-
-    /// 清理已经过期的缓存条目。
-    fn prune_expired(state: &mut CacheState, ttl: Duration, now: Instant) {
-        state
-            .entries
-            .retain(|_, entry| !Self::is_expired(entry, ttl, now));
-        state.order.retain(|key| state.entries.contains_key(key));
-    }
-
-    /// 在分片容量超限时淘汰最久未使用条目。
-    fn evict_if_needed(state: &mut CacheState, shard_capacity: usize) {
-        if shard_capacity == 0 {
-            state.entries.clear();
-            state.order.clear();
-            return;
-        }
-        while state.entries.len() > shard_capacity {
-            if let Some(key) = state.order.pop_front() {
-                state.entries.remove(&key);
-            } else {
-                break;
-            }
-        }
-    }
-
-    /// 删除某个租户下的所有缓存条目。
-    fn remove_tenant_entries(state: &mut CacheState, tenant: &TenantId) {
-        state.entries.retain(|key, _| &key.tenant != tenant);
-        state.order.retain(|key| state.entries.contains_key(key));
+    /// 清理已经过期的缓存条目，返回删除数量。
+    fn prune_expired(state: &mut CacheState, now: Instant) -> usize {
+        state.remove_entries_where(|_, entry| entry.is_expired(now))
     }
-}
 
-#[async_trait]
-impl Cache for MemoryCache {
-    /// 从缓存读取有效授权，并在必要时刷新 LRU 顺序。
-    async fn get_effective_grants(
+    /// 读取有效授权并刷新 LRU 顺序，同时返回是否需要提前刷新；`with_meta` 为真时附带条目元数据。
+    fn lookup(
         &self,
         tenant: &TenantId,
         principal: &PrincipalId,
         config_signature: &str,
-    ) -> Option<Vec<EffectiveGrant>> {
+        with_meta: bool,
+    ) -> Option<(Vec<EffectiveGrant>, bool, Option<CacheEntryMeta>)> {
         if self.capacity == 0 {
             return None;
         }
@@ -214,34 +692,135 @@ impl Cache for MemoryCache {
         let key = Self::key(tenant, principal, config_signature);
         let now = Instant::now();
         let shard_index = self.shard_index(&key);
+        let counters = &self.counters[shard_index];
         {
             let guard = self.read_shard(shard_index);
             if let Some(entry) = guard.entries.get(&key) {
-                if let Some(ttl) = self.ttl
-                    && Self::is_expired(entry, ttl, now)
-                {
-                    // 下方的写锁会删除过期条目。
+                if entry.is_expired(now) || entry.wants_refresh(now, self.refresh_ahead) {
+                    // 下方的写锁会删除过期条目或记录刷新提示。
                 } else if guard.order.back().is_some_and(|last| last == &key) {
-                    return Some(entry.grants.clone());
+                    ShardCounters::incr(&counters.hits);
+                    let meta = with_meta.then(|| entry.meta(now));
+                    return Some((entry.grants.clone(), false, meta));
                 }
             } else {
+                ShardCounters::incr(&counters.misses);
                 return None;
             }
         }
 
         let mut guard = self.write_shard(shard_index);
-        if let Some(ttl) = self.ttl
-            && let Some(entry) = guard.entries.get(&key)
-            && Self::is_expired(entry, ttl, now)
+        if guard
+            .entries
+            .get(&key)
+            .is_some_and(|entry| entry.is_expired(now))
         {
             Self::remove_key(&mut guard, &key);
+            ShardCounters::incr(&counters.expirations);
+            ShardCounters::incr(&counters.misses);
+            return None;
+        }
+        let refresh_ahead = self.refresh_ahead;
+        let Some(entry) = guard.entries.get_mut(&key) else {
+            ShardCounters::incr(&counters.misses);
             return None;
+        };
+        let refresh = entry.wants_refresh(now, refresh_ahead);
+        entry.refresh_requested |= refresh;
+        let grants = entry.grants.clone();
+        let meta = with_meta.then(|| entry.meta(now));
+        Self::touch(&mut guard, &key);
+        ShardCounters::incr(&counters.hits);
+        Some((grants, refresh, meta))
+    }
+
+    /// 按全局存活时间和条目存活时间上限计算过期时间。
+    fn expires_at(&self, now: Instant, max_ttl: Option<Duration>) -> Option<Instant> {
+        let ttl = match (self.ttl, max_ttl) {
+            (Some(ttl), Some(max_ttl)) => Some(ttl.min(max_ttl)),
+            (ttl, max_ttl) => ttl.or(max_ttl),
+        };
+        ttl.map(|ttl| now + ttl)
+    }
+
+    /// 返回分片的权重上限，未开启权重限制时为 `None`。
+    ///
+    /// 总权重小于分片数量时每个分片至少分到 1，避免部分分片被静默禁用。
+    fn shard_weight_limit(&self, shard_index: usize) -> Option<usize> {
+        self.weight_limit.map(|limit| {
+            (limit / self.shard_count + usize::from(shard_index < limit % self.shard_count)).max(1)
+        })
+    }
+
+    /// 在分片容量或权重超限时淘汰最久未使用条目，返回淘汰数量。
+    fn evict_if_needed(
+        state: &mut CacheState,
+        shard_capacity: usize,
+        weight_limit: Option<usize>,
+    ) -> usize {
+        if shard_capacity == 0 {
+            return state.clear_entries();
         }
-        let grants = guard.entries.get(&key).map(|entry| entry.grants.clone());
-        if grants.is_some() {
-            Self::touch(&mut guard, &key);
+        let mut evicted = 0;
+        while state.entries.len() > shard_capacity
+            || weight_limit.is_some_and(|limit| state.weight > limit)
+        {
+            if let Some(key) = state.order.pop_front() {
+                evicted += usize::from(state.unlink(&key));
+            } else {
+                break;
+            }
         }
-        grants
+        evicted
+    }
+
+    /// 删除某个租户下的所有缓存条目，返回删除数量。
+    fn remove_tenant_entries(state: &mut CacheState, tenant: &TenantId) -> usize {
+        state.remove_entries_where(|key, _| &key.tenant == tenant)
+    }
+
+    /// 按状态存活时间和条目存活时间上限计算状态条目的过期时间。
+    ///
+    /// 未配置状态存活时间和条目存活时间时返回 `None`，此时不缓存状态。
+    fn status_expires_at(&self, now: Instant, max_ttl: Option<Duration>) -> Option<Instant> {
+        let ttl = self.status_ttl.or(self.ttl)?;
+        Some(now + max_ttl.map_or(ttl, |max_ttl| ttl.min(max_ttl)))
+    }
+}
+
+#[async_trait]
+impl Cache for MemoryCache {
+    /// 从缓存读取有效授权，并在必要时刷新 LRU 顺序。
+    async fn get_effective_grants(
+        &self,
+        tenant: &TenantId,
+        principal: &PrincipalId,
+        config_signature: &str,
+    ) -> Option<Vec<EffectiveGrant>> {
+        self.lookup(tenant, principal, config_signature, false)
+            .map(|(grants, _, _)| grants)
+    }
+
+    /// 读取有效授权，条目进入提前刷新窗口时提示刷新。
+    async fn get_effective_grants_with_refresh(
+        &self,
+        tenant: &TenantId,
+        principal: &PrincipalId,
+        config_signature: &str,
+    ) -> Option<(Vec<EffectiveGrant>, bool)> {
+        self.lookup(tenant, principal, config_signature, false)
+            .map(|(grants, refresh, _)| (grants, refresh))
+    }
+
+    /// 读取有效授权及其剩余存活时间和依赖角色。
+    async fn get_effective_grants_with_meta(
+        &self,
+        tenant: &TenantId,
+        principal: &PrincipalId,
+        config_signature: &str,
+    ) -> Option<(Vec<EffectiveGrant>, Option<CacheEntryMeta>)> {
+        self.lookup(tenant, principal, config_signature, true)
+            .map(|(grants, _, meta)| (grants, meta))
     }
 
     /// 写入有效授权并按分片容量执行淘汰。
@@ -251,6 +830,25 @@ impl Cache for MemoryCache {
         principal: &PrincipalId,
         config_signature: &str,
         grants: Vec<EffectiveGrant>,
+    ) {
+        self.set_effective_grants_with_meta(
+            tenant,
+            principal,
+            config_signature,
+            grants,
+            CacheEntryMeta::default(),
+        )
+        .await;
+    }
+
+    /// 写入有效授权，过期时间不晚于条目存活时间上限。
+    async fn set_effective_grants_with_meta(
+        &self,
+        tenant: &TenantId,
+        principal: &PrincipalId,
+        config_signature: &str,
+        grants: Vec<EffectiveGrant>,
+        meta: CacheEntryMeta,
     ) {
         if self.capacity == 0 {
             return;
@@ -259,59 +857,261 @@ impl Cache for MemoryCache {
         let key = Self::key(tenant, principal, config_signature);
         let now = Instant::now();
         let shard_index = self.shard_index(&key);
+        let counters = &self.counters[shard_index];
+        let weight = self.weigher.weigh(&grants);
+        let weight_limit = self.shard_weight_limit(shard_index);
         let mut guard = self.write_shard(shard_index);
 
-        if let Some(ttl) = self.ttl {
-            Self::prune_expired(&mut guard, ttl, now);
+        // 超过分片权重上限的条目不写入，只删除同一键下的旧条目，避免挤出整个分片。
+        if weight_limit.is_some_and(|limit| weight > limit) {
+            Self::remove_key(&mut guard, &key);
+            return;
+        }
+        if self.ttl.is_some() {
+            ShardCounters::add(&counters.expirations, Self::prune_expired(&mut guard, now));
         }
 
-        guard.entries.insert(
+        guard.insert_entry(
             key.clone(),
             CacheEntry {
+                expires_at: self.expires_at(now, meta.max_ttl),
+                weight,
                 grants,
-                updated_at: now,
+                roles: meta.roles,
+                refresh_requested: false,
             },
         );
         Self::touch(&mut guard, &key);
-        Self::evict_if_needed(&mut guard, self.shard_capacities[shard_index]);
+        ShardCounters::incr(&counters.inserts);
+        ShardCounters::add(
+            &counters.capacity_evictions,
+            Self::evict_if_needed(&mut guard, self.shard_capacities[shard_index], weight_limit),
+        );
+    }
+
+    /// 读取策略版本一致且未过期的租户状态。
+    async fn get_tenant_status(
+        &self,
+        tenant: &TenantId,
+        policy_version: &str,
+    ) -> Option<TenantStatus> {
+        self.get_tenant_status_with_meta(tenant, policy_version)
+            .await
+            .map(|(status, _)| status)
+    }
+
+    /// 读取租户状态及其剩余存活时间。
+    async fn get_tenant_status_with_meta(
+        &self,
+        tenant: &TenantId,
+        policy_version: &str,
+    ) -> Option<(TenantStatus, Option<StatusEntryMeta>)> {
+        if self.capacity == 0 {
+            return None;
+        }
+        let shard_index = self.shard_index(tenant);
+        let status = self.read_shard(shard_index).tenant_statuses.get(
+            tenant,
+            policy_version,
+            Instant::now(),
+        );
+        self.counters[shard_index]
+            .record_status(status)
+            .map(|(status, meta)| (status, Some(meta)))
     }
 
-    /// 失效某个租户主体的所有缓存条目。
+    /// 写入租户状态并按分片容量淘汰。
+    async fn set_tenant_status(
+        &self,
+        tenant: &TenantId,
+        policy_version: &str,
+        status: TenantStatus,
+    ) {
+        self.set_tenant_status_with_meta(
+            tenant,
+            policy_version,
+            status,
+            StatusEntryMeta::default(),
+        )
+        .await;
+    }
+
+    /// 写入租户状态，存活时间不超过元数据中的上限。
+    async fn set_tenant_status_with_meta(
+        &self,
+        tenant: &TenantId,
+        policy_version: &str,
+        status: TenantStatus,
+        meta: StatusEntryMeta,
+    ) {
+        if self.capacity == 0 {
+            return;
+        }
+        let now = Instant::now();
+        let Some(expires_at) = self.status_expires_at(now, meta.max_ttl) else {
+            return;
+        };
+        let shard_index = self.shard_index(tenant);
+        self.write_shard(shard_index).tenant_statuses.insert(
+            tenant.clone(),
+            StatusEntry {
+                status,
+                policy_version: policy_version.to_string(),
+                expires_at,
+            },
+            self.shard_capacities[shard_index],
+        );
+    }
+
+    /// 读取策略版本一致且未过期的主体成员状态。
+    async fn get_membership_status(
+        &self,
+        tenant: &TenantId,
+        principal: &PrincipalId,
+        policy_version: &str,
+    ) -> Option<MembershipStatus> {
+        self.get_membership_status_with_meta(tenant, principal, policy_version)
+            .await
+            .map(|(status, _)| status)
+    }
+
+    /// 读取主体成员状态及其剩余存活时间。
+    async fn get_membership_status_with_meta(
+        &self,
+        tenant: &TenantId,
+        principal: &PrincipalId,
+        policy_version: &str,
+    ) -> Option<(MembershipStatus, Option<StatusEntryMeta>)> {
+        if self.capacity == 0 {
+            return None;
+        }
+        let key = (tenant.clone(), principal.clone());
+        let shard_index = self.shard_index(&key);
+        let status =
+            self.read_shard(shard_index)
+                .memberships
+                .get(&key, policy_version, Instant::now());
+        self.counters[shard_index]
+            .record_status(status)
+            .map(|(status, meta)| (status, Some(meta)))
+    }
+
+    /// 写入主体成员状态并按分片容量淘汰。
+    async fn set_membership_status(
+        &self,
+        tenant: &TenantId,
+        principal: &PrincipalId,
+        policy_version: &str,
+        status: MembershipStatus,
+    ) {
+        self.set_membership_status_with_meta(
+            tenant,
+            principal,
+            policy_version,
+            status,
+            StatusEntryMeta::default(),
+        )
+        .await;
+    }
+
+    /// 写入主体成员状态，存活时间不超过元数据中的上限。
+    async fn set_membership_status_with_meta(
+        &self,
+        tenant: &TenantId,
+        principal: &PrincipalId,
+        policy_version: &str,
+        status: MembershipStatus,
+        meta: StatusEntryMeta,
+    ) {
+        if self.capacity == 0 {
+            return;
+        }
+        let now = Instant::now();
+        let Some(expires_at) = self.status_expires_at(now, meta.max_ttl) else {
+            return;
+        };
+        let key = (tenant.clone(), principal.clone());
+        let shard_index = self.shard_index(&key);
+        self.write_shard(shard_index).memberships.insert(
+            key,
+            StatusEntry {
+                status,
+                policy_version: policy_version.to_string(),
+                expires_at,
+            },
+            self.shard_capacities[shard_index],
+        );
+    }
+
+    /// 失效某个租户的缓存状态。
+    async fn invalidate_tenant_status(&self, tenant: &TenantId) {
+        self.write_shard(self.shard_index(tenant))
+            .tenant_statuses
+            .remove(tenant);
+    }
+
+    /// 失效某个主体的缓存成员状态。
+    async fn invalidate_membership(&self, tenant: &TenantId, principal: &PrincipalId) {
+        let key = (tenant.clone(), principal.clone());
+        self.write_shard(self.shard_index(&key))
+            .memberships
+            .remove(&key);
+    }
+
+    /// 失效某个租户主体的所有缓存条目和成员状态。
     async fn invalidate_principal(&self, tenant: &TenantId, principal: &PrincipalId) {
+        self.invalidate_membership(tenant, principal).await;
         for shard_index in 0..self.shard_count {
-            let mut guard = self.write_shard(shard_index);
-            guard
-                .entries
-                .retain(|key, _| &key.tenant != tenant || &key.principal != principal);
-            let retained_order = guard
-                .order
-                .iter()
-                .filter(|key| guard.entries.contains_key(*key))
-                .cloned()
-                .collect();
-            guard.order = retained_order;
+            let removed = self
+                .write_shard(shard_index)
+                .remove_entries_where(|key, _| {
+                    &key.tenant == tenant && &key.principal == principal
+                });
+            ShardCounters::add(&self.counters[shard_index].invalidations, removed);
         }
     }
 
-    /// 角色级缓存失效退化为租户级缓存失效。
-    async fn invalidate_role(&self, tenant: &TenantId, _role: &RoleId) {
-        self.invalidate_tenant(tenant).await;
+    /// 只失效依赖该角色的缓存条目，缺少角色信息的条目按租户保守清理。
+    async fn invalidate_role(&self, tenant: &TenantId, role: &RoleId) {
+        let index_key = (tenant.clone(), role.clone());
+        for shard_index in 0..self.shard_count {
+            let mut guard = self.write_shard(shard_index);
+            let mut keys: Vec<CacheKey> = guard
+                .role_index
+                .get(&index_key)
+                .map(|keys| keys.iter().cloned().collect())
+                .unwrap_or_default();
+            keys.extend(
+                guard
+                    .untracked
+                    .iter()
+                    .filter(|key| &key.tenant == tenant)
+                    .cloned(),
+            );
+            let removed = guard.remove_keys(keys);
+            ShardCounters::add(&self.counters[shard_index].invalidations, removed);
+        }
     }
 
-    /// 失效某个租户的所有缓存条目。
+    /// 失效某个租户的所有缓存条目、租户状态和成员状态。
     async fn invalidate_tenant(&self, tenant: &TenantId) {
         for shard_index in 0..self.shard_count {
             let mut guard = self.write_shard(shard_index);
-            Self::remove_tenant_entries(&mut guard, tenant);
+            let removed = Self::remove_tenant_entries(&mut guard, tenant);
+            ShardCounters::add(&self.counters[shard_index].invalidations, removed);
+            guard.tenant_statuses.remove_where(|key| key == tenant);
+            guard.memberships.remove_where(|(key, _)| key == tenant);
         }
     }
 
-    /// 清空所有分片中的缓存条目。
+    /// 清空所有分片中的缓存条目和状态。
     async fn invalidate_all(&self) {
         for shard_index in 0..self.shard_count {
             let mut guard = self.write_shard(shard_index);
-            guard.entries.clear();
-            guard.order.clear();
+            let removed = guard.clear_entries();
+            ShardCounters::add(&self.counters[shard_index].invalidations, removed);
+            guard.tenant_statuses.clear();
+            guard.memberships.clear();
         }
     }
 }
@@ -319,8 +1119,10 @@ impl Cache for MemoryCache {
 #[cfg(test)]
 mod tests {
     use super::MemoryCache;
-    use crate::cache::{Cache, EffectiveGrant};
-    use crate::{GrantScope, Permission, PrincipalId, RoleId, TenantId};
+    use crate::cache::{Cache, CacheEntryMeta, EffectiveGrant};
+    use crate::{
+        GrantScope, MembershipStatus, Permission, PrincipalId, RoleId, TenantId, TenantStatus,
+    };
     use futures::executor::block_on;
     use std::time::Duration;
 
@@ -434,6 +1236,26 @@ mod tests {
         assert!(block_on(cache.get_effective_grants(&tenant, &principal, "a")).is_none());
     }
 
+    #[test]
+    fn memory_cache_should_cap_entry_lifetime_by_meta() {
+        let (tenant, principal, role) = ids("max_ttl");
+        let cache = MemoryCache::new(8).with_ttl(Duration::from_secs(60));
+        block_on(cache.set_effective_grants_with_meta(
+            &tenant,
+            &principal,
+            "a",
+            vec![grant(role, "invoice:read")],
+            CacheEntryMeta {
+                max_ttl: Some(Duration::from_nanos(1)),
+                ..CacheEntryMeta::default()
+            },
+        ));
+
+        std::thread::sleep(Duration::from_millis(1));
+
+        assert!(block_on(cache.get_effective_grants(&tenant, &principal, "a")).is_none());
+    }
+
     #[test]
     fn memory_cache_should_evict_lru_entry() {
         let tenant = TenantId::parse("tenant_lru").expect("tenant");
@@ -451,4 +1273,329 @@ mod tests {
         assert!(block_on(cache.get_effective_grants(&tenant, &b, "a")).is_none());
         assert!(block_on(cache.get_effective_grants(&tenant, &c, "a")).is_some());
     }
+
+    #[test]
+    fn memory_cache_stats_should_count_hits_misses_and_removals() {
+        let (tenant, principal, role) = ids("stats");
+        let other = PrincipalId::parse("principal_stats_other").expect("principal");
+        let cache = MemoryCache::new(1);
+        let grants = vec![grant(role, "invoice:read")];
+
+        assert!(block_on(cache.get_effective_grants(&tenant, &principal, "a")).is_none());
+        block_on(cache.set_effective_grants(&tenant, &principal, "a", grants.clone()));
+        assert!(block_on(cache.get_effective_grants(&tenant, &principal, "a")).is_some());
+        block_on(cache.set_effective_grants(&tenant, &other, "a", grants));
+        assert!(block_on(cache.get_tenant_status(&tenant, "")).is_none());
+        block_on(cache.invalidate_tenant(&tenant));
+
+        let stats = cache.stats();
+        assert_eq!(stats.shards.len(), 1);
+        assert_eq!(stats.total, stats.shards[0]);
+        assert_eq!(
+            (stats.total.hits, stats.total.misses, stats.total.inserts),
+            (1, 1, 2)
+        );
+        assert_eq!(stats.total.capacity_evictions, 1);
+        assert_eq!(stats.total.invalidations, 1);
+        assert_eq!(stats.total.status_misses, 1);
+        assert_eq!((stats.total.entries, stats.total.capacity), (0, 1));
+        assert_eq!(stats.total.hit_rate(), Some(0.5));
+
+        cache.reset_stats();
+        assert_eq!(cache.stats().total.hits, 0);
+        assert_eq!(cache.stats().total.hit_rate(), None);
+    }
+
+    #[test]
+    fn memory_cache_stats_should_count_expirations() {
+        let (tenant, principal, role) = ids("stats_ttl");
+        let cache = MemoryCache::new(8).with_ttl(Duration::from_nanos(1));
+        block_on(cache.set_effective_grants(
+            &tenant,
+            &principal,
+            "a",
+            vec![grant(role, "invoice:read")],
+        ));
+
+        std::thread::sleep(Duration::from_millis(1));
+
+        assert!(block_on(cache.get_effective_grants(&tenant, &principal, "a")).is_none());
+        let stats = cache.stats();
+        assert_eq!((stats.total.expirations, stats.total.misses), (1, 1));
+        assert_eq!(stats.total.entries, 0);
+    }
+
+    #[test]
+    fn sweeper_should_remove_idle_expired_entries() {
+        let (tenant, principal, role) = ids("sweep");
+        let cache = MemoryCache::new(8).with_ttl(Duration::from_nanos(1));
+        block_on(cache.set_effective_grants(
+            &tenant,
+            &principal,
+            "a",
+            vec![grant(role, "invoice:read")],
+        ));
+        block_on(cache.set_tenant_status(&tenant, "", TenantStatus::Active));
+
+        std::thread::sleep(Duration::from_millis(1));
+        let mut ticks = 0;
+        block_on(cache.run_sweeper(|| {
+            ticks += 1;
+            std::future::ready(ticks <= 2)
+        }));
+
+        assert_eq!(ticks, 3);
+        let stats = cache.stats();
+        assert_eq!((stats.total.entries, stats.total.expirations), (0, 1));
+        assert_eq!(cache.sweep_expired(), 0);
+    }
+
+    #[test]
+    fn refresh_ahead_should_hint_once_per_entry() {
+        let (tenant, principal, role) = ids("refresh");
+        let cache = MemoryCache::new(8)
+            .with_ttl(Duration::from_secs(60))
+            .with_refresh_ahead(Duration::from_secs(61));
+        let lookup = || block_on(cache.get_effective_grants_with_refresh(&tenant, &principal, "a"));
+        block_on(cache.set_effective_grants(
+            &tenant,
+            &principal,
+            "a",
+            vec![grant(role.clone(), "invoice:read")],
+        ));
+
+        assert_eq!(lookup().map(|(_, refresh)| refresh), Some(true));
+        assert_eq!(lookup().map(|(_, refresh)| refresh), Some(false));
+
+        // 重新写入的条目可以再次提示。
+        block_on(cache.set_effective_grants(
+            &tenant,
+            &principal,
+            "a",
+            vec![grant(role, "invoice:read")],
+        ));
+        assert_eq!(lookup().map(|(_, refresh)| refresh), Some(true));
+    }
+
+    #[test]
+    fn weight_limit_should_evict_by_total_grants() {
+        let (tenant, _, role) = ids("weight");
+        let principal = |value: &str| PrincipalId::parse(value).expect("principal");
+        let grants = |count: usize| -> Vec<EffectiveGrant> {
+            (0..count)
+                .map(|index| grant(role.clone(), &format!("res_{index}:read")))
+                .collect()
+        };
+        let cache = MemoryCache::new(8)
+            .with_shards(1)
+            .with_weight_limit(10, super::CacheWeigher::Grants);
+
+        block_on(cache.set_effective_grants(&tenant, &principal("viewer"), "a", grants(2)));
+        block_on(cache.set_effective_grants(&tenant, &principal("editor"), "a", grants(5)));
+        block_on(cache.set_effective_grants(&tenant, &principal("admin"), "a", grants(6)));
+
+        assert!(block_on(cache.get_effective_grants(&tenant, &principal("viewer"), "a")).is_none());
+        assert!(block_on(cache.get_effective_grants(&tenant, &principal("editor"), "a")).is_none());
+        assert!(block_on(cache.get_effective_grants(&tenant, &principal("admin"), "a")).is_some());
+        let stats = cache.stats().total;
+        assert_eq!((stats.weight, stats.weight_limit), (6, Some(10)));
+        assert_eq!(stats.capacity_evictions, 2);
+
+        // 超过分片权重上限的条目不会被缓存，只替换掉同一键下的旧条目。
+        block_on(cache.set_effective_grants(&tenant, &principal("root"), "a", grants(1)));
+        block_on(cache.set_effective_grants(&tenant, &principal("root"), "a", grants(11)));
+        assert!(block_on(cache.get_effective_grants(&tenant, &principal("root"), "a")).is_none());
+        assert!(block_on(cache.get_effective_grants(&tenant, &principal("admin"), "a")).is_some());
+        assert_eq!(cache.stats().total.weight, 6);
+    }
+
+    #[test]
+    fn weight_limit_below_shard_count_should_keep_every_shard_enabled() {
+        let (tenant, principal, role) = ids("small_weight");
+        let cache = MemoryCache::new(8)
+            .with_shards(4)
+            .with_weight_limit(2, super::CacheWeigher::Grants);
+
+        assert!(
+            cache
+                .stats()
+                .shards
+                .iter()
+                .all(|shard| shard.weight_limit == Some(1))
+        );
+        block_on(cache.set_effective_grants(
+            &tenant,
+            &principal,
+            "a",
+            vec![grant(role, "invoice:read")],
+        ));
+        assert!(block_on(cache.get_effective_grants(&tenant, &principal, "a")).is_some());
+    }
+
+    #[test]
+    fn estimated_bytes_weigher_should_grow_with_grant_content() {
+        let (_, _, role) = ids("bytes");
+        let weigher = super::CacheWeigher::EstimatedBytes;
+        let short = vec![grant(role.clone(), "a:read")];
+        let long = vec![grant(role, "a_much_longer_resource_name:read")];
+
+        assert!(weigher.weigh(&long) > weigher.weigh(&short));
+        assert!(weigher.weigh(&[]) > 0);
+    }
+
+    #[test]
+    fn memory_cache_should_store_statuses_with_own_ttl() {
+        let (tenant, principal, role) = ids("status");
+        let cache = MemoryCache::new(8)
+            .with_ttl(Duration::from_secs(60))
+            .with_status_ttl(Duration::from_nanos(1));
+        block_on(cache.set_effective_grants(
+            &tenant,
+            &principal,
+            "a",
+            vec![grant(role, "invoice:read")],
+        ));
+        block_on(cache.set_tenant_status(&tenant, "", TenantStatus::Inactive));
+        block_on(cache.set_membership_status(&tenant, &principal, "", MembershipStatus::Active));
+
+        std::thread::sleep(Duration::from_millis(1));
+
+        assert!(block_on(cache.get_tenant_status(&tenant, "")).is_none());
+        assert!(block_on(cache.get_membership_status(&tenant, &principal, "")).is_none());
+        assert!(block_on(cache.get_effective_grants(&tenant, &principal, "a")).is_some());
+    }
+
+    #[test]
+    fn memory_cache_should_not_store_statuses_without_ttl() {
+        let (tenant, principal, role) = ids("status_no_ttl");
+        let cache = MemoryCache::new(8);
+        block_on(cache.set_effective_grants(
+            &tenant,
+            &principal,
+            "a",
+            vec![grant(role, "invoice:read")],
+        ));
+        block_on(cache.set_tenant_status(&tenant, "", TenantStatus::Active));
+        block_on(cache.set_membership_status(&tenant, &principal, "", MembershipStatus::Active));
+
+        assert!(block_on(cache.get_tenant_status(&tenant, "")).is_none());
+        assert!(block_on(cache.get_membership_status(&tenant, &principal, "")).is_none());
+        assert!(block_on(cache.get_effective_grants(&tenant, &principal, "a")).is_some());
+    }
+
+    #[test]
+    fn memory_cache_status_invalidation_should_keep_grants() {
+        let (tenant, principal, role) = ids("status_invalidate");
+        let cache = MemoryCache::new(8).with_status_ttl(Duration::from_secs(60));
+        block_on(cache.set_effective_grants(
+            &tenant,
+            &principal,
+            "a",
+            vec![grant(role, "invoice:read")],
+        ));
+        block_on(cache.set_tenant_status(&tenant, "", TenantStatus::Inactive));
+        block_on(cache.set_membership_status(&tenant, &principal, "", MembershipStatus::Active));
+
+        assert_eq!(
+            block_on(cache.get_tenant_status(&tenant, "")),
+            Some(TenantStatus::Inactive)
+        );
+        block_on(cache.invalidate_tenant_status(&tenant));
+        block_on(cache.invalidate_membership(&tenant, &principal));
+
+        assert!(block_on(cache.get_tenant_status(&tenant, "")).is_none());
+        assert!(block_on(cache.get_membership_status(&tenant, &principal, "")).is_none());
+        assert!(block_on(cache.get_effective_grants(&tenant, &principal, "a")).is_some());
+
+        block_on(cache.set_tenant_status(&tenant, "", TenantStatus::Active));
+        block_on(cache.set_membership_status(&tenant, &principal, "", MembershipStatus::Active));
+        block_on(cache.invalidate_tenant(&tenant));
+
+        assert!(block_on(cache.get_tenant_status(&tenant, "")).is_none());
+        assert!(block_on(cache.get_membership_status(&tenant, &principal, "")).is_none());
+    }
+
+    #[test]
+    fn memory_cache_should_evict_statuses_in_first_write_order() {
+        let cache = MemoryCache::new(2)
+            .with_shards(1)
+            .with_status_ttl(Duration::from_secs(60));
+        let tenant = |name: &str| ids(name).0;
+        let status = |name: &str| block_on(cache.get_tenant_status(&tenant(name), ""));
+        block_on(cache.set_tenant_status(&tenant("a"), "", TenantStatus::Active));
+        block_on(cache.set_tenant_status(&tenant("b"), "", TenantStatus::Active));
+        block_on(cache.set_tenant_status(&tenant("a"), "", TenantStatus::Inactive));
+        block_on(cache.set_tenant_status(&tenant("c"), "", TenantStatus::Active));
+
+        assert!(status("a").is_none());
+        assert!(status("b").is_some());
+        assert!(status("c").is_some());
+
+        block_on(cache.invalidate_tenant_status(&tenant("b")));
+        block_on(cache.set_tenant_status(&tenant("d"), "", TenantStatus::Active));
+
+        assert!(status("b").is_none());
+        assert!(status("c").is_some());
+        assert!(status("d").is_some());
+    }
+
+    #[test]
+    fn memory_cache_should_ignore_statuses_from_other_policy_versions() {
+        let (tenant, principal, _) = ids("status_version");
+        let cache = MemoryCache::new(8).with_status_ttl(Duration::from_secs(60));
+        block_on(cache.set_tenant_status(&tenant, "g0.t0", TenantStatus::Active));
+        block_on(cache.set_membership_status(
+            &tenant,
+            &principal,
+            "g0.t0",
+            MembershipStatus::Active,
+        ));
+
+        assert!(block_on(cache.get_tenant_status(&tenant, "g0.t1")).is_none());
+        assert!(block_on(cache.get_membership_status(&tenant, &principal, "g0.t1")).is_none());
+
+        block_on(cache.set_tenant_status(&tenant, "g0.t1", TenantStatus::Inactive));
+
+        assert_eq!(
+            block_on(cache.get_tenant_status(&tenant, "g0.t1")),
+            Some(TenantStatus::Inactive)
+        );
+        assert!(block_on(cache.get_tenant_status(&tenant, "g0.t0")).is_none());
+    }
+
+    #[test]
+    fn memory_cache_invalidate_role_should_only_evict_dependent_entries() {
+        let (tenant, principal, role) = ids("role_index");
+        let parent = RoleId::parse("role_parent").expect("role");
+        let other_principal = PrincipalId::parse("principal_other").expect("principal");
+        let other_role = RoleId::parse("role_other").expect("role");
+        let cache = MemoryCache::new(8);
+        let meta = |roles: Vec<RoleId>| CacheEntryMeta {
+            roles: Some(roles),
+            ..CacheEntryMeta::default()
+        };
+        block_on(cache.set_effective_grants_with_meta(
+            &tenant,
+            &principal,
+            "a",
+            vec![grant(role.clone(), "invoice:read")],
+            meta(vec![role.clone(), parent.clone()]),
+        ));
+        block_on(cache.set_effective_grants_with_meta(
+            &tenant,
+            &other_principal,
+            "a",
+            vec![grant(other_role.clone(), "order:read")],
+            meta(vec![other_role.clone()]),
+        ));
+
+        block_on(cache.invalidate_role(&tenant, &parent));
+
+        assert!(block_on(cache.get_effective_grants(&tenant, &principal, "a")).is_none());
+        assert!(block_on(cache.get_effective_grants(&tenant, &other_principal, "a")).is_some());
+
+        block_on(cache.invalidate_role(&tenant, &other_role));
+
+        assert!(block_on(cache.get_effective_grants(&tenant, &other_principal, "a")).is_none());
+    }
 }
@@ -1,3 +1,4 @@
+use crate::condition::{Condition, PermissionCondition};
 use crate::ids::{PrincipalId, RoleId, TenantId};
 use crate::permission::Permission;
 use crate::request::AuthSubject;
@@ -22,6 +23,7 @@ struct Inner {
     assignments: RwLock<HashMap<TenantId, HashMap<PrincipalId, Vec<RoleAssignment>>>>,
     role_permissions: RwLock<HashMap<TenantId, HashMap<RoleId, HashSet<Permission>>>>,
     parent_roles: RwLock<HashMap<TenantId, HashMap<RoleId, HashSet<RoleId>>>>,
+    conditions: RwLock<HashMap<TenantId, HashMap<RoleId, Vec<PermissionCondition>>>>,
 }
 
 /// 获取读锁，并在锁中毒时恢复内部值。
@@ -80,6 +82,21 @@ impl MemorySource {
             .push(RoleAssignment::new(role, scope));
     }
 
+    /// 添加完整配置的角色分配，例如拒绝效果的分配。
+    pub fn insert_role_assignment(
+        &self,
+        tenant: TenantId,
+        principal: PrincipalId,
+        assignment: RoleAssignment,
+    ) {
+        write_guard(&self.inner.assignments)
+            .entry(tenant)
+            .or_default()
+            .entry(principal)
+            .or_default()
+            .push(assignment);
+    }
+
     /// 为角色添加权限。
     pub fn add_role_permission(&self, tenant: TenantId, role: RoleId, permission: Permission) {
         write_guard(&self.inner.role_permissions)
@@ -90,6 +107,22 @@ impl MemorySource {
             .insert(permission);
     }
 
+    /// 为角色权限添加条件。
+    pub fn add_permission_condition(
+        &self,
+        tenant: TenantId,
+        role: RoleId,
+        permission: Permission,
+        condition: Condition,
+    ) {
+        write_guard(&self.inner.conditions)
+            .entry(tenant)
+            .or_default()
+            .entry(role)
+            .or_default()
+            .push(PermissionCondition::new(permission, condition));
+    }
+
     /// 添加直接父角色。
     pub fn add_parent_role(&self, tenant: TenantId, role: RoleId, parent: RoleId) {
         write_guard(&self.inner.parent_roles)
@@ -163,6 +196,19 @@ impl AuthorizationSource for MemorySource {
             .map(|parents| parents.iter().cloned().collect())
             .unwrap_or_default())
     }
+
+    /// 查询角色权限上的条件。
+    async fn role_permission_conditions(
+        &self,
+        tenant: &TenantId,
+        role: &RoleId,
+    ) -> std::result::Result<Vec<PermissionCondition>, crate::SourceError> {
+        Ok(read_guard(&self.inner.conditions)
+            .get(tenant)
+            .and_then(|conditions| conditions.get(role))
+            .cloned()
+            .unwrap_or_default())
+    }
 }
 
 #[cfg(test)]
@@ -1,4 +1,5 @@
 use crate::error::{Error, Result};
+use crate::implication::ActionImplications;
 use std::borrow::Borrow;
 use std::fmt;
 
@@ -24,6 +25,10 @@ fn validate_segment(value: &str, kind: &str, allow_slash: bool) -> Result<()> {
     if value == "*" {
         return Ok(());
     }
+    let value = match value.rsplit_once('/') {
+        Some((prefix, "*" | "**")) if allow_slash => prefix,
+        _ => value,
+    };
     for segment in value.split('/') {
         if segment.is_empty() {
             return Err(Error::InvalidPermission(format!(
@@ -127,6 +132,36 @@ define_permission_part!(
     "resource",
     true
 );
+impl Resource {
+    /// 返回资源是否以 `/*` 或 `/**` 结尾的前缀通配符。
+    pub fn is_prefix_wildcard(&self) -> bool {
+        self.prefix_wildcard().is_some()
+    }
+
+    /// 拆分前缀通配符，返回前缀以及是否同时覆盖前缀本身（`/**`）。
+    fn prefix_wildcard(&self) -> Option<(&str, bool)> {
+        match self.0.rsplit_once('/')? {
+            (prefix, "*") => Some((prefix, false)),
+            (prefix, "**") => Some((prefix, true)),
+            _ => None,
+        }
+    }
+
+    /// 返回前缀通配符是否覆盖 `required`。
+    ///
+    /// `billing/*` 覆盖 `billing` 下任意深度的资源，`billing/**` 还覆盖 `billing` 本身。
+    fn covers_by_prefix(&self, required: &Resource) -> bool {
+        let Some((prefix, inclusive)) = self.prefix_wildcard() else {
+            return false;
+        };
+        match required.0.strip_prefix(prefix) {
+            Some("") => inclusive,
+            Some(rest) => rest.starts_with('/'),
+            None => false,
+        }
+    }
+}
+
 define_permission_part!(
     /// 权限动作，例如 `read`。
     Action,
@@ -178,19 +213,53 @@ impl Permission {
 
     /// 返回该权限是否包含任意通配符片段。
     pub fn has_wildcard(&self) -> bool {
-        self.resource.is_wildcard() || self.action.is_wildcard()
+        self.resource.is_wildcard()
+            || self.resource.is_prefix_wildcard()
+            || self.action.is_wildcard()
     }
 
     /// 返回当前授予的权限是否覆盖 `required`。
+    ///
+    /// 资源前缀通配符（如 `billing/*`）不会通过该方法匹配，需要在引擎配置中单独启用。
     pub fn matches(&self, required: &Permission, enable_wildcard: bool) -> bool {
-        if !enable_wildcard && self.has_wildcard() {
-            return false;
+        PermissionMatcher {
+            wildcard: enable_wildcard,
+            resource_prefix: false,
+            implications: None,
         }
-        if !enable_wildcard {
-            return self == required;
-        }
-        let resource_matches = self.resource.is_wildcard() || self.resource == required.resource;
-        let action_matches = self.action.is_wildcard() || self.action == required.action;
+        .matches(self, required)
+    }
+}
+
+/// 按引擎配置匹配授予权限和所需权限。
+#[derive(Clone, Copy, Debug, Default)]
+pub(crate) struct PermissionMatcher<'a> {
+    /// 是否启用完整资源/动作通配符。
+    pub(crate) wildcard: bool,
+    /// 是否启用资源前缀通配符。
+    pub(crate) resource_prefix: bool,
+    /// 动作蕴含关系。
+    pub(crate) implications: Option<&'a ActionImplications>,
+}
+
+impl PermissionMatcher<'_> {
+    /// 返回 `granted` 是否覆盖 `required`。
+    pub(crate) fn matches(self, granted: &Permission, required: &Permission) -> bool {
+        let resource_matches = if granted.resource.is_wildcard() {
+            self.wildcard
+        } else if granted.resource.is_prefix_wildcard() {
+            self.resource_prefix && granted.resource.covers_by_prefix(&required.resource)
+        } else {
+            granted.resource == required.resource
+        };
+        let action_matches = if granted.action.is_wildcard() {
+            self.wildcard
+        } else {
+            granted.action == required.action
+                || self.implications.is_some_and(|implications| {
+                    implications.implies(&granted.action, &required.action)
+                })
+        };
         resource_matches && action_matches
     }
 }
@@ -232,7 +301,7 @@ impl<'de> serde::Deserialize<'de> for Permission {
 
 #[cfg(test)]
 mod tests {
-    use super::{Action, MAX_PERMISSION_PART_LEN, Permission, Resource};
+    use super::{Action, MAX_PERMISSION_PART_LEN, Permission, PermissionMatcher, Resource};
     use crate::Error;
 
     #[test]
@@ -275,11 +344,47 @@ mod tests {
     }
 
     #[test]
-    fn resource_should_not_support_partial_wildcard() {
-        let err = Resource::parse("billing/*").expect_err("must reject");
+    fn resource_should_only_accept_trailing_wildcard_segment() {
+        for value in ["billing/*", "billing/**", "billing/refund/*"] {
+            let resource = Resource::parse(value).expect("resource");
+            assert!(resource.is_prefix_wildcard());
+        }
+        for value in ["billing/*/line", "*/invoice", "bill*", "**", "billing/***"] {
+            let err = Resource::parse(value).expect_err(value);
+            assert!(matches!(err, Error::InvalidPermission(_)));
+        }
+        let err = Action::parse("read/*").expect_err("must reject");
         assert!(matches!(err, Error::InvalidPermission(_)));
     }
 
+    #[test]
+    fn resource_prefix_wildcard_should_match_only_when_enabled() {
+        let strict = PermissionMatcher::default();
+        let hierarchical = PermissionMatcher {
+            wildcard: false,
+            resource_prefix: true,
+            implications: None,
+        };
+        let descendants = Permission::parse("billing/*:read").expect("permission");
+        let inclusive = Permission::parse("billing/**:read").expect("permission");
+        let invoice = Permission::parse("billing/invoice:read").expect("permission");
+        let line = Permission::parse("billing/refund/line:read").expect("permission");
+        let billing = Permission::parse("billing:read").expect("permission");
+        let sibling = Permission::parse("billing_admin/user:read").expect("permission");
+
+        assert!(!strict.matches(&descendants, &invoice));
+        assert!(!descendants.matches(&invoice, true));
+        assert!(hierarchical.matches(&descendants, &invoice));
+        assert!(hierarchical.matches(&descendants, &line));
+        assert!(!hierarchical.matches(&descendants, &billing));
+        assert!(hierarchical.matches(&inclusive, &billing));
+        assert!(!hierarchical.matches(&inclusive, &sibling));
+        assert!(!hierarchical.matches(
+            &descendants,
+            &Permission::parse("billing/invoice:write").expect("permission")
+        ));
+    }
+
     #[cfg(feature = "serde")]
     #[test]
     fn serde_should_validate_permission() {
@@ -0,0 +1,100 @@
+use super::{PlatformGrantScope, PlatformPrincipalId, PlatformPrincipalStatus, PlatformRoleId};
+use crate::grant::ScopedGrant;
+use async_trait::async_trait;
+use std::time::Duration;
+
+/// 按平台主体和平台引擎配置缓存的有效授权。
+pub type PlatformEffectiveGrant = ScopedGrant<PlatformRoleId, PlatformGrantScope>;
+
+/// 写入平台有效授权时附带的缓存条目元数据。
+#[derive(Debug, Clone, Default, Eq, PartialEq)]
+pub struct PlatformCacheEntryMeta {
+    /// 条目的最长存活时间，由最早生效或失效的平台角色分配决定。
+    pub max_ttl: Option<Duration>,
+    /// 生成该条目时涉及的全部平台角色，包括继承展开得到的角色。
+    pub roles: Vec<PlatformRoleId>,
+}
+
+/// 平台有效授权的缓存接口。
+#[async_trait]
+pub trait PlatformCache: Send + Sync {
+    /// 按配置签名获取平台主体的缓存授权。
+    async fn get_effective_grants(
+        &self,
+        principal: &PlatformPrincipalId,
+        config_signature: &str,
+    ) -> Option<Vec<PlatformEffectiveGrant>>;
+
+    /// 按配置签名写入平台主体的缓存授权。
+    ///
+    /// 实现必须遵守 `meta.max_ttl`，无法保证时应忽略该次写入。
+    async fn set_effective_grants(
+        &self,
+        principal: &PlatformPrincipalId,
+        config_signature: &str,
+        grants: Vec<PlatformEffectiveGrant>,
+        meta: PlatformCacheEntryMeta,
+    );
+
+    /// 获取缓存的平台主体状态，默认不缓存。
+    async fn get_principal_status(
+        &self,
+        _principal: &PlatformPrincipalId,
+    ) -> Option<PlatformPrincipalStatus> {
+        None
+    }
+
+    /// 写入平台主体状态，非活跃状态同样缓存。
+    async fn set_principal_status(
+        &self,
+        _principal: &PlatformPrincipalId,
+        _status: PlatformPrincipalStatus,
+    ) {
+    }
+
+    /// 失效某个平台主体的缓存授权和状态。
+    async fn invalidate_principal(&self, principal: &PlatformPrincipalId);
+
+    /// 失效依赖某个平台角色的缓存授权。
+    ///
+    /// 实现可以只清理 [`PlatformCacheEntryMeta::roles`] 包含该角色的条目。
+    async fn invalidate_role(&self, role: &PlatformRoleId);
+
+    /// 失效全部平台缓存。
+    async fn invalidate_all(&self);
+}
+
+/// 空操作平台缓存实现。
+#[derive(Debug, Default, Clone, Copy)]
+pub struct NoPlatformCache;
+
+#[async_trait]
+impl PlatformCache for NoPlatformCache {
+    /// 始终返回缓存未命中。
+    async fn get_effective_grants(
+        &self,
+        _principal: &PlatformPrincipalId,
+        _config_signature: &str,
+    ) -> Option<Vec<PlatformEffectiveGrant>> {
+        None
+    }
+
+    /// 忽略缓存写入。
+    async fn set_effective_grants(
+        &self,
+        _principal: &PlatformPrincipalId,
+        _config_signature: &str,
+        _grants: Vec<PlatformEffectiveGrant>,
+        _meta: PlatformCacheEntryMeta,
+    ) {
+    }
+
+    /// 忽略主体级缓存失效。
+    async fn invalidate_principal(&self, _principal: &PlatformPrincipalId) {}
+
+    /// 忽略角色级缓存失效。
+    async fn invalidate_role(&self, _role: &PlatformRoleId) {}
+
+    /// 忽略全量缓存失效。
+    async fn invalidate_all(&self) {}
+}
@@ -0,0 +1,39 @@
+use super::{PlatformGrantScope, PlatformRoleId, TenantDataAccessScope};
+use crate::{AccessDecision, AccessTrace};
+
+/// 平台授权拒绝的原因。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PlatformDenyReason {
+    /// 平台主体不存在或未激活。
+    PrincipalInactive,
+    /// 没有可用于该目标的匹配权限授权。
+    PermissionMissing,
+    /// 匹配的授权只覆盖租户数据，不能访问平台自有资源。
+    ScopeIsTenantOnly,
+    /// 目标租户不在匹配授权的租户范围内。
+    TenantNotInSet,
+    /// 目标租户只有路径级授权，且不覆盖目标（租户级检查时总是如此）。
+    PathDenied,
+}
+
+/// 平台授权决策的轻量解释信息。
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PlatformAccessExplanation {
+    /// 最终决策。
+    pub decision: AccessDecision,
+    /// 当决策为 [`AccessDecision::Deny`] 时的拒绝原因。
+    pub reason: Option<PlatformDenyReason>,
+    /// 逐条平台授权追踪，仅由 `trace_*` 系列方法填充。
+    pub trace: Option<AccessTrace<PlatformRoleId, PlatformGrantScope>>,
+}
+
+/// 平台租户数据范围查询的解释信息。
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PlatformScopeExplanation {
+    /// 范围为 [`TenantDataAccessScope::None`] 时为 [`AccessDecision::Deny`]。
+    pub decision: AccessDecision,
+    /// 当决策为 [`AccessDecision::Deny`] 时的拒绝原因。
+    pub reason: Option<PlatformDenyReason>,
+    /// 可访问的租户数据范围。
+    pub scope: TenantDataAccessScope,
+}
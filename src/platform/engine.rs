@@ -1,12 +1,20 @@
 use super::{
-    PlatformAccessRequest, PlatformAuthorizationSource, PlatformGrantScope,
-    PlatformPrincipalStatus, PlatformRoleId, PlatformSubject, TenantDataAccessRequest,
-    TenantDataAccessScope, TenantDataScopeQuery, TenantScopedDataAccessRequest,
+    NoPlatformCache, PlatformAccessExplanation, PlatformAccessRequest, PlatformAuthorizationSource,
+    PlatformCache, PlatformCacheEntryMeta, PlatformDenyReason, PlatformEffectiveGrant,
+    PlatformGrantScope, PlatformPrincipalId, PlatformPrincipalStatus, PlatformRoleId,
+    PlatformScopeExplanation, PlatformSubject, TenantDataAccessRequest, TenantDataAccessScope,
+    TenantDataScopeQuery, TenantScopedDataAccessRequest,
+};
+use crate::clock::{Clock, SystemClock};
+use crate::permission::PermissionMatcher;
+use crate::role_hierarchy::{ExpandedRole, RoleHierarchy, expand_roles};
+use crate::{
+    AccessDecision, AccessTrace, ActionImplications, Error, Permission, Result, ScopePath, TenantId,
 };
-use crate::grant::ScopedGrant;
-use crate::role_hierarchy::{RoleHierarchy, expand_roles};
-use crate::{AccessDecision, Error, Permission, Result};
 use async_trait::async_trait;
+use std::collections::BTreeSet;
+use std::sync::Arc;
+use std::time::SystemTime;
 
 /// 平台引擎行为配置。
 #[derive(Debug, Clone, Eq, PartialEq)]
@@ -15,8 +23,12 @@ pub struct PlatformEngineConfig {
     pub enable_role_hierarchy: bool,
     /// 是否启用完整资源/动作通配符匹配。
     pub enable_wildcard: bool,
+    /// 是否启用 `billing/*`、`billing/**` 这类资源前缀通配符匹配。
+    pub enable_resource_prefix_wildcard: bool,
     /// 最大平台角色继承深度。
     pub max_role_depth: usize,
+    /// 动作蕴含关系，例如 `manage` 蕴含 `read`。
+    pub action_implications: ActionImplications,
 }
 
 impl Default for PlatformEngineConfig {
@@ -24,32 +36,76 @@ impl Default for PlatformEngineConfig {
         Self {
             enable_role_hierarchy: false,
             enable_wildcard: false,
+            enable_resource_prefix_wildcard: false,
             max_role_depth: 16,
+            action_implications: ActionImplications::default(),
+        }
+    }
+}
+
+impl PlatformEngineConfig {
+    /// 生成用于区分缓存条目的配置签名。
+    fn signature(&self) -> String {
+        format!(
+            "rh:{};wc:{};rp:{};depth:{};ai:{}",
+            u8::from(self.enable_role_hierarchy),
+            u8::from(self.enable_wildcard),
+            u8::from(self.enable_resource_prefix_wildcard),
+            self.max_role_depth,
+            self.action_implications.signature()
+        )
+    }
+
+    /// 返回按当前配置匹配权限的匹配器。
+    fn matcher(&self) -> PermissionMatcher<'_> {
+        PermissionMatcher {
+            wildcard: self.enable_wildcard,
+            resource_prefix: self.enable_resource_prefix_wildcard,
+            implications: Some(&self.action_implications),
         }
     }
 }
 
 /// 平台授权引擎。
 #[derive(Debug)]
-pub struct PlatformEngine<S> {
+pub struct PlatformEngine<S, C = NoPlatformCache> {
     source: S,
+    cache: C,
+    clock: Arc<dyn Clock>,
     config: PlatformEngineConfig,
+    config_signature: String,
 }
 
 /// [`PlatformEngine`] 构造器。
-pub struct PlatformEngineBuilder<S> {
+pub struct PlatformEngineBuilder<S, C = NoPlatformCache> {
     source: S,
+    cache: C,
+    clock: Arc<dyn Clock>,
     config: PlatformEngineConfig,
 }
 
-impl<S> PlatformEngineBuilder<S> {
+impl<S> PlatformEngineBuilder<S, NoPlatformCache> {
     /// 使用默认配置创建构造器。
     pub fn new(source: S) -> Self {
         Self {
             source,
+            cache: NoPlatformCache,
+            clock: Arc::new(SystemClock),
             config: PlatformEngineConfig::default(),
         }
     }
+}
+
+impl<S, C> PlatformEngineBuilder<S, C> {
+    /// 设置平台缓存实现。
+    pub fn cache<C2: PlatformCache>(self, cache: C2) -> PlatformEngineBuilder<S, C2> {
+        PlatformEngineBuilder {
+            source: self.source,
+            cache,
+            clock: self.clock,
+            config: self.config,
+        }
+    }
 
     /// 替换完整平台引擎配置。
     pub fn config(mut self, config: PlatformEngineConfig) -> Self {
@@ -69,24 +125,47 @@ impl<S> PlatformEngineBuilder<S> {
         self
     }
 
+    /// 启用或禁用资源前缀通配符匹配。
+    pub fn enable_resource_prefix_wildcard(mut self, on: bool) -> Self {
+        self.config.enable_resource_prefix_wildcard = on;
+        self
+    }
+
     /// 设置最大平台角色继承深度。
     pub fn max_role_depth(mut self, depth: usize) -> Self {
         self.config.max_role_depth = depth;
         self
     }
 
+    /// 设置动作蕴含关系。
+    pub fn action_implications(mut self, implications: ActionImplications) -> Self {
+        self.config.action_implications = implications;
+        self
+    }
+
+    /// 设置判断平台角色分配有效期使用的时钟。
+    pub fn clock(mut self, clock: impl Clock + 'static) -> Self {
+        self.clock = Arc::new(clock);
+        self
+    }
+
     /// 构建平台引擎。
-    pub fn build(self) -> PlatformEngine<S> {
+    pub fn build(self) -> PlatformEngine<S, C> {
+        let config_signature = self.config.signature();
         PlatformEngine {
             source: self.source,
+            cache: self.cache,
+            clock: self.clock,
             config: self.config,
+            config_signature,
         }
     }
 }
 
-impl<S> PlatformEngine<S>
+impl<S, C> PlatformEngine<S, C>
 where
     S: PlatformAuthorizationSource,
+    C: PlatformCache,
 {
     /// 返回当前平台引擎配置。
     pub fn config(&self) -> &PlatformEngineConfig {
@@ -95,24 +174,47 @@ where
 
     /// 检查平台主体是否可以访问平台自有资源。
     pub async fn can_platform(&self, request: PlatformAccessRequest) -> Result<AccessDecision> {
-        let grants = self
-            .matching_grants(&request.subject, &request.permission)
-            .await?;
-        let allowed = grants
-            .into_iter()
-            .any(|grant| matches!(grant.scope, PlatformGrantScope::Platform));
-        Ok(decision(allowed))
+        Ok(self.explain_platform(request).await?.decision)
     }
 
     /// 计算平台权限可访问的租户数据范围。
+    ///
+    /// 需要知道范围为空的原因时使用 [`PlatformEngine::explain_accessible_tenants`]。
     pub async fn accessible_tenants(
         &self,
         query: TenantDataScopeQuery,
     ) -> Result<TenantDataAccessScope> {
-        let grants = self
-            .matching_grants(&query.subject, &query.permission)
-            .await?;
-        TenantDataAccessScope::merge(grants.into_iter().map(|grant| grant.scope))
+        Ok(self.explain_accessible_tenants(query).await?.scope)
+    }
+
+    /// 解释平台权限可访问的租户数据范围。
+    ///
+    /// 范围为空时给出拒绝原因：主体未激活，或没有覆盖租户数据的匹配授权。
+    pub async fn explain_accessible_tenants(
+        &self,
+        query: TenantDataScopeQuery,
+    ) -> Result<PlatformScopeExplanation> {
+        if self.principal_status(&query.subject).await? != PlatformPrincipalStatus::Active {
+            return Ok(PlatformScopeExplanation {
+                decision: AccessDecision::Deny,
+                reason: Some(PlatformDenyReason::PrincipalInactive),
+                scope: TenantDataAccessScope::None,
+            });
+        }
+
+        let grants = self.effective_grants(&query.subject).await?;
+        let scope = TenantDataAccessScope::merge(
+            grants
+                .into_iter()
+                .filter(|grant| grant.matches_permission(&query.permission, self.config.matcher()))
+                .map(|grant| grant.scope),
+        )?;
+        let allowed = scope != TenantDataAccessScope::None;
+        Ok(PlatformScopeExplanation {
+            decision: decision(allowed),
+            reason: (!allowed).then_some(PlatformDenyReason::PermissionMissing),
+            scope,
+        })
     }
 
     /// 检查平台主体的租户级数据访问权。
@@ -120,13 +222,7 @@ where
         &self,
         request: TenantDataAccessRequest,
     ) -> Result<AccessDecision> {
-        let scope = self
-            .accessible_tenants(TenantDataScopeQuery {
-                subject: request.subject,
-                permission: request.permission,
-            })
-            .await?;
-        Ok(decision(scope.allows_tenant(&request.tenant)))
+        Ok(self.explain_access_tenant(request).await?.decision)
     }
 
     /// 检查平台主体的租户路径数据访问权。
@@ -134,69 +230,344 @@ where
         &self,
         request: TenantScopedDataAccessRequest,
     ) -> Result<AccessDecision> {
-        let scope = self
-            .accessible_tenants(TenantDataScopeQuery {
-                subject: request.subject,
-                permission: request.permission,
+        Ok(self.explain_access_tenant_scope(request).await?.decision)
+    }
+
+    /// 解释平台自有资源访问检查结果。
+    pub async fn explain_platform(
+        &self,
+        request: PlatformAccessRequest,
+    ) -> Result<PlatformAccessExplanation> {
+        self.explain_request(
+            &request.subject,
+            &request.permission,
+            PlatformTarget::Platform,
+            false,
+        )
+        .await
+    }
+
+    /// 解释租户级数据访问检查结果。
+    pub async fn explain_access_tenant(
+        &self,
+        request: TenantDataAccessRequest,
+    ) -> Result<PlatformAccessExplanation> {
+        self.explain_request(
+            &request.subject,
+            &request.permission,
+            PlatformTarget::Tenant(&request.tenant),
+            false,
+        )
+        .await
+    }
+
+    /// 解释租户路径数据访问检查结果。
+    pub async fn explain_access_tenant_scope(
+        &self,
+        request: TenantScopedDataAccessRequest,
+    ) -> Result<PlatformAccessExplanation> {
+        self.explain_request(
+            &request.subject,
+            &request.permission,
+            PlatformTarget::Path(&request.tenant, &request.target),
+            false,
+        )
+        .await
+    }
+
+    /// 解释平台自有资源访问检查结果，并附带逐条授权追踪。
+    pub async fn trace_platform(
+        &self,
+        request: PlatformAccessRequest,
+    ) -> Result<PlatformAccessExplanation> {
+        self.explain_request(
+            &request.subject,
+            &request.permission,
+            PlatformTarget::Platform,
+            true,
+        )
+        .await
+    }
+
+    /// 解释租户级数据访问检查结果，并附带逐条授权追踪。
+    pub async fn trace_access_tenant(
+        &self,
+        request: TenantDataAccessRequest,
+    ) -> Result<PlatformAccessExplanation> {
+        self.explain_request(
+            &request.subject,
+            &request.permission,
+            PlatformTarget::Tenant(&request.tenant),
+            true,
+        )
+        .await
+    }
+
+    /// 解释租户路径数据访问检查结果，并附带逐条授权追踪。
+    pub async fn trace_access_tenant_scope(
+        &self,
+        request: TenantScopedDataAccessRequest,
+    ) -> Result<PlatformAccessExplanation> {
+        self.explain_request(
+            &request.subject,
+            &request.permission,
+            PlatformTarget::Path(&request.tenant, &request.target),
+            true,
+        )
+        .await
+    }
+
+    /// 批量检查同一平台主体对多个租户和路径的数据访问权。
+    ///
+    /// 结果顺序与输入一致，详见 [`PlatformEngine::explain_many`]。
+    pub async fn check_many(
+        &self,
+        subject: PlatformSubject,
+        checks: Vec<(Permission, TenantId, Option<ScopePath>)>,
+    ) -> Result<Vec<AccessDecision>> {
+        Ok(self
+            .explain_many(subject, checks)
+            .await?
+            .into_iter()
+            .map(|explanation| explanation.decision)
+            .collect())
+    }
+
+    /// 批量解释同一平台主体对多个租户和路径的数据访问权。
+    ///
+    /// 主体状态和有效授权只解析一次，结果顺序与输入一致。目标路径为 `Some` 时等价于
+    /// [`PlatformEngine::explain_access_tenant_scope`]，为 `None` 时等价于
+    /// [`PlatformEngine::explain_access_tenant`]。平台授权不带权限条件，因此不需要请求上下文。
+    pub async fn explain_many(
+        &self,
+        subject: PlatformSubject,
+        checks: Vec<(Permission, TenantId, Option<ScopePath>)>,
+    ) -> Result<Vec<PlatformAccessExplanation>> {
+        if self.principal_status(&subject).await? != PlatformPrincipalStatus::Active {
+            return Ok(checks
+                .iter()
+                .map(|_| PlatformAccessExplanation {
+                    decision: AccessDecision::Deny,
+                    reason: Some(PlatformDenyReason::PrincipalInactive),
+                    trace: None,
+                })
+                .collect());
+        }
+
+        let grants = self.effective_grants(&subject).await?;
+        checks
+            .into_iter()
+            .map(|(permission, tenant, target)| {
+                let target = match &target {
+                    Some(target) => PlatformTarget::Path(&tenant, target),
+                    None => PlatformTarget::Tenant(&tenant),
+                };
+                self.explain_grants(&grants, &permission, target, false)
             })
-            .await?;
-        Ok(decision(
-            scope.allows_path(&request.tenant, &request.target),
-        ))
+            .collect()
     }
 
-    /// 过滤出主体拥有且匹配所需权限的有效授权。
-    async fn matching_grants(
+    /// 失效某个平台主体的缓存授权和状态，在主体状态或角色分配变更后调用。
+    pub async fn invalidate_platform_principal(&self, principal: &PlatformPrincipalId) {
+        self.cache.invalidate_principal(principal).await;
+    }
+
+    /// 失效依赖某个平台角色的缓存授权，在角色权限或父角色变更后调用。
+    pub async fn invalidate_platform_role(&self, role: &PlatformRoleId) {
+        self.cache.invalidate_role(role).await;
+    }
+
+    /// 失效全部平台缓存。
+    pub async fn invalidate_all(&self) {
+        self.cache.invalidate_all().await;
+    }
+
+    /// 解释单个平台权限检查。
+    async fn explain_request(
         &self,
         subject: &PlatformSubject,
-        required: &Permission,
-    ) -> Result<Vec<PlatformEffectiveGrant>> {
-        if self.source.platform_principal_status(subject).await? != PlatformPrincipalStatus::Active
-        {
-            return Ok(Vec::new());
+        permission: &Permission,
+        target: PlatformTarget<'_>,
+        trace: bool,
+    ) -> Result<PlatformAccessExplanation> {
+        if self.principal_status(subject).await? != PlatformPrincipalStatus::Active {
+            return Ok(PlatformAccessExplanation {
+                decision: AccessDecision::Deny,
+                reason: Some(PlatformDenyReason::PrincipalInactive),
+                trace: trace.then(AccessTrace::default),
+            });
         }
 
         let grants = self.effective_grants(subject).await?;
-        Ok(grants
-            .into_iter()
-            .filter(|grant| grant.matches_permission(required, self.config.enable_wildcard))
-            .collect())
+        self.explain_grants(&grants, permission, target, trace)
+    }
+
+    /// 根据已解析的有效授权解释单个平台权限检查。
+    fn explain_grants(
+        &self,
+        grants: &[PlatformEffectiveGrant],
+        permission: &Permission,
+        target: PlatformTarget<'_>,
+        trace: bool,
+    ) -> Result<PlatformAccessExplanation> {
+        let matching = || {
+            grants
+                .iter()
+                .filter(|grant| grant.matches_permission(permission, self.config.matcher()))
+        };
+        let (allowed, reason) = match target {
+            PlatformTarget::Platform => {
+                if matching().any(|grant| matches!(grant.scope, PlatformGrantScope::Platform)) {
+                    (true, None)
+                } else if matching().next().is_some() {
+                    (false, Some(PlatformDenyReason::ScopeIsTenantOnly))
+                } else {
+                    (false, Some(PlatformDenyReason::PermissionMissing))
+                }
+            }
+            PlatformTarget::Tenant(tenant) => {
+                let scope =
+                    TenantDataAccessScope::merge(matching().map(|grant| grant.scope.clone()))?;
+                let allowed = scope.allows_tenant(tenant);
+                (
+                    allowed,
+                    (!allowed).then(|| tenant_deny_reason(&scope, tenant)),
+                )
+            }
+            PlatformTarget::Path(tenant, path) => {
+                let scope =
+                    TenantDataAccessScope::merge(matching().map(|grant| grant.scope.clone()))?;
+                let allowed = scope.allows_path(tenant, path);
+                (
+                    allowed,
+                    (!allowed).then(|| tenant_deny_reason(&scope, tenant)),
+                )
+            }
+        };
+        let decision = decision(allowed);
+        let trace = trace.then(|| {
+            AccessTrace::collect(
+                grants,
+                permission,
+                self.config.matcher(),
+                |grant| target.covered_by(&grant.scope),
+                decision == AccessDecision::Deny,
+            )
+        });
+        Ok(PlatformAccessExplanation {
+            decision,
+            reason,
+            trace,
+        })
     }
 
-    /// 计算平台主体在当前配置下的有效授权。
+    /// 优先从缓存读取平台主体状态，未命中时回源并写入缓存。
+    async fn principal_status(&self, subject: &PlatformSubject) -> Result<PlatformPrincipalStatus> {
+        if let Some(status) = self.cache.get_principal_status(&subject.principal).await {
+            return Ok(status);
+        }
+        let status = self.source.platform_principal_status(subject).await?;
+        self.cache
+            .set_principal_status(&subject.principal, status)
+            .await;
+        Ok(status)
+    }
+
+    /// 计算平台主体在当前配置下的有效授权，优先读取缓存。
+    ///
+    /// 只有处于有效期内的平台角色分配参与计算。
     async fn effective_grants(
         &self,
         subject: &PlatformSubject,
     ) -> Result<Vec<PlatformEffectiveGrant>> {
+        if let Some(grants) = self
+            .cache
+            .get_effective_grants(&subject.principal, &self.config_signature)
+            .await
+        {
+            return Ok(grants);
+        }
+
+        let now = self.clock.now();
         let assignments = self.source.platform_role_assignments(subject).await?;
+        let mut next_change: Option<SystemTime> = None;
         let mut grants = Vec::new();
+        let mut involved_roles = BTreeSet::new();
         for assignment in assignments {
+            if let Some(at) = assignment.next_change_after(now) {
+                next_change = Some(next_change.map_or(at, |current| current.min(at)));
+            }
+            if !assignment.is_active_at(now) {
+                continue;
+            }
+
             let roles = if self.config.enable_role_hierarchy {
                 let hierarchy = PlatformRoleHierarchy { engine: self };
                 expand_roles(&hierarchy, assignment.role.clone()).await?
             } else {
-                vec![assignment.role]
+                vec![ExpandedRole {
+                    role: assignment.role,
+                    via: Vec::new(),
+                }]
             };
 
-            for role in roles {
+            for ExpandedRole { role, via } in roles {
+                involved_roles.insert(role.clone());
                 let permissions = self.source.platform_role_permissions(&role).await?;
                 grants.extend(permissions.into_iter().map(|permission| {
                     PlatformEffectiveGrant::new(role.clone(), permission, assignment.scope.clone())
+                        .with_via(via.clone())
                 }));
             }
         }
+
+        let meta = PlatformCacheEntryMeta {
+            max_ttl: next_change.map(|at| at.duration_since(now).unwrap_or_default()),
+            roles: involved_roles.into_iter().collect(),
+        };
+        self.cache
+            .set_effective_grants(
+                &subject.principal,
+                &self.config_signature,
+                grants.clone(),
+                meta,
+            )
+            .await;
         Ok(grants)
     }
 }
 
-struct PlatformRoleHierarchy<'a, S> {
-    engine: &'a PlatformEngine<S>,
+/// 平台权限检查的目标。
+#[derive(Clone, Copy)]
+enum PlatformTarget<'a> {
+    /// 平台自有资源。
+    Platform,
+    /// 租户级数据。
+    Tenant(&'a TenantId),
+    /// 租户内的目标路径。
+    Path(&'a TenantId, &'a ScopePath),
+}
+
+impl PlatformTarget<'_> {
+    /// 返回单个授权范围是否覆盖该目标。
+    fn covered_by(&self, scope: &PlatformGrantScope) -> bool {
+        match self {
+            Self::Platform => matches!(scope, PlatformGrantScope::Platform),
+            Self::Tenant(tenant) => scope.allows_tenant(tenant),
+            Self::Path(tenant, path) => scope.allows_path(tenant, path),
+        }
+    }
+}
+
+struct PlatformRoleHierarchy<'a, S, C> {
+    engine: &'a PlatformEngine<S, C>,
 }
 
 #[async_trait]
-impl<S> RoleHierarchy for PlatformRoleHierarchy<'_, S>
+impl<S, C> RoleHierarchy for PlatformRoleHierarchy<'_, S, C>
 where
     S: PlatformAuthorizationSource,
+    C: PlatformCache,
 {
     type Role = PlatformRoleId;
 
@@ -224,8 +595,18 @@ where
     }
 }
 
-/// 平台引擎内部计算出的有效授权。
-type PlatformEffectiveGrant = ScopedGrant<PlatformRoleId, PlatformGrantScope>;
+/// 返回租户数据访问被拒绝的原因。
+fn tenant_deny_reason(scope: &TenantDataAccessScope, tenant: &TenantId) -> PlatformDenyReason {
+    match scope {
+        TenantDataAccessScope::None => PlatformDenyReason::PermissionMissing,
+        TenantDataAccessScope::TenantPaths { entries }
+            if entries.iter().any(|entry| &entry.tenant == tenant) =>
+        {
+            PlatformDenyReason::PathDenied
+        }
+        _ => PlatformDenyReason::TenantNotInSet,
+    }
+}
 
 /// 将布尔允许结果转换为访问决策。
 fn decision(allowed: bool) -> AccessDecision {
@@ -239,7 +620,7 @@ fn decision(allowed: bool) -> AccessDecision {
 #[cfg(all(test, feature = "memory-store"))]
 mod tests {
     use super::*;
-    use crate::platform::{MemoryPlatformSource, TenantScopedRoots};
+    use crate::platform::{MemoryPlatformSource, PlatformRoleAssignment, TenantScopedRoots};
     use crate::{Permission, ScopePath, ScopeRoots, TenantId};
     use futures::executor::block_on;
 
@@ -334,6 +715,34 @@ mod tests {
         assert_eq!(scope, TenantDataAccessScope::None);
     }
 
+    #[test]
+    fn explain_accessible_tenants_should_report_why_the_scope_is_empty() {
+        let (source, subject) = active_source(PlatformGrantScope::platform(), "tenant:read");
+        let inactive =
+            PlatformSubject::new(PlatformPrincipalId::parse("ops_2").expect("principal"));
+        let engine = PlatformEngineBuilder::new(source).build();
+        let explain = |subject: &PlatformSubject, permission: &str| {
+            block_on(engine.explain_accessible_tenants(TenantDataScopeQuery {
+                subject: subject.clone(),
+                permission: Permission::parse(permission).expect("permission"),
+            }))
+            .expect("explanation")
+        };
+
+        let platform_only = explain(&subject, "tenant:read");
+        let missing = explain(&subject, "tenant:write");
+        let inactive = explain(&inactive, "tenant:read");
+
+        assert_eq!(platform_only.decision, AccessDecision::Deny);
+        assert_eq!(platform_only.scope, TenantDataAccessScope::None);
+        assert_eq!(
+            platform_only.reason,
+            Some(PlatformDenyReason::PermissionMissing)
+        );
+        assert_eq!(missing.reason, Some(PlatformDenyReason::PermissionMissing));
+        assert_eq!(inactive.reason, Some(PlatformDenyReason::PrincipalInactive));
+    }
+
     #[test]
     fn all_tenants_should_access_any_tenant_data() {
         let (source, subject) = active_source(PlatformGrantScope::all_tenants(), "tenant:read");
@@ -543,6 +952,170 @@ mod tests {
         assert_eq!(decision, AccessDecision::Allow);
     }
 
+    #[test]
+    fn trace_platform_should_report_tenant_only_grant_as_closest() {
+        let source = MemoryPlatformSource::new();
+        let subject = principal();
+        let child = role("child");
+        let parent = role("parent");
+        source.set_principal_status(subject.principal.clone(), PlatformPrincipalStatus::Active);
+        source.add_role_assignment(
+            subject.principal.clone(),
+            child.clone(),
+            PlatformGrantScope::all_tenants(),
+        );
+        source.add_parent_role(child.clone(), parent.clone());
+        source.add_role_permission(
+            parent.clone(),
+            Permission::parse("platform/role:update").expect("permission"),
+        );
+        let engine = PlatformEngineBuilder::new(source)
+            .enable_role_hierarchy(true)
+            .build();
+        let platform_request = PlatformAccessRequest {
+            subject: subject.clone(),
+            permission: Permission::parse("platform/role:update").expect("permission"),
+        };
+
+        let denied = block_on(engine.trace_platform(platform_request)).expect("explanation");
+        let trace = denied.trace.expect("trace");
+        assert_eq!(denied.decision, AccessDecision::Deny);
+        assert!(trace.matched.is_empty());
+        assert_eq!(trace.closest.len(), 1);
+        assert_eq!(trace.closest[0].role, parent);
+        assert_eq!(trace.closest[0].via, vec![child]);
+
+        let allowed = block_on(engine.trace_access_tenant(TenantDataAccessRequest {
+            subject,
+            permission: Permission::parse("platform/role:update").expect("permission"),
+            tenant: tenant("tenant_a"),
+        }))
+        .expect("explanation");
+        assert_eq!(allowed.decision, AccessDecision::Allow);
+        assert_eq!(allowed.trace.expect("trace").matched.len(), 1);
+    }
+
+    #[test]
+    fn explain_should_report_platform_deny_reasons() {
+        let (source, subject) = active_source(
+            PlatformGrantScope::tenant_paths(vec![TenantScopedRoots::new(
+                tenant("tenant_a"),
+                ScopeRoots::new(vec![path("agent/1")]).expect("roots"),
+            )])
+            .expect("scope"),
+            "tenant/order:read",
+        );
+        let engine = PlatformEngineBuilder::new(source.clone()).build();
+        let read = Permission::parse("tenant/order:read").expect("permission");
+        let reason = |explanation: Result<PlatformAccessExplanation>| {
+            let explanation = explanation.expect("explanation");
+            assert_eq!(
+                explanation.decision == AccessDecision::Allow,
+                explanation.reason.is_none()
+            );
+            explanation.reason
+        };
+
+        assert_eq!(
+            reason(block_on(engine.explain_platform(PlatformAccessRequest {
+                subject: subject.clone(),
+                permission: read.clone(),
+            }))),
+            Some(PlatformDenyReason::ScopeIsTenantOnly)
+        );
+        assert_eq!(
+            reason(block_on(engine.explain_platform(PlatformAccessRequest {
+                subject: subject.clone(),
+                permission: Permission::parse("platform/role:update").expect("permission"),
+            }))),
+            Some(PlatformDenyReason::PermissionMissing)
+        );
+        assert_eq!(
+            reason(block_on(engine.explain_access_tenant(
+                TenantDataAccessRequest {
+                    subject: subject.clone(),
+                    permission: read.clone(),
+                    tenant: tenant("tenant_a"),
+                }
+            ))),
+            Some(PlatformDenyReason::PathDenied)
+        );
+        assert_eq!(
+            reason(block_on(engine.explain_access_tenant_scope(
+                TenantScopedDataAccessRequest {
+                    subject: subject.clone(),
+                    permission: read.clone(),
+                    tenant: tenant("tenant_b"),
+                    target: path("agent/1"),
+                }
+            ))),
+            Some(PlatformDenyReason::TenantNotInSet)
+        );
+        assert_eq!(
+            reason(block_on(engine.explain_access_tenant_scope(
+                TenantScopedDataAccessRequest {
+                    subject: subject.clone(),
+                    permission: read.clone(),
+                    tenant: tenant("tenant_a"),
+                    target: path("agent/1/order/7"),
+                }
+            ))),
+            None
+        );
+
+        source.set_principal_status(subject.principal.clone(), PlatformPrincipalStatus::Inactive);
+        assert_eq!(
+            reason(block_on(engine.explain_access_tenant(
+                TenantDataAccessRequest {
+                    subject,
+                    permission: read,
+                    tenant: tenant("tenant_a"),
+                }
+            ))),
+            Some(PlatformDenyReason::PrincipalInactive)
+        );
+    }
+
+    #[test]
+    fn explain_many_should_report_reasons_in_input_order() {
+        let (source, subject) = active_source(
+            PlatformGrantScope::tenants(vec![tenant("tenant_a")]).expect("scope"),
+            "tenant/order:read",
+        );
+        let engine = PlatformEngineBuilder::new(source.clone()).build();
+        let read = Permission::parse("tenant/order:read").expect("permission");
+        let checks = vec![
+            (read.clone(), tenant("tenant_a"), Some(path("agent/1"))),
+            (read.clone(), tenant("tenant_b"), None),
+            (
+                Permission::parse("tenant/order:delete").expect("permission"),
+                tenant("tenant_a"),
+                None,
+            ),
+        ];
+
+        let reasons: Vec<_> = block_on(engine.explain_many(subject.clone(), checks.clone()))
+            .expect("explanations")
+            .into_iter()
+            .map(|explanation| explanation.reason)
+            .collect();
+        assert_eq!(
+            reasons,
+            vec![
+                None,
+                Some(PlatformDenyReason::TenantNotInSet),
+                Some(PlatformDenyReason::PermissionMissing),
+            ]
+        );
+
+        source.set_principal_status(subject.principal.clone(), PlatformPrincipalStatus::Inactive);
+        let explanations = block_on(engine.explain_many(subject, checks)).expect("explanations");
+        assert!(explanations.iter().all(|explanation| {
+            explanation.decision == AccessDecision::Deny
+                && explanation.reason == Some(PlatformDenyReason::PrincipalInactive)
+        }));
+    }
+
     #[test]
     fn role_hierarchy_should_detect_cycle() {
         let source = MemoryPlatformSource::new();
@@ -603,6 +1176,177 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn check_many_should_return_decisions_in_input_order() {
+        let (source, subject) = active_source(
+            PlatformGrantScope::tenant_paths(vec![TenantScopedRoots::new(
+                tenant("tenant_a"),
+                ScopeRoots::new(vec![path("agent/1")]).expect("roots"),
+            )])
+            .expect("scope"),
+            "tenant/order:read",
+        );
+        let engine = PlatformEngineBuilder::new(source).build();
+        let read = Permission::parse("tenant/order:read").expect("permission");
+        let decisions = block_on(engine.check_many(
+            subject,
+            vec![
+                (
+                    read.clone(),
+                    tenant("tenant_a"),
+                    Some(path("agent/1/store/2")),
+                ),
+                (read.clone(), tenant("tenant_a"), Some(path("agent/2"))),
+                (read.clone(), tenant("tenant_a"), None),
+                (read, tenant("tenant_b"), Some(path("agent/1"))),
+                (
+                    Permission::parse("tenant/order:delete").expect("permission"),
+                    tenant("tenant_a"),
+                    Some(path("agent/1")),
+                ),
+            ],
+        ))
+        .expect("decisions");
+
+        assert_eq!(
+            decisions,
+            vec![
+                AccessDecision::Allow,
+                AccessDecision::Deny,
+                AccessDecision::Deny,
+                AccessDecision::Deny,
+                AccessDecision::Deny,
+            ]
+        );
+    }
+
+    #[test]
+    fn expired_platform_assignment_should_deny() {
+        let source = MemoryPlatformSource::new();
+        let subject = principal();
+        let support = role("support");
+        let now = std::time::SystemTime::now();
+        source.set_principal_status(subject.principal.clone(), PlatformPrincipalStatus::Active);
+        source.insert_role_assignment(
+            subject.principal.clone(),
+            PlatformRoleAssignment::new(support.clone(), PlatformGrantScope::platform())
+                .with_expires_at(now - std::time::Duration::from_secs(1)),
+        );
+        source.add_role_permission(
+            support,
+            Permission::parse("platform/role:update").expect("permission"),
+        );
+        let engine = PlatformEngineBuilder::new(source).build();
+        let decision = block_on(engine.can_platform(PlatformAccessRequest {
+            subject,
+            permission: Permission::parse("platform/role:update").expect("permission"),
+        }))
+        .expect("decision");
+
+        assert_eq!(decision, AccessDecision::Deny);
+    }
+
+    #[test]
+    fn action_implications_should_apply_to_platform_grants() {
+        let (source, subject) =
+            active_source(PlatformGrantScope::platform(), "platform/role:manage");
+        let engine = PlatformEngineBuilder::new(source)
+            .action_implications(
+                ActionImplications::new()
+                    .imply(
+                        crate::Action::parse("manage").expect("action"),
+                        [crate::Action::parse("update").expect("action")],
+                    )
+                    .expect("implications"),
+            )
+            .build();
+        let decision = block_on(engine.can_platform(PlatformAccessRequest {
+            subject,
+            permission: Permission::parse("platform/role:update").expect("permission"),
+        }))
+        .expect("decision");
+
+        assert_eq!(decision, AccessDecision::Allow);
+    }
+
+    #[cfg(feature = "memory-cache")]
+    #[test]
+    fn platform_cache_should_serve_until_invalidated() {
+        let source = MemoryPlatformSource::new();
+        let subject = principal();
+        let child = role("child");
+        let parent = role("parent");
+        source.set_principal_status(subject.principal.clone(), PlatformPrincipalStatus::Active);
+        source.add_role_assignment(
+            subject.principal.clone(),
+            child.clone(),
+            PlatformGrantScope::platform(),
+        );
+        source.add_parent_role(child, parent.clone());
+        let engine = PlatformEngineBuilder::new(source.clone())
+            .enable_role_hierarchy(true)
+            .cache(crate::platform::MemoryPlatformCache::new(16))
+            .build();
+        let request = |permission: &str| PlatformAccessRequest {
+            subject: subject.clone(),
+            permission: Permission::parse(permission).expect("permission"),
+        };
+
+        let before =
+            block_on(engine.can_platform(request("platform/role:update"))).expect("decision");
+        assert_eq!(before, AccessDecision::Deny);
+
+        // 缓存命中期间看不到父角色新增的权限，失效父角色后重新计算。
+        source.add_role_permission(
+            parent.clone(),
+            Permission::parse("platform/role:update").expect("permission"),
+        );
+        let cached =
+            block_on(engine.can_platform(request("platform/role:update"))).expect("decision");
+        assert_eq!(cached, AccessDecision::Deny);
+        block_on(engine.invalidate_platform_role(&parent));
+        let refreshed =
+            block_on(engine.can_platform(request("platform/role:update"))).expect("decision");
+        assert_eq!(refreshed, AccessDecision::Allow);
+
+        // 主体状态同样被缓存，失效主体后才读取新状态。
+        source.set_principal_status(subject.principal.clone(), PlatformPrincipalStatus::Inactive);
+        let cached =
+            block_on(engine.can_platform(request("platform/role:update"))).expect("decision");
+        assert_eq!(cached, AccessDecision::Allow);
+        block_on(engine.invalidate_platform_principal(&subject.principal));
+        let refreshed =
+            block_on(engine.can_platform(request("platform/role:update"))).expect("decision");
+        assert_eq!(refreshed, AccessDecision::Deny);
+    }
+
+    #[cfg(feature = "memory-cache")]
+    #[test]
+    fn platform_cache_should_isolate_config_signatures() {
+        let (source, subject) = active_source(PlatformGrantScope::platform(), "platform/role:*");
+        let cache = crate::platform::MemoryPlatformCache::new(16);
+        let strict_engine = PlatformEngineBuilder::new(source.clone())
+            .cache(cache.clone())
+            .build();
+        let wildcard_engine = PlatformEngineBuilder::new(source)
+            .enable_wildcard(true)
+            .cache(cache.clone())
+            .build();
+        let request = PlatformAccessRequest {
+            subject,
+            permission: Permission::parse("platform/role:update").expect("permission"),
+        };
+
+        let strict = block_on(strict_engine.can_platform(request.clone())).expect("decision");
+        let wildcard = block_on(wildcard_engine.can_platform(request)).expect("decision");
+
+        assert_eq!(strict, AccessDecision::Deny);
+        assert_eq!(wildcard, AccessDecision::Allow);
+        assert_eq!(cache.len(), 2);
+        block_on(strict_engine.invalidate_all());
+        assert!(cache.is_empty());
+    }
+
     #[test]
     fn wildcard_should_require_config_flag() {
         let (source, subject) = active_source(PlatformGrantScope::platform(), "platform/role:*");
@@ -621,4 +1365,30 @@ mod tests {
         assert_eq!(strict, AccessDecision::Deny);
         assert_eq!(wildcard, AccessDecision::Allow);
     }
+
+    #[test]
+    fn resource_prefix_wildcard_should_require_its_own_config_flag() {
+        let (source, subject) = active_source(PlatformGrantScope::platform(), "billing/*:read");
+        let wildcard_engine = PlatformEngineBuilder::new(source.clone())
+            .enable_wildcard(true)
+            .build();
+        let prefix_engine = PlatformEngineBuilder::new(source)
+            .enable_resource_prefix_wildcard(true)
+            .build();
+        let request = |permission: &str| PlatformAccessRequest {
+            subject: subject.clone(),
+            permission: Permission::parse(permission).expect("permission"),
+        };
+
+        let wildcard = block_on(wildcard_engine.can_platform(request("billing/invoice:read")))
+            .expect("decision");
+        let prefix = block_on(prefix_engine.can_platform(request("billing/invoice:read")))
+            .expect("decision");
+        let other_action = block_on(prefix_engine.can_platform(request("billing/invoice:delete")))
+            .expect("decision");
+
+        assert_eq!(wildcard, AccessDecision::Deny);
+        assert_eq!(prefix, AccessDecision::Allow);
+        assert_eq!(other_action, AccessDecision::Deny);
+    }
 }
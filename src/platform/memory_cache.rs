@@ -0,0 +1,364 @@
+use super::cache::{PlatformCache, PlatformCacheEntryMeta, PlatformEffectiveGrant};
+use super::{PlatformPrincipalId, PlatformPrincipalStatus, PlatformRoleId};
+use crate::sync::lock;
+use async_trait::async_trait;
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// 平台有效授权和平台主体状态的内存缓存。
+///
+/// 平台主体数量通常远少于租户主体，因此只使用一把锁；有效授权按 LRU 淘汰，
+/// 主体状态按首次写入顺序淘汰，两者各自受容量限制。写入不扫描过期条目，
+/// 过期条目读取时视为未命中并删除，或在超出容量时被淘汰。
+///
+/// 未配置存活时间时主体状态长期有效，停用平台主体后必须调用
+/// [`PlatformEngine::invalidate_platform_principal`](super::PlatformEngine::invalidate_platform_principal)。
+#[derive(Debug, Clone)]
+pub struct MemoryPlatformCache {
+    state: Arc<Mutex<PlatformCacheState>>,
+    capacity: usize,
+    ttl: Option<Duration>,
+    status_ttl: Option<Duration>,
+}
+
+/// 缓存内容，`order` 和 `status_order` 中可能残留已删除的键，超出一定比例时才压缩。
+#[derive(Debug, Default)]
+struct PlatformCacheState {
+    entries: HashMap<PlatformCacheKey, PlatformCacheEntry>,
+    order: VecDeque<PlatformCacheKey>,
+    statuses: HashMap<PlatformPrincipalId, (PlatformPrincipalStatus, Option<Instant>)>,
+    status_order: VecDeque<PlatformPrincipalId>,
+}
+
+/// 缓存条目的唯一键。
+#[derive(Debug, Clone, Hash, Eq, PartialEq)]
+struct PlatformCacheKey {
+    principal: PlatformPrincipalId,
+    config_signature: String,
+}
+
+/// 缓存条目及其过期时间。
+#[derive(Debug, Clone)]
+struct PlatformCacheEntry {
+    grants: Vec<PlatformEffectiveGrant>,
+    expires_at: Option<Instant>,
+    roles: Vec<PlatformRoleId>,
+}
+
+impl PlatformCacheState {
+    /// 删除满足条件的缓存条目，并同步 LRU 顺序。
+    fn remove_entries_where(
+        &mut self,
+        predicate: impl Fn(&PlatformCacheKey, &PlatformCacheEntry) -> bool,
+    ) {
+        self.entries.retain(|key, entry| !predicate(key, entry));
+        self.order.retain(|key| self.entries.contains_key(key));
+    }
+
+    /// 删除单个缓存条目，LRU 顺序中的键留待淘汰或压缩时跳过。
+    fn remove_entry(&mut self, key: &PlatformCacheKey) {
+        if self.entries.remove(key).is_some() && self.order.len() > self.entries.len() * 2 + 16 {
+            self.order.retain(|key| self.entries.contains_key(key));
+        }
+    }
+
+    /// 删除某个主体的状态，写入顺序中的键留待淘汰或压缩时跳过。
+    fn remove_status(&mut self, principal: &PlatformPrincipalId) {
+        if self.statuses.remove(principal).is_some()
+            && self.status_order.len() > self.statuses.len() * 2 + 16
+        {
+            self.status_order
+                .retain(|key| self.statuses.contains_key(key));
+        }
+    }
+}
+
+/// 判断过期时间是否已经过去。
+fn is_expired(expires_at: Option<Instant>, now: Instant) -> bool {
+    expires_at.is_some_and(|at| now > at)
+}
+
+impl MemoryPlatformCache {
+    /// 使用给定容量创建缓存。
+    ///
+    /// 容量为零时禁用缓存。
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            state: Arc::new(Mutex::new(PlatformCacheState::default())),
+            capacity,
+            ttl: None,
+            status_ttl: None,
+        }
+    }
+
+    /// 配置有效授权和主体状态的存活时间。
+    pub fn with_ttl(mut self, ttl: Duration) -> Self {
+        self.ttl = Some(ttl);
+        self
+    }
+
+    /// 单独配置主体状态的存活时间，未配置时沿用 [`MemoryPlatformCache::with_ttl`]。
+    pub fn with_status_ttl(mut self, ttl: Duration) -> Self {
+        self.status_ttl = Some(ttl);
+        self
+    }
+
+    /// 返回当前缓存的有效授权条目数，包括尚未清理的过期条目。
+    pub fn len(&self) -> usize {
+        lock(&self.state).entries.len()
+    }
+
+    /// 返回是否没有缓存的有效授权条目。
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// 按全局存活时间和条目存活时间上限计算过期时间。
+    fn expires_at(&self, now: Instant, max_ttl: Option<Duration>) -> Option<Instant> {
+        let ttl = match (self.ttl, max_ttl) {
+            (Some(ttl), Some(max_ttl)) => Some(ttl.min(max_ttl)),
+            (ttl, max_ttl) => ttl.or(max_ttl),
+        };
+        ttl.map(|ttl| now + ttl)
+    }
+}
+
+#[async_trait]
+impl PlatformCache for MemoryPlatformCache {
+    /// 读取未过期的有效授权并刷新 LRU 顺序。
+    async fn get_effective_grants(
+        &self,
+        principal: &PlatformPrincipalId,
+        config_signature: &str,
+    ) -> Option<Vec<PlatformEffectiveGrant>> {
+        if self.capacity == 0 {
+            return None;
+        }
+        let key = PlatformCacheKey {
+            principal: principal.clone(),
+            config_signature: config_signature.to_string(),
+        };
+        let mut state = lock(&self.state);
+        let entry = state.entries.get(&key)?;
+        if is_expired(entry.expires_at, Instant::now()) {
+            state.remove_entry(&key);
+            return None;
+        }
+        let grants = entry.grants.clone();
+        if state.order.back() != Some(&key) {
+            state.order.retain(|existing| existing != &key);
+            state.order.push_back(key);
+        }
+        Some(grants)
+    }
+
+    /// 写入有效授权，过期时间不晚于条目存活时间上限，超出容量时淘汰最久未使用条目。
+    ///
+    /// 覆盖已有条目时保留其 LRU 位置，下次读取时再移到队尾。
+    async fn set_effective_grants(
+        &self,
+        principal: &PlatformPrincipalId,
+        config_signature: &str,
+        grants: Vec<PlatformEffectiveGrant>,
+        meta: PlatformCacheEntryMeta,
+    ) {
+        if self.capacity == 0 {
+            return;
+        }
+        let now = Instant::now();
+        let key = PlatformCacheKey {
+            principal: principal.clone(),
+            config_signature: config_signature.to_string(),
+        };
+        let mut state = lock(&self.state);
+        let entry = PlatformCacheEntry {
+            grants,
+            expires_at: self.expires_at(now, meta.max_ttl),
+            roles: meta.roles,
+        };
+        if state.entries.insert(key.clone(), entry).is_none() {
+            state.order.push_back(key);
+        }
+        while state.entries.len() > self.capacity {
+            match state.order.pop_front() {
+                Some(key) => {
+                    state.entries.remove(&key);
+                }
+                None => break,
+            }
+        }
+    }
+
+    /// 读取未过期的平台主体状态，过期状态读取时删除。
+    async fn get_principal_status(
+        &self,
+        principal: &PlatformPrincipalId,
+    ) -> Option<PlatformPrincipalStatus> {
+        if self.capacity == 0 {
+            return None;
+        }
+        let mut state = lock(&self.state);
+        let (status, expires_at) = *state.statuses.get(principal)?;
+        if is_expired(expires_at, Instant::now()) {
+            state.remove_status(principal);
+            return None;
+        }
+        Some(status)
+    }
+
+    /// 写入平台主体状态，覆盖已有状态时保留其淘汰位置，超出容量时淘汰最早写入的状态。
+    async fn set_principal_status(
+        &self,
+        principal: &PlatformPrincipalId,
+        status: PlatformPrincipalStatus,
+    ) {
+        if self.capacity == 0 {
+            return;
+        }
+        let expires_at = self.status_ttl.or(self.ttl).map(|ttl| Instant::now() + ttl);
+        let mut state = lock(&self.state);
+        let PlatformCacheState {
+            statuses,
+            status_order,
+            ..
+        } = &mut *state;
+        if statuses
+            .insert(principal.clone(), (status, expires_at))
+            .is_none()
+        {
+            status_order.push_back(principal.clone());
+        }
+        while statuses.len() > self.capacity {
+            match status_order.pop_front() {
+                Some(key) => {
+                    statuses.remove(&key);
+                }
+                None => break,
+            }
+        }
+    }
+
+    /// 失效某个平台主体的全部缓存授权和状态。
+    async fn invalidate_principal(&self, principal: &PlatformPrincipalId) {
+        let mut state = lock(&self.state);
+        state.remove_entries_where(|key, _| &key.principal == principal);
+        state.remove_status(principal);
+    }
+
+    /// 只失效依赖该平台角色的缓存授权。
+    async fn invalidate_role(&self, role: &PlatformRoleId) {
+        lock(&self.state).remove_entries_where(|_, entry| entry.roles.contains(role));
+    }
+
+    /// 清空全部缓存授权和状态。
+    async fn invalidate_all(&self) {
+        let mut state = lock(&self.state);
+        state.entries.clear();
+        state.order.clear();
+        state.statuses.clear();
+        state.status_order.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Permission;
+    use crate::platform::PlatformGrantScope;
+    use futures::executor::block_on;
+
+    /// 解析测试平台主体标识符。
+    fn principal(value: &str) -> PlatformPrincipalId {
+        PlatformPrincipalId::parse(value).expect("principal")
+    }
+
+    /// 构造平台级测试授权及其角色元数据。
+    fn grants(role: &str) -> (Vec<PlatformEffectiveGrant>, PlatformCacheEntryMeta) {
+        let role = PlatformRoleId::parse(role).expect("role");
+        let grants = vec![PlatformEffectiveGrant::new(
+            role.clone(),
+            Permission::parse("platform/role:update").expect("permission"),
+            PlatformGrantScope::platform(),
+        )];
+        let meta = PlatformCacheEntryMeta {
+            max_ttl: None,
+            roles: vec![role],
+        };
+        (grants, meta)
+    }
+
+    #[test]
+    fn memory_platform_cache_should_evict_lru_entry() {
+        let cache = MemoryPlatformCache::new(2);
+        for name in ["ops_1", "ops_2"] {
+            let (grants, meta) = grants("ops");
+            block_on(cache.set_effective_grants(&principal(name), "a", grants, meta));
+        }
+        assert!(block_on(cache.get_effective_grants(&principal("ops_1"), "a")).is_some());
+
+        let (grants, meta) = grants("ops");
+        block_on(cache.set_effective_grants(&principal("ops_3"), "a", grants, meta));
+
+        assert!(block_on(cache.get_effective_grants(&principal("ops_1"), "a")).is_some());
+        assert!(block_on(cache.get_effective_grants(&principal("ops_2"), "a")).is_none());
+        assert!(block_on(cache.get_effective_grants(&principal("ops_3"), "b")).is_none());
+    }
+
+    #[test]
+    fn memory_platform_cache_should_invalidate_by_role_and_principal() {
+        let cache = MemoryPlatformCache::new(8);
+        let (support, support_meta) = grants("support");
+        let (billing, billing_meta) = grants("billing");
+        block_on(cache.set_effective_grants(&principal("ops_1"), "a", support, support_meta));
+        block_on(cache.set_effective_grants(&principal("ops_2"), "a", billing, billing_meta));
+        block_on(cache.set_principal_status(&principal("ops_2"), PlatformPrincipalStatus::Active));
+
+        block_on(cache.invalidate_role(&PlatformRoleId::parse("support").expect("role")));
+        assert!(block_on(cache.get_effective_grants(&principal("ops_1"), "a")).is_none());
+        assert!(block_on(cache.get_effective_grants(&principal("ops_2"), "a")).is_some());
+
+        block_on(cache.invalidate_principal(&principal("ops_2")));
+        assert!(cache.is_empty());
+        assert!(block_on(cache.get_principal_status(&principal("ops_2"))).is_none());
+    }
+
+    #[test]
+    fn memory_platform_cache_should_cap_entry_lifetime_by_meta() {
+        let cache = MemoryPlatformCache::new(8).with_ttl(Duration::from_secs(60));
+        let (grants, mut meta) = grants("ops");
+        meta.max_ttl = Some(Duration::ZERO);
+        block_on(cache.set_effective_grants(&principal("ops_1"), "a", grants, meta));
+        std::thread::sleep(Duration::from_millis(2));
+
+        assert!(block_on(cache.get_effective_grants(&principal("ops_1"), "a")).is_none());
+        assert!(cache.is_empty());
+    }
+
+    #[test]
+    fn memory_platform_cache_should_expire_status_by_status_ttl() {
+        let cache = MemoryPlatformCache::new(8)
+            .with_ttl(Duration::from_secs(60))
+            .with_status_ttl(Duration::ZERO);
+        block_on(cache.set_principal_status(&principal("ops_1"), PlatformPrincipalStatus::Active));
+        let (grants, meta) = grants("ops");
+        block_on(cache.set_effective_grants(&principal("ops_1"), "a", grants, meta));
+        std::thread::sleep(Duration::from_millis(2));
+
+        assert!(block_on(cache.get_principal_status(&principal("ops_1"))).is_none());
+        assert!(block_on(cache.get_effective_grants(&principal("ops_1"), "a")).is_some());
+    }
+
+    #[test]
+    fn memory_platform_cache_should_evict_statuses_in_first_write_order() {
+        let cache = MemoryPlatformCache::new(2);
+        for name in ["ops_1", "ops_2", "ops_1"] {
+            block_on(cache.set_principal_status(&principal(name), PlatformPrincipalStatus::Active));
+        }
+        block_on(cache.set_principal_status(&principal("ops_3"), PlatformPrincipalStatus::Active));
+
+        assert!(block_on(cache.get_principal_status(&principal("ops_1"))).is_none());
+        assert!(block_on(cache.get_principal_status(&principal("ops_2"))).is_some());
+        assert!(block_on(cache.get_principal_status(&principal("ops_3"))).is_some());
+    }
+}
@@ -66,6 +66,18 @@ impl MemoryPlatformSource {
             .push(PlatformRoleAssignment::new(role, scope));
     }
 
+    /// 添加完整配置的平台角色分配，例如带有效期的分配。
+    pub fn insert_role_assignment(
+        &self,
+        principal: PlatformPrincipalId,
+        assignment: PlatformRoleAssignment,
+    ) {
+        write_guard(&self.inner.assignments)
+            .entry(principal)
+            .or_default()
+            .push(assignment);
+    }
+
     /// 为平台角色添加权限。
     pub fn add_role_permission(&self, role: PlatformRoleId, permission: Permission) {
         write_guard(&self.inner.role_permissions)
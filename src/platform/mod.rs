@@ -3,8 +3,12 @@
 //! 本模块是租户级 [`crate::Engine`] 的同级模块，用于建模平台主体、平台角色、平台自有权限，
 //! 以及平台主体可以管理的租户数据范围。
 
+mod cache;
+mod decision;
 mod engine;
 mod ids;
+#[cfg(feature = "memory-cache")]
+mod memory_cache;
 #[cfg(feature = "memory-store")]
 mod memory_source;
 mod request;
@@ -12,9 +16,16 @@ mod role;
 mod scope;
 mod source;
 mod subject;
+mod unified;
 
+pub use self::cache::{
+    NoPlatformCache, PlatformCache, PlatformCacheEntryMeta, PlatformEffectiveGrant,
+};
+pub use self::decision::{PlatformAccessExplanation, PlatformDenyReason, PlatformScopeExplanation};
 pub use self::engine::{PlatformEngine, PlatformEngineBuilder, PlatformEngineConfig};
 pub use self::ids::{PlatformPrincipalId, PlatformRoleId};
+#[cfg(feature = "memory-cache")]
+pub use self::memory_cache::MemoryPlatformCache;
 #[cfg(feature = "memory-store")]
 pub use self::memory_source::MemoryPlatformSource;
 pub use self::request::{
@@ -27,3 +38,7 @@ pub use self::scope::{
 };
 pub use self::source::PlatformAuthorizationSource;
 pub use self::subject::{PlatformPrincipalStatus, PlatformSubject};
+pub use self::unified::{
+    Impersonation, Principal, UnifiedAccessExplanation, UnifiedAccessRequest, UnifiedAuthorizer,
+    UnifiedDenyReason, UnifiedScopeQuery,
+};
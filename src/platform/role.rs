@@ -1,4 +1,6 @@
 use super::{PlatformGrantScope, PlatformRoleId};
+use crate::clock::{next_window_change, window_contains};
+use std::time::SystemTime;
 
 /// 平台角色分配及其显式授权范围。
 #[derive(Clone, Debug, Eq, PartialEq, Hash)]
@@ -8,11 +10,50 @@ pub struct PlatformRoleAssignment {
     pub role: PlatformRoleId,
     /// 该平台分配授予的范围。
     pub scope: PlatformGrantScope,
+    /// 分配开始生效的时间；为空时立即生效。
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub not_before: Option<SystemTime>,
+    /// 分配失效的时间；为空时长期有效。
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub expires_at: Option<SystemTime>,
 }
 
 impl PlatformRoleAssignment {
     /// 创建平台角色分配。
     pub fn new(role: PlatformRoleId, scope: PlatformGrantScope) -> Self {
-        Self { role, scope }
+        Self {
+            role,
+            scope,
+            not_before: None,
+            expires_at: None,
+        }
+    }
+
+    /// 设置分配开始生效的时间。
+    pub fn with_not_before(mut self, at: SystemTime) -> Self {
+        self.not_before = Some(at);
+        self
+    }
+
+    /// 设置分配失效的时间。
+    pub fn with_expires_at(mut self, at: SystemTime) -> Self {
+        self.expires_at = Some(at);
+        self
+    }
+
+    /// 返回分配在 `now` 是否处于有效期内。
+    pub fn is_active_at(&self, now: SystemTime) -> bool {
+        window_contains(self.not_before, self.expires_at, now)
+    }
+
+    /// 返回 `now` 之后分配最早一次生效或失效的时间。
+    pub(crate) fn next_change_after(&self, now: SystemTime) -> Option<SystemTime> {
+        next_window_change(self.not_before, self.expires_at, now)
     }
 }
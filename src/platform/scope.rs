@@ -1,5 +1,5 @@
 use crate::error::{Error, Result};
-use crate::{ScopePath, ScopeRoots, TenantId};
+use crate::{AccessScope, GrantScope, ScopePath, ScopePattern, ScopeRoots, TenantId};
 use std::collections::{BTreeMap, BTreeSet};
 
 /// 平台角色分配授予的范围。
@@ -35,6 +35,25 @@ impl PlatformGrantScope {
     pub fn tenant_paths(entries: Vec<TenantScopedRoots>) -> Result<Self> {
         TenantScopeRoots::new(entries).map(Self::TenantPaths)
     }
+
+    /// 返回该授权是否覆盖租户级数据访问。
+    pub fn allows_tenant(&self, tenant: &TenantId) -> bool {
+        match self {
+            Self::AllTenants => true,
+            Self::Tenants(set) => set.contains(tenant),
+            Self::Platform | Self::TenantPaths(_) => false,
+        }
+    }
+
+    /// 返回该授权是否覆盖租户内的目标路径。
+    pub fn allows_path(&self, tenant: &TenantId, target: &ScopePath) -> bool {
+        match self {
+            Self::AllTenants => true,
+            Self::Tenants(set) => set.contains(tenant),
+            Self::TenantPaths(entries) => entries.allows_path(tenant, target),
+            Self::Platform => false,
+        }
+    }
 }
 
 #[cfg(feature = "serde")]
@@ -181,19 +200,20 @@ impl TenantScopeRoots {
             ));
         }
 
-        let mut by_tenant: BTreeMap<TenantId, Vec<ScopePath>> = BTreeMap::new();
+        let mut by_tenant: BTreeMap<TenantId, (Vec<ScopePath>, Vec<ScopePattern>)> =
+            BTreeMap::new();
         for entry in entries {
-            by_tenant
-                .entry(entry.tenant)
-                .or_default()
-                .extend(entry.roots.into_vec());
+            let (roots, patterns) = entry.roots.into_parts();
+            let merged = by_tenant.entry(entry.tenant).or_default();
+            merged.0.extend(roots);
+            merged.1.extend(patterns);
         }
 
         let mut compacted = Vec::with_capacity(by_tenant.len());
-        for (tenant, roots) in by_tenant {
+        for (tenant, (roots, patterns)) in by_tenant {
             compacted.push(TenantScopedRoots {
                 tenant,
-                roots: ScopeRoots::new(roots)?,
+                roots: ScopeRoots::with_patterns(roots, patterns)?,
             });
         }
         Ok(Self { entries: compacted })
@@ -214,13 +234,7 @@ impl TenantScopeRoots {
         self.entries
             .iter()
             .find(|entry| &entry.tenant == tenant)
-            .is_some_and(|entry| {
-                entry
-                    .roots
-                    .as_slice()
-                    .iter()
-                    .any(|root| root.allows(target))
-            })
+            .is_some_and(|entry| entry.roots.allows(target))
     }
 }
 
@@ -379,16 +393,24 @@ impl TenantDataAccessScope {
             Self::TenantPaths { entries } => entries
                 .iter()
                 .find(|entry| &entry.tenant == tenant)
-                .is_some_and(|entry| {
-                    entry
-                        .roots
-                        .as_slice()
-                        .iter()
-                        .any(|root| root.allows(target))
-                }),
+                .is_some_and(|entry| entry.roots.allows(target)),
             Self::None => false,
         }
     }
+
+    /// 把跨租户范围投影为单个租户内的访问范围，便于与租户内 [`AccessScope`] 统一下推查询。
+    pub fn for_tenant(&self, tenant: &TenantId) -> AccessScope {
+        let grant = match self {
+            Self::AllTenants => Some(GrantScope::tenant()),
+            Self::Tenants { tenants } => tenants.contains(tenant).then(GrantScope::tenant),
+            Self::TenantPaths { entries } => entries
+                .iter()
+                .find(|entry| &entry.tenant == tenant)
+                .map(|entry| GrantScope::Paths(entry.roots.clone())),
+            Self::None => None,
+        };
+        AccessScope::merge(tenant.clone(), grant)
+    }
 }
 
 #[cfg(test)]
@@ -0,0 +1,670 @@
+use super::{
+    NoPlatformCache, PlatformAccessExplanation, PlatformAuthorizationSource, PlatformCache,
+    PlatformDenyReason, PlatformEngine, PlatformSubject, TenantDataAccessRequest,
+    TenantDataScopeQuery, TenantScopedDataAccessRequest,
+};
+use crate::cache::{Cache, NoCache};
+use crate::engine::Engine;
+use crate::source::AuthorizationSource;
+use crate::{
+    AccessDecision, AccessScope, AuthSubject, DenyReason, Permission, RequestContext, Result,
+    ScopePath, ScopeQuery, ScopedAccessRequest, TenantId,
+};
+
+/// 统一授权入口的调用主体：租户成员、平台员工或代替租户成员操作的平台员工。
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(tag = "type", content = "subject", rename_all = "snake_case")
+)]
+pub enum Principal {
+    /// 租户内主体，交给租户引擎判定。
+    Tenant(AuthSubject),
+    /// 平台主体，交给平台引擎按租户数据范围判定。
+    Platform(PlatformSubject),
+    /// 平台员工以租户成员身份查看数据，范围取两者的交集。
+    Impersonation(Impersonation),
+}
+
+/// 平台员工代替租户成员操作的身份链。
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Impersonation {
+    /// 发起代替操作的平台主体。
+    pub operator: PlatformSubject,
+    /// 被代替的租户主体。
+    pub target: AuthSubject,
+}
+
+impl Impersonation {
+    /// 创建平台主体代替租户主体的身份链。
+    pub fn new(operator: PlatformSubject, target: AuthSubject) -> Self {
+        Self { operator, target }
+    }
+}
+
+impl From<AuthSubject> for Principal {
+    fn from(subject: AuthSubject) -> Self {
+        Self::Tenant(subject)
+    }
+}
+
+impl From<PlatformSubject> for Principal {
+    fn from(subject: PlatformSubject) -> Self {
+        Self::Platform(subject)
+    }
+}
+
+impl From<Impersonation> for Principal {
+    fn from(impersonation: Impersonation) -> Self {
+        Self::Impersonation(impersonation)
+    }
+}
+
+/// 统一的租户路径访问请求。
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct UnifiedAccessRequest {
+    /// 调用主体。
+    pub principal: Principal,
+    /// 被访问数据所属租户。
+    pub tenant: TenantId,
+    /// 包含动作的完整权限。
+    pub permission: Permission,
+    /// 正在访问的目标路径。
+    pub target: ScopePath,
+    /// 供租户内权限条件求值的请求属性，平台主体忽略。
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub context: RequestContext,
+}
+
+/// 统一的租户内访问范围查询。
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct UnifiedScopeQuery {
+    /// 调用主体。
+    pub principal: Principal,
+    /// 被查询数据所属租户。
+    pub tenant: TenantId,
+    /// 包含动作的完整权限。
+    pub permission: Permission,
+}
+
+/// 统一授权拒绝的原因。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UnifiedDenyReason {
+    /// 租户主体不属于被访问的租户。
+    TenantMismatch,
+    /// 租户引擎给出的拒绝原因。
+    Tenant(DenyReason),
+    /// 平台引擎给出的拒绝原因。
+    Platform(PlatformDenyReason),
+    /// 平台主体在该租户上没有代替权限，或未配置代替权限。
+    ImpersonationDenied(PlatformDenyReason),
+}
+
+/// 统一授权决策的解释信息。
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct UnifiedAccessExplanation {
+    /// 最终决策。
+    pub decision: AccessDecision,
+    /// 当决策为 [`AccessDecision::Deny`] 时的拒绝原因。
+    pub reason: Option<UnifiedDenyReason>,
+    /// 代替操作时记录的身份链，直接调用时为 `None`。
+    pub impersonation: Option<Impersonation>,
+}
+
+/// 组合租户引擎和平台引擎的统一授权入口。
+///
+/// 同一个后台接口既允许租户成员按租户内授权访问，也允许平台员工按跨租户数据范围访问时，
+/// 按 [`Principal`] 分派到对应引擎，调用方无需手工组合两套判定。
+#[derive(Debug)]
+pub struct UnifiedAuthorizer<S, PS, C = NoCache, PC = NoPlatformCache> {
+    tenant: Engine<S, C>,
+    platform: PlatformEngine<PS, PC>,
+    impersonation_permission: Option<Permission>,
+}
+
+impl<S, PS, C, PC> UnifiedAuthorizer<S, PS, C, PC> {
+    /// 使用租户引擎和平台引擎创建统一授权入口。
+    pub fn new(tenant: Engine<S, C>, platform: PlatformEngine<PS, PC>) -> Self {
+        Self {
+            tenant,
+            platform,
+            impersonation_permission: None,
+        }
+    }
+
+    /// 配置代替租户成员所需的平台权限，例如 `tenant/principal:impersonate`。
+    ///
+    /// 未配置时拒绝全部代替操作。
+    pub fn with_impersonation_permission(mut self, permission: Permission) -> Self {
+        self.impersonation_permission = Some(permission);
+        self
+    }
+
+    /// 返回租户引擎，用于缓存失效等租户内操作。
+    pub fn tenant(&self) -> &Engine<S, C> {
+        &self.tenant
+    }
+
+    /// 返回平台引擎。
+    pub fn platform(&self) -> &PlatformEngine<PS, PC> {
+        &self.platform
+    }
+}
+
+impl<S, PS, C, PC> UnifiedAuthorizer<S, PS, C, PC>
+where
+    S: AuthorizationSource,
+    PS: PlatformAuthorizationSource,
+    C: Cache,
+    PC: PlatformCache,
+{
+    /// 检查调用主体对租户内目标路径的访问权。
+    pub async fn can_access_scope(&self, request: UnifiedAccessRequest) -> Result<AccessDecision> {
+        Ok(self.explain_access_scope(request).await?.decision)
+    }
+
+    /// 解释调用主体对租户内目标路径的访问检查结果。
+    ///
+    /// 租户主体等价于 [`Engine::explain_access_scope`]，平台主体等价于
+    /// [`PlatformEngine::explain_access_tenant_scope`]。代替操作要求平台主体拥有代替权限，
+    /// 且被代替的租户主体和平台主体都允许访问目标路径。
+    pub async fn explain_access_scope(
+        &self,
+        request: UnifiedAccessRequest,
+    ) -> Result<UnifiedAccessExplanation> {
+        match request.principal {
+            Principal::Tenant(subject) if subject.tenant != request.tenant => {
+                Ok(UnifiedAccessExplanation {
+                    decision: AccessDecision::Deny,
+                    reason: Some(UnifiedDenyReason::TenantMismatch),
+                    impersonation: None,
+                })
+            }
+            Principal::Tenant(subject) => {
+                let explanation = self
+                    .tenant
+                    .explain_access_scope(ScopedAccessRequest {
+                        subject,
+                        permission: request.permission,
+                        target: request.target,
+                        context: request.context,
+                    })
+                    .await?;
+                Ok(UnifiedAccessExplanation {
+                    decision: explanation.decision,
+                    reason: explanation.reason.map(UnifiedDenyReason::Tenant),
+                    impersonation: None,
+                })
+            }
+            Principal::Platform(subject) => {
+                let explanation = self
+                    .platform
+                    .explain_access_tenant_scope(TenantScopedDataAccessRequest {
+                        subject,
+                        permission: request.permission,
+                        tenant: request.tenant,
+                        target: request.target,
+                    })
+                    .await?;
+                Ok(UnifiedAccessExplanation {
+                    decision: explanation.decision,
+                    reason: explanation.reason.map(UnifiedDenyReason::Platform),
+                    impersonation: None,
+                })
+            }
+            Principal::Impersonation(impersonation) => {
+                let reason = self
+                    .impersonation_deny_reason(
+                        &impersonation,
+                        &request.tenant,
+                        &request.permission,
+                        &request.target,
+                        &request.context,
+                    )
+                    .await?;
+                Ok(UnifiedAccessExplanation {
+                    decision: if reason.is_none() {
+                        AccessDecision::Allow
+                    } else {
+                        AccessDecision::Deny
+                    },
+                    reason,
+                    impersonation: Some(impersonation),
+                })
+            }
+        }
+    }
+
+    /// 检查平台主体是否可以代替租户主体。
+    pub async fn can_impersonate(&self, impersonation: &Impersonation) -> Result<AccessDecision> {
+        Ok(self.explain_impersonation(impersonation).await?.decision)
+    }
+
+    /// 解释平台主体代替租户主体的检查结果。
+    ///
+    /// 要求平台主体对被代替主体所属租户拥有代替权限，等价于
+    /// [`PlatformEngine::explain_access_tenant`]；未配置代替权限时拒绝，原因为
+    /// [`PlatformDenyReason::PermissionMissing`]。
+    pub async fn explain_impersonation(
+        &self,
+        impersonation: &Impersonation,
+    ) -> Result<PlatformAccessExplanation> {
+        let Some(permission) = &self.impersonation_permission else {
+            return Ok(PlatformAccessExplanation {
+                decision: AccessDecision::Deny,
+                reason: Some(PlatformDenyReason::PermissionMissing),
+                trace: None,
+            });
+        };
+        self.platform
+            .explain_access_tenant(TenantDataAccessRequest {
+                subject: impersonation.operator.clone(),
+                permission: permission.clone(),
+                tenant: impersonation.target.tenant.clone(),
+            })
+            .await
+    }
+
+    /// 依次检查租户、代替权限、被代替主体和平台主体，返回第一个拒绝原因。
+    async fn impersonation_deny_reason(
+        &self,
+        impersonation: &Impersonation,
+        tenant: &TenantId,
+        permission: &Permission,
+        target: &ScopePath,
+        context: &RequestContext,
+    ) -> Result<Option<UnifiedDenyReason>> {
+        if &impersonation.target.tenant != tenant {
+            return Ok(Some(UnifiedDenyReason::TenantMismatch));
+        }
+        if let Some(reason) = self.explain_impersonation(impersonation).await?.reason {
+            return Ok(Some(UnifiedDenyReason::ImpersonationDenied(reason)));
+        }
+        let member = self
+            .tenant
+            .explain_access_scope(ScopedAccessRequest {
+                subject: impersonation.target.clone(),
+                permission: permission.clone(),
+                target: target.clone(),
+                context: context.clone(),
+            })
+            .await?;
+        if let Some(reason) = member.reason {
+            return Ok(Some(UnifiedDenyReason::Tenant(reason)));
+        }
+        let operator = self
+            .platform
+            .explain_access_tenant_scope(TenantScopedDataAccessRequest {
+                subject: impersonation.operator.clone(),
+                permission: permission.clone(),
+                tenant: tenant.clone(),
+                target: target.clone(),
+            })
+            .await?;
+        Ok(operator.reason.map(UnifiedDenyReason::Platform))
+    }
+
+    /// 计算调用主体在某个租户内的访问范围，可直接下推到查询。
+    ///
+    /// 平台主体的跨租户范围会投影到该租户；租户主体查询其他租户时返回 [`AccessScope::None`]。
+    /// 代替操作返回被代替主体的范围与平台主体投影范围的交集，没有代替权限时返回
+    /// [`AccessScope::None`]。
+    pub async fn accessible_scope(&self, query: UnifiedScopeQuery) -> Result<AccessScope> {
+        match query.principal {
+            Principal::Tenant(subject) if subject.tenant != query.tenant => Ok(AccessScope::None),
+            Principal::Tenant(subject) => {
+                self.tenant
+                    .accessible_scope(ScopeQuery {
+                        subject,
+                        permission: query.permission,
+                    })
+                    .await
+            }
+            Principal::Platform(subject) => {
+                let scope = self
+                    .platform
+                    .accessible_tenants(TenantDataScopeQuery {
+                        subject,
+                        permission: query.permission,
+                    })
+                    .await?;
+                Ok(scope.for_tenant(&query.tenant))
+            }
+            Principal::Impersonation(impersonation) => {
+                if impersonation.target.tenant != query.tenant
+                    || self.can_impersonate(&impersonation).await? != AccessDecision::Allow
+                {
+                    return Ok(AccessScope::None);
+                }
+                let target = self
+                    .tenant
+                    .accessible_scope(ScopeQuery {
+                        subject: impersonation.target,
+                        permission: query.permission.clone(),
+                    })
+                    .await?;
+                let operator = self
+                    .platform
+                    .accessible_tenants(TenantDataScopeQuery {
+                        subject: impersonation.operator,
+                        permission: query.permission,
+                    })
+                    .await?;
+                Ok(target.intersect(operator.for_tenant(&query.tenant)))
+            }
+        }
+    }
+}
+
+#[cfg(all(test, feature = "memory-store"))]
+mod tests {
+    use super::*;
+    use crate::platform::{
+        MemoryPlatformSource, PlatformEngineBuilder, PlatformGrantScope, PlatformPrincipalId,
+        PlatformPrincipalStatus, PlatformRoleId, TenantScopedRoots,
+    };
+    use crate::{
+        EngineBuilder, GrantScope, MembershipStatus, MemorySource, PrincipalId, RoleId,
+        ScopePattern, ScopeRoots, TenantStatus,
+    };
+    use futures::executor::block_on;
+
+    /// 解析测试租户标识符。
+    fn tenant(value: &str) -> TenantId {
+        TenantId::parse(value).expect("tenant")
+    }
+
+    /// 解析测试范围路径。
+    fn path(value: &str) -> ScopePath {
+        ScopePath::parse(value).expect("path")
+    }
+
+    /// 构造租户成员在 `agent/1` 下拥有 `invoice:read`、平台员工在 `tenant_a` 的
+    /// `agent/2` 下拥有同一权限的统一授权入口。
+    fn authorizer() -> (
+        UnifiedAuthorizer<MemorySource, MemoryPlatformSource>,
+        AuthSubject,
+        PlatformSubject,
+    ) {
+        authorizer_with(&["agent/1"], &["agent/2"])
+    }
+
+    /// 解析测试根路径和模式，含 `*` 的条目按模式解析。
+    fn roots(values: &[&str]) -> ScopeRoots {
+        let (patterns, roots): (Vec<_>, Vec<_>) =
+            values.iter().partition(|value| value.contains('*'));
+        ScopeRoots::with_patterns(
+            roots.into_iter().map(path).collect(),
+            patterns
+                .into_iter()
+                .map(|pattern| ScopePattern::parse(pattern).expect("pattern"))
+                .collect(),
+        )
+        .expect("roots")
+    }
+
+    /// 按给定根路径或模式构造租户成员和平台员工的 `invoice:read` 授权，平台员工同时在
+    /// `tenant_a` 上拥有 `tenant/principal:impersonate`。
+    fn authorizer_with(
+        member_roots: &[&str],
+        operator_roots: &[&str],
+    ) -> (
+        UnifiedAuthorizer<MemorySource, MemoryPlatformSource>,
+        AuthSubject,
+        PlatformSubject,
+    ) {
+        let read = Permission::parse("invoice:read").expect("permission");
+        let source = MemorySource::new();
+        let member = AuthSubject::new(
+            tenant("tenant_a"),
+            PrincipalId::parse("user_1").expect("principal"),
+        );
+        let reader = RoleId::parse("reader").expect("role");
+        source.set_tenant_status(member.tenant.clone(), TenantStatus::Active);
+        source.set_membership_status(
+            member.tenant.clone(),
+            member.principal.clone(),
+            MembershipStatus::Active,
+        );
+        source.add_role_assignment(
+            member.tenant.clone(),
+            member.principal.clone(),
+            reader.clone(),
+            GrantScope::Paths(roots(member_roots)),
+        );
+        source.add_role_permission(member.tenant.clone(), reader, read.clone());
+
+        let platform_source = MemoryPlatformSource::new();
+        let operator =
+            PlatformSubject::new(PlatformPrincipalId::parse("operator").expect("principal"));
+        let support = PlatformRoleId::parse("support").expect("role");
+        platform_source
+            .set_principal_status(operator.principal.clone(), PlatformPrincipalStatus::Active);
+        platform_source.add_role_assignment(
+            operator.principal.clone(),
+            support.clone(),
+            PlatformGrantScope::tenant_paths(vec![TenantScopedRoots::new(
+                tenant("tenant_a"),
+                roots(operator_roots),
+            )])
+            .expect("scope"),
+        );
+        platform_source.add_role_permission(support, read);
+        let impersonator = PlatformRoleId::parse("impersonator").expect("role");
+        platform_source.add_role_assignment(
+            operator.principal.clone(),
+            impersonator.clone(),
+            PlatformGrantScope::tenants(vec![tenant("tenant_a")]).expect("scope"),
+        );
+        platform_source.add_role_permission(
+            impersonator,
+            Permission::parse("tenant/principal:impersonate").expect("permission"),
+        );
+
+        let authorizer = UnifiedAuthorizer::new(
+            EngineBuilder::new(source).build(),
+            PlatformEngineBuilder::new(platform_source).build(),
+        );
+        (authorizer, member, operator)
+    }
+
+    /// 构造访问 `tenant` 下目标路径的统一请求。
+    fn request(
+        principal: impl Into<Principal>,
+        tenant_id: &str,
+        target: &str,
+    ) -> UnifiedAccessRequest {
+        UnifiedAccessRequest {
+            principal: principal.into(),
+            tenant: tenant(tenant_id),
+            permission: Permission::parse("invoice:read").expect("permission"),
+            target: path(target),
+            context: RequestContext::new(),
+        }
+    }
+
+    #[test]
+    fn unified_authorizer_should_dispatch_by_principal() {
+        let (authorizer, member, operator) = authorizer();
+
+        let explain =
+            |request| block_on(authorizer.explain_access_scope(request)).expect("explanation");
+        assert_eq!(
+            explain(request(member.clone(), "tenant_a", "agent/1/invoice/9")).decision,
+            AccessDecision::Allow
+        );
+        assert_eq!(
+            explain(request(member.clone(), "tenant_a", "agent/2")).reason,
+            Some(UnifiedDenyReason::Tenant(DenyReason::ScopeDenied))
+        );
+        assert_eq!(
+            explain(request(member, "tenant_b", "agent/1")).reason,
+            Some(UnifiedDenyReason::TenantMismatch)
+        );
+        assert_eq!(
+            explain(request(operator.clone(), "tenant_a", "agent/2/invoice/3")).decision,
+            AccessDecision::Allow
+        );
+        assert_eq!(
+            explain(request(operator, "tenant_a", "agent/1")).reason,
+            Some(UnifiedDenyReason::Platform(PlatformDenyReason::PathDenied))
+        );
+    }
+
+    #[test]
+    fn unified_accessible_scope_should_project_platform_scope_to_tenant() {
+        let (authorizer, member, operator) = authorizer();
+        let query = |principal: Principal, tenant_id: &str| UnifiedScopeQuery {
+            principal,
+            tenant: tenant(tenant_id),
+            permission: Permission::parse("invoice:read").expect("permission"),
+        };
+        let scope = |query| block_on(authorizer.accessible_scope(query)).expect("scope");
+
+        assert_eq!(
+            scope(query(operator.clone().into(), "tenant_a")),
+            AccessScope::merge(
+                tenant("tenant_a"),
+                [GrantScope::paths(vec![path("agent/2")]).expect("scope")]
+            )
+        );
+        assert_eq!(scope(query(operator.into(), "tenant_b")), AccessScope::None);
+        assert_eq!(
+            scope(query(member.clone().into(), "tenant_a")),
+            AccessScope::merge(
+                tenant("tenant_a"),
+                [GrantScope::paths(vec![path("agent/1")]).expect("scope")]
+            )
+        );
+        assert_eq!(scope(query(member.into(), "tenant_b")), AccessScope::None);
+    }
+
+    #[test]
+    fn impersonation_should_intersect_target_and_operator_scopes() {
+        let (authorizer, member, operator) =
+            authorizer_with(&["agent/1", "agent/2"], &["agent/2", "agent/3"]);
+        let impersonation = Impersonation::new(operator.clone(), member.clone());
+
+        // 未配置代替权限时拒绝全部代替操作。
+        let denied = block_on(authorizer.explain_access_scope(request(
+            impersonation.clone(),
+            "tenant_a",
+            "agent/2",
+        )))
+        .expect("explanation");
+        assert_eq!(
+            denied.reason,
+            Some(UnifiedDenyReason::ImpersonationDenied(
+                PlatformDenyReason::PermissionMissing
+            ))
+        );
+
+        let authorizer = authorizer.with_impersonation_permission(
+            Permission::parse("tenant/principal:impersonate").expect("permission"),
+        );
+        let explain =
+            |request| block_on(authorizer.explain_access_scope(request)).expect("explanation");
+        let allowed = explain(request(
+            impersonation.clone(),
+            "tenant_a",
+            "agent/2/invoice/1",
+        ));
+        assert_eq!(allowed.decision, AccessDecision::Allow);
+        assert_eq!(allowed.impersonation, Some(impersonation.clone()));
+        assert_eq!(
+            explain(request(impersonation.clone(), "tenant_a", "agent/1")).reason,
+            Some(UnifiedDenyReason::Platform(PlatformDenyReason::PathDenied))
+        );
+        assert_eq!(
+            explain(request(impersonation.clone(), "tenant_a", "agent/3")).reason,
+            Some(UnifiedDenyReason::Tenant(DenyReason::ScopeDenied))
+        );
+        assert_eq!(
+            explain(request(impersonation.clone(), "tenant_b", "agent/2")).reason,
+            Some(UnifiedDenyReason::TenantMismatch)
+        );
+
+        let scope = block_on(authorizer.accessible_scope(UnifiedScopeQuery {
+            principal: impersonation.into(),
+            tenant: tenant("tenant_a"),
+            permission: Permission::parse("invoice:read").expect("permission"),
+        }))
+        .expect("scope");
+        assert_eq!(
+            scope,
+            AccessScope::merge(
+                tenant("tenant_a"),
+                [GrantScope::paths(vec![path("agent/2")]).expect("scope")]
+            )
+        );
+    }
+
+    #[test]
+    fn impersonation_scope_should_agree_with_access_checks_for_patterns() {
+        let (authorizer, member, operator) =
+            authorizer_with(&["agent/*/store/**"], &["agent/1/*/**"]);
+        let authorizer = authorizer.with_impersonation_permission(
+            Permission::parse("tenant/principal:impersonate").expect("permission"),
+        );
+        let impersonation = Impersonation::new(operator, member);
+        let scope = block_on(authorizer.accessible_scope(UnifiedScopeQuery {
+            principal: impersonation.clone().into(),
+            tenant: tenant("tenant_a"),
+            permission: Permission::parse("invoice:read").expect("permission"),
+        }))
+        .expect("scope");
+
+        for (target, decision) in [
+            ("agent/1/store/x", AccessDecision::Allow),
+            ("agent/1/store", AccessDecision::Allow),
+            ("agent/2/store/x", AccessDecision::Deny),
+            ("agent/1/office/x", AccessDecision::Deny),
+        ] {
+            let checked = block_on(authorizer.can_access_scope(request(
+                impersonation.clone(),
+                "tenant_a",
+                target,
+            )))
+            .expect("decision");
+            assert_eq!(checked, decision, "{target}");
+            assert_eq!(
+                scope.allows_path(&path(target)),
+                decision == AccessDecision::Allow,
+                "{target}"
+            );
+        }
+    }
+
+    #[test]
+    fn impersonation_should_require_permission_on_target_tenant() {
+        let (authorizer, _, operator) = authorizer();
+        let authorizer = authorizer.with_impersonation_permission(
+            Permission::parse("tenant/principal:impersonate").expect("permission"),
+        );
+        let outsider = Impersonation::new(
+            operator,
+            AuthSubject::new(
+                tenant("tenant_b"),
+                PrincipalId::parse("user_2").expect("principal"),
+            ),
+        );
+
+        assert_eq!(
+            block_on(authorizer.explain_impersonation(&outsider))
+                .expect("explanation")
+                .reason,
+            Some(PlatformDenyReason::TenantNotInSet)
+        );
+        let scope = block_on(authorizer.accessible_scope(UnifiedScopeQuery {
+            principal: outsider.into(),
+            tenant: tenant("tenant_b"),
+            permission: Permission::parse("invoice:read").expect("permission"),
+        }))
+        .expect("scope");
+        assert_eq!(scope, AccessScope::None);
+    }
+}
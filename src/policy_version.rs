@@ -0,0 +1,106 @@
+use crate::error::SourceError;
+use crate::ids::TenantId;
+use async_trait::async_trait;
+use std::fmt;
+
+/// 策略版本来源，用于多节点部署下的缓存失效。
+///
+/// 管理端修改授权数据后递增对应版本；引擎把版本纳入缓存键，
+/// 各节点上的旧缓存条目会在下一次判定时自动失效，无需逐个节点广播失效。
+/// 每次判定都会读取版本，实现应尽量轻量，例如读取共享存储中的计数器。
+#[async_trait]
+pub trait PolicyVersionSource: fmt::Debug + Send + Sync {
+    /// 返回全局策略版本，变化后所有租户的缓存失效。
+    async fn global_version(&self) -> std::result::Result<u64, SourceError>;
+
+    /// 返回租户策略版本，变化后该租户的缓存失效。
+    async fn tenant_version(&self, tenant: &TenantId) -> std::result::Result<u64, SourceError>;
+}
+
+#[cfg(feature = "memory-store")]
+pub use self::memory::MemoryPolicyVersions;
+
+#[cfg(feature = "memory-store")]
+mod memory {
+    use super::PolicyVersionSource;
+    use crate::error::SourceError;
+    use crate::ids::TenantId;
+    use crate::sync::lock;
+    use async_trait::async_trait;
+    use std::collections::HashMap;
+    use std::sync::atomic::{AtomicU64, Ordering};
+    use std::sync::{Arc, Mutex};
+
+    /// 用于测试和演示的内存策略版本。
+    #[derive(Debug, Default, Clone)]
+    pub struct MemoryPolicyVersions {
+        inner: Arc<Inner>,
+    }
+
+    /// 内存策略版本的共享内部状态。
+    #[derive(Debug, Default)]
+    struct Inner {
+        global: AtomicU64,
+        tenants: Mutex<HashMap<TenantId, u64>>,
+    }
+
+    impl MemoryPolicyVersions {
+        /// 创建所有版本均为 0 的版本来源。
+        pub fn new() -> Self {
+            Self::default()
+        }
+
+        /// 递增全局版本并返回新版本。
+        pub fn bump_global(&self) -> u64 {
+            self.inner.global.fetch_add(1, Ordering::SeqCst) + 1
+        }
+
+        /// 递增租户版本并返回新版本。
+        pub fn bump_tenant(&self, tenant: &TenantId) -> u64 {
+            let mut tenants = lock(&self.inner.tenants);
+            let version = tenants.entry(tenant.clone()).or_default();
+            *version += 1;
+            *version
+        }
+    }
+
+    #[async_trait]
+    impl PolicyVersionSource for MemoryPolicyVersions {
+        /// 返回全局版本。
+        async fn global_version(&self) -> std::result::Result<u64, SourceError> {
+            Ok(self.inner.global.load(Ordering::SeqCst))
+        }
+
+        /// 返回租户版本，未递增过的租户为 0。
+        async fn tenant_version(&self, tenant: &TenantId) -> std::result::Result<u64, SourceError> {
+            Ok(lock(&self.inner.tenants).get(tenant).copied().unwrap_or(0))
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+        use futures::executor::block_on;
+
+        #[test]
+        fn memory_policy_versions_should_bump_independently() {
+            let versions = MemoryPolicyVersions::new();
+            let tenant = TenantId::parse("tenant_1").expect("tenant");
+            let other = TenantId::parse("tenant_2").expect("tenant");
+
+            assert_eq!(versions.bump_tenant(&tenant), 1);
+            assert_eq!(versions.bump_tenant(&tenant), 2);
+            assert_eq!(versions.bump_global(), 1);
+
+            assert_eq!(
+                block_on(versions.tenant_version(&tenant)).expect("version"),
+                2
+            );
+            assert_eq!(
+                block_on(versions.tenant_version(&other)).expect("version"),
+                0
+            );
+            assert_eq!(block_on(versions.global_version()).expect("version"), 1);
+        }
+    }
+}
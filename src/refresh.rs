@@ -0,0 +1,73 @@
+use crate::request::AuthSubject;
+use crate::sync::lock;
+use std::collections::{HashSet, VecDeque};
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Mutex;
+use std::task::{Context, Poll, Waker};
+
+/// 等待后台提前刷新的有效授权，按主体和缓存签名去重。
+#[derive(Debug, Default)]
+pub(crate) struct RefreshQueue {
+    state: Mutex<RefreshState>,
+}
+
+/// 队列内容及等待中的刷新任务。
+#[derive(Debug, Default)]
+struct RefreshState {
+    pending: VecDeque<(AuthSubject, String)>,
+    queued: HashSet<(AuthSubject, String)>,
+    wakers: Vec<Waker>,
+}
+
+impl RefreshQueue {
+    /// 加入刷新请求，已在队列中的请求会被忽略。
+    pub(crate) fn push(&self, subject: &AuthSubject, signature: &str) {
+        let wakers = {
+            let mut state = lock(&self.state);
+            let item = (subject.clone(), signature.to_string());
+            if !state.queued.insert(item.clone()) {
+                return;
+            }
+            state.pending.push_back(item);
+            std::mem::take(&mut state.wakers)
+        };
+        for waker in wakers {
+            waker.wake();
+        }
+    }
+
+    /// 取出下一个刷新请求。
+    pub(crate) fn pop(&self) -> Option<(AuthSubject, String)> {
+        let mut state = lock(&self.state);
+        let item = state.pending.pop_front()?;
+        state.queued.remove(&item);
+        Some(item)
+    }
+
+    /// 等待并取出下一个刷新请求。
+    pub(crate) fn next(&self) -> impl Future<Output = (AuthSubject, String)> + '_ {
+        Next { queue: self }
+    }
+}
+
+/// 等待刷新请求的 future。
+struct Next<'a> {
+    queue: &'a RefreshQueue,
+}
+
+impl Future for Next<'_> {
+    type Output = (AuthSubject, String);
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let mut state = lock(&self.queue.state);
+        if let Some(item) = state.pending.pop_front() {
+            state.queued.remove(&item);
+            return Poll::Ready(item);
+        }
+        if !state.wakers.iter().any(|waker| waker.will_wake(cx.waker())) {
+            state.wakers.push(cx.waker().clone());
+        }
+        Poll::Pending
+    }
+}
@@ -1,5 +1,7 @@
+use crate::condition::RequestContext;
 use crate::ids::{PrincipalId, TenantId};
 use crate::permission::Permission;
+use crate::role::RoleAssignment;
 use crate::scope::ScopePath;
 
 /// 用于授权的租户级主体。
@@ -37,6 +39,26 @@ pub struct TenantAccessRequest {
     pub subject: AuthSubject,
     /// 包含动作的完整权限。
     pub permission: Permission,
+    /// 供权限条件求值的请求属性。
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub context: RequestContext,
+}
+
+impl TenantAccessRequest {
+    /// 创建不带请求属性的租户级访问请求。
+    pub fn new(subject: AuthSubject, permission: Permission) -> Self {
+        Self {
+            subject,
+            permission,
+            context: RequestContext::new(),
+        }
+    }
+
+    /// 设置供权限条件求值的请求属性。
+    pub fn with_context(mut self, context: RequestContext) -> Self {
+        self.context = context;
+        self
+    }
 }
 
 /// 路径级访问请求。
@@ -49,4 +71,37 @@ pub struct ScopedAccessRequest {
     pub permission: Permission,
     /// 正在访问的目标路径。
     pub target: ScopePath,
+    /// 供权限条件求值的请求属性。
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub context: RequestContext,
+}
+
+impl ScopedAccessRequest {
+    /// 创建不带请求属性的路径级访问请求。
+    pub fn new(subject: AuthSubject, permission: Permission, target: ScopePath) -> Self {
+        Self {
+            subject,
+            permission,
+            target,
+            context: RequestContext::new(),
+        }
+    }
+
+    /// 设置供权限条件求值的请求属性。
+    pub fn with_context(mut self, context: RequestContext) -> Self {
+        self.context = context;
+        self
+    }
+}
+
+/// 主体把自己的部分访问权委托给其他主体的请求。
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DelegationRequest {
+    /// 发起委托的租户级主体。
+    pub delegator: AuthSubject,
+    /// 委托所需的权限，例如 `role/assignment:delegate`。
+    pub permission: Permission,
+    /// 拟创建的角色分配。
+    pub assignment: RoleAssignment,
 }
@@ -1,5 +1,8 @@
+use crate::clock::{next_window_change, window_contains};
+use crate::grant::Effect;
 use crate::ids::RoleId;
 use crate::scope::GrantScope;
+use std::time::SystemTime;
 
 /// 角色分配及其显式授权范围。
 #[derive(Clone, Debug, Eq, PartialEq, Hash)]
@@ -9,11 +12,64 @@ pub struct RoleAssignment {
     pub role: RoleId,
     /// 该分配授予的范围。
     pub scope: GrantScope,
+    /// 该分配是授予还是扣除访问范围。
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub effect: Effect,
+    /// 分配开始生效的时间；为空时立即生效。
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub not_before: Option<SystemTime>,
+    /// 分配失效的时间；为空时长期有效。
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub expires_at: Option<SystemTime>,
 }
 
 impl RoleAssignment {
-    /// 创建角色分配。
+    /// 创建允许效果的角色分配。
     pub fn new(role: RoleId, scope: GrantScope) -> Self {
-        Self { role, scope }
+        Self {
+            role,
+            scope,
+            effect: Effect::Allow,
+            not_before: None,
+            expires_at: None,
+        }
+    }
+
+    /// 创建拒绝效果的角色分配。
+    ///
+    /// 角色权限匹配时，分配范围会从其他允许分配合并出的访问范围中扣除。
+    pub fn deny(role: RoleId, scope: GrantScope) -> Self {
+        Self {
+            effect: Effect::Deny,
+            ..Self::new(role, scope)
+        }
+    }
+
+    /// 设置分配开始生效的时间。
+    pub fn with_not_before(mut self, at: SystemTime) -> Self {
+        self.not_before = Some(at);
+        self
+    }
+
+    /// 设置分配失效的时间。
+    pub fn with_expires_at(mut self, at: SystemTime) -> Self {
+        self.expires_at = Some(at);
+        self
+    }
+
+    /// 返回分配在 `now` 是否处于有效期内。
+    pub fn is_active_at(&self, now: SystemTime) -> bool {
+        window_contains(self.not_before, self.expires_at, now)
+    }
+
+    /// 返回 `now` 之后分配最早一次生效或失效的时间。
+    pub(crate) fn next_change_after(&self, now: SystemTime) -> Option<SystemTime> {
+        next_window_change(self.not_before, self.expires_at, now)
     }
 }
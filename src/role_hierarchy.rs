@@ -22,8 +22,19 @@ pub(crate) trait RoleHierarchy {
     fn depth_error(&self, role: Self::Role) -> Error;
 }
 
+/// 继承展开得到的角色及其继承路径。
+pub(crate) struct ExpandedRole<R> {
+    /// 展开得到的角色。
+    pub(crate) role: R,
+    /// 从起始角色到该角色的路径，不含该角色本身；起始角色为空。
+    pub(crate) via: Vec<R>,
+}
+
 /// 展开角色及其继承链上的父角色。
-pub(crate) async fn expand_roles<H>(hierarchy: &H, root: H::Role) -> Result<Vec<H::Role>>
+pub(crate) async fn expand_roles<H>(
+    hierarchy: &H,
+    root: H::Role,
+) -> Result<Vec<ExpandedRole<H::Role>>>
 where
     H: RoleHierarchy + Sync,
 {
@@ -39,13 +50,16 @@ async fn expand_from<H>(
     root: H::Role,
     visited: &mut HashSet<H::Role>,
     visiting: &mut HashSet<H::Role>,
-    output: &mut Vec<H::Role>,
+    output: &mut Vec<ExpandedRole<H::Role>>,
 ) -> Result<()>
 where
     H: RoleHierarchy + Sync,
 {
     visiting.insert(root.clone());
-    output.push(root.clone());
+    output.push(ExpandedRole {
+        role: root.clone(),
+        via: Vec::new(),
+    });
     let parents = hierarchy.parent_roles(&root).await?;
     let mut stack: Vec<(H::Role, usize, std::vec::IntoIter<H::Role>)> =
         vec![(root, 0, parents.into_iter())];
@@ -66,7 +80,10 @@ where
 
             let parents = hierarchy.parent_roles(&parent).await?;
             visiting.insert(parent.clone());
-            output.push(parent.clone());
+            output.push(ExpandedRole {
+                role: parent.clone(),
+                via: stack.iter().map(|(role, _, _)| role.clone()).collect(),
+            });
             stack.push((parent, next_depth, parents.into_iter()));
             continue;
         }
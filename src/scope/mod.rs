@@ -0,0 +1,1165 @@
+use crate::error::{Error, Result};
+use crate::ids::TenantId;
+use std::borrow::Borrow;
+use std::collections::BTreeSet;
+use std::fmt;
+
+mod pattern;
+pub mod sql;
+
+pub use self::pattern::ScopePattern;
+
+const MAX_SCOPE_PATH_LEN: usize = 256;
+
+/// 层级范围路径，例如 `agent/123/store/456`。
+#[derive(Clone, Debug, Eq, PartialEq, Hash, Ord, PartialOrd)]
+pub struct ScopePath(String);
+
+impl ScopePath {
+    /// 解析并校验范围路径。
+    pub fn parse(value: impl AsRef<str>) -> Result<Self> {
+        let trimmed = value.as_ref().trim();
+        if trimmed.is_empty() {
+            return Err(Error::InvalidScope(
+                "scope path must not be empty".to_string(),
+            ));
+        }
+        if trimmed.len() > MAX_SCOPE_PATH_LEN {
+            return Err(Error::InvalidScope(format!(
+                "scope path length must be <= {MAX_SCOPE_PATH_LEN}"
+            )));
+        }
+        for segment in trimmed.split('/') {
+            validate_segment(segment)?;
+        }
+        Ok(Self(trimmed.to_string()))
+    }
+
+    /// 返回路径字符串。
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// 返回当前路径是否为 `other` 的严格祖先。
+    pub fn is_ancestor_of(&self, other: &ScopePath) -> bool {
+        self != other && other.0.starts_with(&format!("{}/", self.0))
+    }
+
+    /// 返回当前路径是否允许访问 `target`。
+    pub fn allows(&self, target: &ScopePath) -> bool {
+        self == target || self.is_ancestor_of(target)
+    }
+}
+
+impl fmt::Display for ScopePath {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl AsRef<str> for ScopePath {
+    fn as_ref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl Borrow<str> for ScopePath {
+    fn borrow(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl TryFrom<&str> for ScopePath {
+    type Error = Error;
+
+    fn try_from(value: &str) -> Result<Self> {
+        Self::parse(value)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for ScopePath {
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for ScopePath {
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let value = String::deserialize(deserializer)?;
+        Self::parse(value).map_err(serde::de::Error::custom)
+    }
+}
+
+/// 校验范围路径中的单个段。
+fn validate_segment(segment: &str) -> Result<()> {
+    if segment.is_empty() {
+        return Err(Error::InvalidScope(
+            "scope path contains empty segment".to_string(),
+        ));
+    }
+    if !segment
+        .chars()
+        .all(|ch| ch.is_ascii_alphanumeric() || matches!(ch, '_' | '-' | '.'))
+    {
+        return Err(Error::InvalidScope(
+            "scope path contains invalid characters".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+/// 单个角色分配授予的范围。
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub enum GrantScope {
+    /// 租户级授权。
+    Tenant,
+    /// 以一个或多个范围路径为根的授权。
+    Paths(ScopeRoots),
+}
+
+/// 非空且已压缩的范围根路径集合，可以同时包含路径模式。
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct ScopeRoots {
+    roots: Vec<ScopePath>,
+    patterns: Vec<ScopePattern>,
+}
+
+impl ScopeRoots {
+    /// 创建已校验的范围根路径集合。
+    pub fn new(roots: Vec<ScopePath>) -> Result<Self> {
+        Self::with_patterns(roots, Vec::new())
+    }
+
+    /// 创建同时包含根路径和路径模式的集合，两者合计不能为空。
+    pub fn with_patterns(roots: Vec<ScopePath>, patterns: Vec<ScopePattern>) -> Result<Self> {
+        if roots.is_empty() && patterns.is_empty() {
+            return Err(Error::InvalidScope(
+                "grant scope paths must not be empty".to_string(),
+            ));
+        }
+        let (roots, patterns) = compact_scope(roots, patterns);
+        Ok(Self { roots, patterns })
+    }
+
+    /// 返回压缩后的根路径。
+    pub fn as_slice(&self) -> &[ScopePath] {
+        &self.roots
+    }
+
+    /// 返回压缩后的路径模式。
+    pub fn patterns(&self) -> &[ScopePattern] {
+        &self.patterns
+    }
+
+    /// 消耗包装类型并返回根路径，会丢弃路径模式。
+    #[deprecated(note = "drops scope patterns, use `ScopeRoots::into_parts` instead")]
+    pub fn into_vec(self) -> Vec<ScopePath> {
+        self.roots
+    }
+
+    /// 消耗包装类型并返回根路径和路径模式。
+    pub fn into_parts(self) -> (Vec<ScopePath>, Vec<ScopePattern>) {
+        (self.roots, self.patterns)
+    }
+
+    /// 返回根路径或路径模式是否覆盖目标路径。
+    pub fn allows(&self, target: &ScopePath) -> bool {
+        paths_allow(&self.roots, &self.patterns, target)
+    }
+}
+
+impl GrantScope {
+    /// 创建租户级授权范围。
+    pub fn tenant() -> Self {
+        Self::Tenant
+    }
+
+    /// 创建路径级授权范围。
+    pub fn paths(roots: Vec<ScopePath>) -> Result<Self> {
+        ScopeRoots::new(roots).map(Self::Paths)
+    }
+
+    /// 创建包含路径模式的路径级授权范围。
+    pub fn paths_with_patterns(roots: Vec<ScopePath>, patterns: Vec<ScopePattern>) -> Result<Self> {
+        ScopeRoots::with_patterns(roots, patterns).map(Self::Paths)
+    }
+
+    /// 返回该授权是否覆盖整个租户。
+    pub fn is_tenant(&self) -> bool {
+        matches!(self, Self::Tenant)
+    }
+
+    /// 返回路径级授权的根路径。
+    pub fn roots(&self) -> &[ScopePath] {
+        match self {
+            Self::Tenant => &[],
+            Self::Paths(roots) => roots.as_slice(),
+        }
+    }
+
+    /// 返回路径级授权的路径模式。
+    pub fn patterns(&self) -> &[ScopePattern] {
+        match self {
+            Self::Tenant => &[],
+            Self::Paths(roots) => roots.patterns(),
+        }
+    }
+
+    /// 返回该授权是否覆盖目标路径。
+    pub fn allows_path(&self, target: &ScopePath) -> bool {
+        match self {
+            Self::Tenant => true,
+            Self::Paths(roots) => roots.allows(target),
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for ScopeRoots {
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        use serde::ser::SerializeSeq;
+
+        // 根路径和模式共用一个字符串列表，含 `*` 的条目是模式。
+        let mut seq = serializer.serialize_seq(Some(self.roots.len() + self.patterns.len()))?;
+        for root in &self.roots {
+            seq.serialize_element(root)?;
+        }
+        for pattern in &self.patterns {
+            seq.serialize_element(pattern)?;
+        }
+        seq.end()
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for ScopeRoots {
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let mut roots = Vec::new();
+        let mut patterns = Vec::new();
+        for value in Vec::<String>::deserialize(deserializer)? {
+            if value.contains('*') {
+                patterns.push(ScopePattern::parse(value).map_err(serde::de::Error::custom)?);
+            } else {
+                roots.push(ScopePath::parse(value).map_err(serde::de::Error::custom)?);
+            }
+        }
+        Self::with_patterns(roots, patterns).map_err(serde::de::Error::custom)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for GrantScope {
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        #[derive(serde::Serialize)]
+        #[serde(tag = "type", rename_all = "snake_case")]
+        enum GrantScopeWire<'a> {
+            Tenant,
+            Paths { roots: &'a ScopeRoots },
+        }
+        match self {
+            Self::Tenant => GrantScopeWire::Tenant.serialize(serializer),
+            Self::Paths(roots) => GrantScopeWire::Paths { roots }.serialize(serializer),
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for GrantScope {
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        #[derive(serde::Deserialize)]
+        #[serde(tag = "type", rename_all = "snake_case")]
+        enum GrantScopeWire {
+            Tenant,
+            Paths { roots: ScopeRoots },
+        }
+        match GrantScopeWire::deserialize(deserializer)? {
+            GrantScopeWire::Tenant => Ok(Self::Tenant),
+            GrantScopeWire::Paths { roots } => Ok(Self::Paths(roots)),
+        }
+    }
+}
+
+/// 针对具体权限查询合并后的访问范围。
+///
+/// 显式拒绝扣除的部分只出现在 [`AccessScope::Restricted`] 中；`Tenant` 和 `Paths`
+/// 总是表示完整的允许范围，匹配它们时不会漏掉排除部分。
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(tag = "type", rename_all = "snake_case")
+)]
+pub enum AccessScope {
+    /// 没有匹配的访问权。
+    None,
+    /// 租户级访问权。
+    Tenant {
+        /// 查询所属租户。
+        tenant: TenantId,
+    },
+    /// 基于路径根的访问权。
+    Paths {
+        /// 查询所属租户。
+        tenant: TenantId,
+        /// 覆盖所有允许后代路径的压缩根路径。
+        roots: Vec<ScopePath>,
+        /// 允许访问的路径模式，与根路径一样覆盖所匹配节点的后代。
+        #[cfg_attr(
+            feature = "serde",
+            serde(default, skip_serializing_if = "Vec::is_empty")
+        )]
+        patterns: Vec<ScopePattern>,
+    },
+    /// 扣除了显式拒绝部分的租户级或路径级访问权。
+    Restricted(RestrictedScope),
+}
+
+impl AccessScope {
+    /// 将授权范围合并为最终访问范围。
+    pub fn merge(tenant: TenantId, grants: impl IntoIterator<Item = GrantScope>) -> Self {
+        let mut roots = Vec::new();
+        let mut patterns = Vec::new();
+        for grant in grants {
+            match grant {
+                GrantScope::Tenant => return Self::Tenant { tenant },
+                GrantScope::Paths(grant_roots) => {
+                    let (grant_roots, grant_patterns) = grant_roots.into_parts();
+                    roots.extend(grant_roots);
+                    patterns.extend(grant_patterns);
+                }
+            }
+        }
+        if roots.is_empty() && patterns.is_empty() {
+            Self::None
+        } else {
+            let (roots, patterns) = compact_scope(roots, patterns);
+            Self::Paths {
+                tenant,
+                roots,
+                patterns,
+            }
+        }
+    }
+
+    /// 从访问范围中扣除显式拒绝的授权范围。
+    ///
+    /// 租户级拒绝会清空访问范围；覆盖整个允许根路径或模式的拒绝会删除它，
+    /// 只覆盖部分范围的拒绝会记录为排除路径或排除模式，结果为 [`AccessScope::Restricted`]。
+    pub fn subtract(self, denied: impl IntoIterator<Item = GrantScope>) -> Self {
+        let mut denied_roots = Vec::new();
+        let mut denied_patterns = Vec::new();
+        for grant in denied {
+            match grant {
+                GrantScope::Tenant => return Self::None,
+                GrantScope::Paths(roots) => {
+                    let (roots, patterns) = roots.into_parts();
+                    denied_roots.extend(roots);
+                    denied_patterns.extend(patterns);
+                }
+            }
+        }
+        if denied_roots.is_empty() && denied_patterns.is_empty() {
+            return self;
+        }
+        let (denied_roots, denied_patterns) = compact_scope(denied_roots, denied_patterns);
+        let Some(mut parts) = RestrictedScope::split(self) else {
+            return Self::None;
+        };
+
+        match parts.allowed.take() {
+            None => {
+                parts.excluded.extend(denied_roots);
+                parts.excluded_patterns.extend(denied_patterns);
+            }
+            Some((roots, patterns)) => {
+                let roots: Vec<_> = roots
+                    .into_iter()
+                    .filter(|root| {
+                        !denied_roots.iter().any(|denied| denied.allows(root))
+                            && !denied_patterns
+                                .iter()
+                                .any(|denied| denied.covers_path(root))
+                    })
+                    .collect();
+                let patterns: Vec<_> = patterns
+                    .into_iter()
+                    .filter(|pattern| {
+                        !denied_roots
+                            .iter()
+                            .any(|denied| pattern.covered_by_path(denied))
+                            && !denied_patterns.iter().any(|denied| denied.covers(pattern))
+                    })
+                    .collect();
+                if roots.is_empty() && patterns.is_empty() {
+                    return Self::None;
+                }
+                parts
+                    .excluded
+                    .extend(denied_roots.into_iter().filter(|denied| {
+                        roots.iter().any(|root| root.is_ancestor_of(denied))
+                            || patterns.iter().any(|pattern| pattern.overlaps_path(denied))
+                    }));
+                parts
+                    .excluded_patterns
+                    .extend(denied_patterns.into_iter().filter(|denied| {
+                        roots.iter().any(|root| denied.overlaps_path(root))
+                            || patterns.iter().any(|pattern| pattern.overlaps(denied))
+                    }));
+                parts.allowed = Some((roots, patterns));
+            }
+        }
+        (parts.excluded, parts.excluded_patterns) =
+            compact_scope(parts.excluded, parts.excluded_patterns);
+        parts.into_scope()
+    }
+
+    /// 计算两个访问范围的交集，例如代办场景下目标主体与操作员范围的交集。
+    ///
+    /// 两侧租户不同时返回 [`AccessScope::None`]，两侧的排除路径都会保留。
+    /// 路径模式会收窄到另一侧的根路径内，两个模式逐段求交，结果只会收窄不会放大。
+    pub fn intersect(self, other: Self) -> Self {
+        let (Some(left), Some(right)) =
+            (RestrictedScope::split(self), RestrictedScope::split(other))
+        else {
+            return Self::None;
+        };
+        if left.tenant != right.tenant {
+            return Self::None;
+        }
+        let tenant = left.tenant;
+        let allowed = match (left.allowed, right.allowed) {
+            (None, None) => Self::Tenant { tenant },
+            (None, Some((roots, patterns))) | (Some((roots, patterns)), None) => {
+                Self::merge(tenant, paths_grant(roots, patterns))
+            }
+            (Some(left_allowed), Some(right_allowed)) => {
+                let (roots, patterns) = intersect_paths(&left_allowed, &right_allowed);
+                Self::merge(tenant, paths_grant(roots, patterns))
+            }
+        };
+        let mut excluded = left.excluded;
+        excluded.extend(right.excluded);
+        let mut excluded_patterns = left.excluded_patterns;
+        excluded_patterns.extend(right.excluded_patterns);
+        allowed.subtract(paths_grant(excluded, excluded_patterns))
+    }
+
+    /// 返回该范围是否完整覆盖授权范围，例如校验委托的分配范围是否在委托人范围内。
+    ///
+    /// 授权范围与任一排除路径或排除模式可能相交时按不覆盖处理。
+    pub fn covers(&self, grant: &GrantScope) -> bool {
+        let Some(parts) = RestrictedScope::split(self.clone()) else {
+            return false;
+        };
+        let GrantScope::Paths(grant_roots) = grant else {
+            return parts.allowed.is_none() && !self.has_exclusions();
+        };
+        let root_covered = |root: &ScopePath| match &parts.allowed {
+            None => true,
+            Some((roots, patterns)) => {
+                roots.iter().any(|allowed| allowed.allows(root))
+                    || patterns.iter().any(|pattern| pattern.covers_path(root))
+            }
+        };
+        let pattern_covered = |pattern: &ScopePattern| match &parts.allowed {
+            None => true,
+            Some((roots, patterns)) => {
+                roots.iter().any(|root| pattern.covered_by_path(root))
+                    || patterns.iter().any(|allowed| allowed.covers(pattern))
+            }
+        };
+        grant_roots.as_slice().iter().all(|root| {
+            root_covered(root)
+                && !parts
+                    .excluded
+                    .iter()
+                    .any(|excluded| excluded.allows(root) || root.allows(excluded))
+                && !parts
+                    .excluded_patterns
+                    .iter()
+                    .any(|excluded| excluded.overlaps_path(root))
+        }) && grant_roots.patterns().iter().all(|pattern| {
+            pattern_covered(pattern)
+                && !parts
+                    .excluded
+                    .iter()
+                    .any(|excluded| pattern.overlaps_path(excluded))
+                && !parts
+                    .excluded_patterns
+                    .iter()
+                    .any(|excluded| excluded.overlaps(pattern))
+        })
+    }
+
+    /// 返回被显式拒绝授权扣除的根路径。
+    pub fn excluded(&self) -> &[ScopePath] {
+        match self {
+            Self::Restricted(scope) => scope.excluded(),
+            Self::None | Self::Tenant { .. } | Self::Paths { .. } => &[],
+        }
+    }
+
+    /// 返回被显式拒绝授权扣除的路径模式。
+    pub fn excluded_patterns(&self) -> &[ScopePattern] {
+        match self {
+            Self::Restricted(scope) => scope.excluded_patterns(),
+            Self::None | Self::Tenant { .. } | Self::Paths { .. } => &[],
+        }
+    }
+
+    /// 返回该范围是否有被显式拒绝扣除的部分。
+    pub fn has_exclusions(&self) -> bool {
+        matches!(self, Self::Restricted(_))
+    }
+
+    /// 返回该范围是否允许访问目标路径。
+    pub fn allows_path(&self, target: &ScopePath) -> bool {
+        match self {
+            Self::None => false,
+            Self::Tenant { .. } => true,
+            Self::Paths {
+                roots, patterns, ..
+            } => paths_allow(roots, patterns, target),
+            Self::Restricted(scope) => scope.allows_path(target),
+        }
+    }
+
+    /// 返回目标路径是否被显式拒绝授权扣除。
+    pub fn excludes_path(&self, target: &ScopePath) -> bool {
+        paths_allow(self.excluded(), self.excluded_patterns(), target)
+    }
+}
+
+/// 扣除了显式拒绝部分的访问范围，字段只能通过访问器读取。
+///
+/// 下推到查询时必须同时处理允许部分和排除部分：只使用 [`RestrictedScope::roots`]
+/// 会把被拒绝的数据一起查出来。
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(try_from = "RestrictedScopeWire", into = "RestrictedScopeWire")
+)]
+pub struct RestrictedScope {
+    tenant: TenantId,
+    /// 扣除前的允许范围，`None` 表示整个租户。
+    allowed: Option<(Vec<ScopePath>, Vec<ScopePattern>)>,
+    excluded: Vec<ScopePath>,
+    excluded_patterns: Vec<ScopePattern>,
+}
+
+impl RestrictedScope {
+    /// 返回查询所属租户。
+    pub fn tenant(&self) -> &TenantId {
+        &self.tenant
+    }
+
+    /// 返回扣除前的允许范围是否覆盖整个租户。
+    pub fn is_tenant_wide(&self) -> bool {
+        self.allowed.is_none()
+    }
+
+    /// 返回扣除前允许访问的压缩根路径，租户级范围时为空。
+    pub fn roots(&self) -> &[ScopePath] {
+        match &self.allowed {
+            None => &[],
+            Some((roots, _)) => roots,
+        }
+    }
+
+    /// 返回扣除前允许访问的路径模式，租户级范围时为空。
+    pub fn patterns(&self) -> &[ScopePattern] {
+        match &self.allowed {
+            None => &[],
+            Some((_, patterns)) => patterns,
+        }
+    }
+
+    /// 返回被显式拒绝授权扣除的压缩根路径，均与允许范围相交。
+    pub fn excluded(&self) -> &[ScopePath] {
+        &self.excluded
+    }
+
+    /// 返回被显式拒绝授权扣除的路径模式，均与允许范围相交。
+    pub fn excluded_patterns(&self) -> &[ScopePattern] {
+        &self.excluded_patterns
+    }
+
+    /// 返回该范围是否允许访问目标路径。
+    pub fn allows_path(&self, target: &ScopePath) -> bool {
+        let allowed = match &self.allowed {
+            None => true,
+            Some((roots, patterns)) => paths_allow(roots, patterns, target),
+        };
+        allowed && !paths_allow(&self.excluded, &self.excluded_patterns, target)
+    }
+
+    /// 拆解访问范围，没有访问权时返回 `None`；结果的排除部分可能为空。
+    fn split(scope: AccessScope) -> Option<Self> {
+        match scope {
+            AccessScope::None => None,
+            AccessScope::Tenant { tenant } => Some(Self {
+                tenant,
+                allowed: None,
+                excluded: Vec::new(),
+                excluded_patterns: Vec::new(),
+            }),
+            AccessScope::Paths {
+                tenant,
+                roots,
+                patterns,
+            } => Some(Self {
+                tenant,
+                allowed: Some((roots, patterns)),
+                excluded: Vec::new(),
+                excluded_patterns: Vec::new(),
+            }),
+            AccessScope::Restricted(scope) => Some(scope),
+        }
+    }
+
+    /// 组装访问范围，没有排除部分时退回 `Tenant` 或 `Paths`。
+    fn into_scope(self) -> AccessScope {
+        if !self.excluded.is_empty() || !self.excluded_patterns.is_empty() {
+            return AccessScope::Restricted(self);
+        }
+        match self.allowed {
+            None => AccessScope::Tenant {
+                tenant: self.tenant,
+            },
+            Some((roots, patterns)) if roots.is_empty() && patterns.is_empty() => AccessScope::None,
+            Some((roots, patterns)) => AccessScope::Paths {
+                tenant: self.tenant,
+                roots,
+                patterns,
+            },
+        }
+    }
+}
+
+/// [`RestrictedScope`] 的序列化格式，省略 `roots` 表示扣除前覆盖整个租户。
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize, serde::Deserialize)]
+struct RestrictedScopeWire {
+    tenant: TenantId,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    roots: Option<Vec<ScopePath>>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    patterns: Vec<ScopePattern>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    excluded: Vec<ScopePath>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    excluded_patterns: Vec<ScopePattern>,
+}
+
+#[cfg(feature = "serde")]
+impl From<RestrictedScope> for RestrictedScopeWire {
+    fn from(scope: RestrictedScope) -> Self {
+        let (roots, patterns) = match scope.allowed {
+            None => (None, Vec::new()),
+            Some((roots, patterns)) => (Some(roots), patterns),
+        };
+        Self {
+            tenant: scope.tenant,
+            roots,
+            patterns,
+            excluded: scope.excluded,
+            excluded_patterns: scope.excluded_patterns,
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<RestrictedScopeWire> for RestrictedScope {
+    type Error = Error;
+
+    fn try_from(wire: RestrictedScopeWire) -> Result<Self> {
+        if wire.excluded.is_empty() && wire.excluded_patterns.is_empty() {
+            return Err(Error::InvalidScope(
+                "restricted scope must have excluded paths or patterns".to_string(),
+            ));
+        }
+        let allowed = match wire.roots {
+            None if wire.patterns.is_empty() => None,
+            None => {
+                return Err(Error::InvalidScope(
+                    "tenant-wide restricted scope must not have patterns".to_string(),
+                ));
+            }
+            Some(roots) if roots.is_empty() && wire.patterns.is_empty() => {
+                return Err(Error::InvalidScope(
+                    "restricted scope paths must not be empty".to_string(),
+                ));
+            }
+            Some(roots) => Some(compact_scope(roots, wire.patterns)),
+        };
+        let (excluded, excluded_patterns) = compact_scope(wire.excluded, wire.excluded_patterns);
+        Ok(Self {
+            tenant: wire.tenant,
+            allowed,
+            excluded,
+            excluded_patterns,
+        })
+    }
+}
+
+/// 返回根路径或路径模式是否覆盖目标路径。
+fn paths_allow(roots: &[ScopePath], patterns: &[ScopePattern], target: &ScopePath) -> bool {
+    roots.iter().any(|root| root.allows(target))
+        || patterns.iter().any(|pattern| pattern.matches(target))
+}
+
+/// 把根路径和模式包装为路径级授权范围，两者都为空时返回 `None`。
+fn paths_grant(roots: Vec<ScopePath>, patterns: Vec<ScopePattern>) -> Option<GrantScope> {
+    ScopeRoots::with_patterns(roots, patterns)
+        .ok()
+        .map(GrantScope::Paths)
+}
+
+/// 计算两组根路径和模式的交集，结果中的条目都能被两侧同时完整覆盖。
+fn intersect_paths(
+    (left_roots, left_patterns): &(Vec<ScopePath>, Vec<ScopePattern>),
+    (right_roots, right_patterns): &(Vec<ScopePath>, Vec<ScopePattern>),
+) -> (Vec<ScopePath>, Vec<ScopePattern>) {
+    let mut roots = Vec::new();
+    let mut patterns = Vec::new();
+    for left in left_roots {
+        for right in right_roots {
+            if left.allows(right) {
+                roots.push(right.clone());
+            } else if right.allows(left) {
+                roots.push(left.clone());
+            }
+        }
+    }
+    for (side_roots, other_patterns) in [(left_roots, right_patterns), (right_roots, left_patterns)]
+    {
+        for root in side_roots {
+            for pattern in other_patterns {
+                if pattern.covers_path(root) {
+                    roots.push(root.clone());
+                } else if let Some(narrowed) = pattern.narrow_to(root) {
+                    let (narrowed_roots, narrowed_patterns) = narrowed.into_parts();
+                    roots.extend(narrowed_roots);
+                    patterns.extend(narrowed_patterns);
+                }
+            }
+        }
+    }
+    for left in left_patterns {
+        for right in right_patterns {
+            if let Some(met) = left.meet(right) {
+                let (met_roots, met_patterns) = met.into_parts();
+                roots.extend(met_roots);
+                patterns.extend(met_patterns);
+            }
+        }
+    }
+    (roots, patterns)
+}
+
+/// 对根路径去重，并删除已被祖先路径覆盖的子路径。
+fn compact_paths(roots: Vec<ScopePath>) -> Vec<ScopePath> {
+    let ordered: BTreeSet<_> = roots.into_iter().collect();
+    let mut compacted: Vec<ScopePath> = Vec::new();
+    for path in ordered {
+        if compacted.iter().any(|root| root.allows(&path)) {
+            continue;
+        }
+        compacted.push(path);
+    }
+    compacted
+}
+
+/// 压缩根路径和模式，删除被其他根路径或模式完全覆盖的条目。
+///
+/// 根路径与 `/**` 模式互相覆盖时保留根路径。
+fn compact_scope(
+    roots: Vec<ScopePath>,
+    patterns: Vec<ScopePattern>,
+) -> (Vec<ScopePath>, Vec<ScopePattern>) {
+    let roots = compact_paths(roots);
+    let ordered: BTreeSet<_> = patterns.into_iter().collect();
+    let patterns: Vec<ScopePattern> = ordered
+        .iter()
+        .filter(|pattern| {
+            !roots.iter().any(|root| pattern.covered_by_path(root))
+                && !ordered
+                    .iter()
+                    .any(|other| other != *pattern && other.covers(pattern))
+        })
+        .cloned()
+        .collect();
+    let roots = roots
+        .into_iter()
+        .filter(|root| !patterns.iter().any(|pattern| pattern.covers_path(root)))
+        .collect();
+    (roots, patterns)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        AccessScope, GrantScope, MAX_SCOPE_PATH_LEN, RestrictedScope, ScopePath, ScopePattern,
+    };
+    use crate::TenantId;
+
+    #[test]
+    fn access_scope_covers_should_reject_grants_touching_exclusions() {
+        let tenant = TenantId::parse("tenant_1").expect("tenant");
+        let path = |value: &str| ScopePath::parse(value).expect("scope path");
+        let paths = |values: &[&str]| {
+            GrantScope::paths(values.iter().map(|value| path(value)).collect())
+                .expect("grant scope")
+        };
+        let lead = AccessScope::merge(tenant.clone(), [paths(&["agent/12"])])
+            .subtract([paths(&["agent/12/store/9"])]);
+
+        assert!(lead.covers(&paths(&["agent/12/store/1", "agent/12/team"])));
+        assert!(!lead.covers(&paths(&["agent/12"])));
+        assert!(!lead.covers(&paths(&["agent/13"])));
+        assert!(!lead.covers(&GrantScope::tenant()));
+        assert!(
+            lead.covers(
+                &GrantScope::paths_with_patterns(
+                    Vec::new(),
+                    vec![ScopePattern::parse("agent/12/team/*").expect("pattern")]
+                )
+                .expect("grant scope")
+            )
+        );
+        assert!(
+            !lead.covers(
+                &GrantScope::paths_with_patterns(
+                    Vec::new(),
+                    vec![ScopePattern::parse("agent/12/store/*").expect("pattern")]
+                )
+                .expect("grant scope")
+            )
+        );
+        assert!(AccessScope::merge(tenant, [GrantScope::tenant()]).covers(&GrantScope::tenant()));
+        assert!(!AccessScope::None.covers(&paths(&["agent/12"])));
+    }
+
+    #[test]
+    fn access_scope_intersect_should_keep_narrower_side() {
+        let tenant = TenantId::parse("tenant_1").expect("tenant");
+        let path = |value: &str| ScopePath::parse(value).expect("scope path");
+        let paths = |values: &[&str]| {
+            GrantScope::paths(values.iter().map(|value| path(value)).collect())
+                .expect("grant scope")
+        };
+        let member = AccessScope::merge(tenant.clone(), [paths(&["agent/1", "agent/2/store/5"])])
+            .subtract([paths(&["agent/1/store/9"])]);
+        let operator = AccessScope::merge(tenant.clone(), [paths(&["agent/1/store", "agent/2"])]);
+
+        let both = member.clone().intersect(operator.clone());
+        assert!(both.allows_path(&path("agent/1/store/3")));
+        assert!(both.allows_path(&path("agent/2/store/5/order/1")));
+        assert!(!both.allows_path(&path("agent/1/store/9")));
+        assert!(!both.allows_path(&path("agent/1/user/1")));
+        assert!(!both.allows_path(&path("agent/2/store/6")));
+
+        let whole = AccessScope::merge(tenant.clone(), [GrantScope::tenant()]);
+        assert_eq!(whole.clone().intersect(operator.clone()), operator);
+        assert_eq!(
+            member.intersect(AccessScope::merge(
+                TenantId::parse("tenant_2").expect("tenant"),
+                [GrantScope::tenant()]
+            )),
+            AccessScope::None
+        );
+        assert_eq!(operator.intersect(AccessScope::None), AccessScope::None);
+
+        let pattern = GrantScope::paths_with_patterns(
+            Vec::new(),
+            vec![ScopePattern::parse("agent/*/store/**").expect("scope pattern")],
+        )
+        .expect("grant scope");
+        let by_pattern = whole
+            .intersect(AccessScope::merge(tenant.clone(), [pattern]))
+            .intersect(AccessScope::merge(tenant, [paths(&["agent/3"])]));
+        assert!(by_pattern.allows_path(&path("agent/3/store/1")));
+        assert!(!by_pattern.allows_path(&path("agent/3/user/1")));
+        assert!(!by_pattern.allows_path(&path("agent/4/store/1")));
+    }
+
+    #[test]
+    fn scope_path_should_allow_descendant() {
+        let root = ScopePath::parse("agent/123").expect("scope path");
+        let target = ScopePath::parse("agent/123/store/456").expect("scope path");
+
+        assert!(root.allows(&target));
+        assert!(!target.allows(&root));
+    }
+
+    #[test]
+    fn scope_path_should_not_allow_prefix_sibling() {
+        let root = ScopePath::parse("agent/1").expect("scope path");
+        let sibling = ScopePath::parse("agent/10/store/456").expect("scope path");
+
+        assert!(!root.allows(&sibling));
+    }
+
+    #[test]
+    fn scope_path_should_reject_invalid_boundaries() {
+        for value in ["", "agent//1", "agent/中文"] {
+            let err = ScopePath::parse(value).expect_err("must reject");
+            assert!(err.to_string().contains("scope path"));
+        }
+
+        let oversized = "a".repeat(MAX_SCOPE_PATH_LEN + 1);
+        let err = ScopePath::parse(oversized).expect_err("must reject");
+        assert!(err.to_string().contains("length must be"));
+    }
+
+    #[test]
+    fn grant_scope_paths_should_reject_empty_roots() {
+        let err = GrantScope::paths(Vec::new()).expect_err("must reject");
+        assert!(err.to_string().contains("must not be empty"));
+    }
+
+    #[test]
+    fn access_scope_should_compact_child_paths() {
+        let tenant = TenantId::parse("tenant_1").expect("tenant");
+        let parent = ScopePath::parse("agent/123").expect("scope path");
+        let child = ScopePath::parse("agent/123/store/456").expect("scope path");
+
+        let scope = AccessScope::merge(
+            tenant,
+            [GrantScope::paths(vec![child, parent]).expect("grant scope")],
+        );
+
+        let AccessScope::Paths { roots, .. } = scope else {
+            panic!("expected paths");
+        };
+        assert_eq!(roots.len(), 1);
+        assert_eq!(roots[0].as_str(), "agent/123");
+    }
+
+    #[test]
+    fn access_scope_subtract_should_drop_covered_roots_and_exclude_subtrees() {
+        let tenant = TenantId::parse("tenant_1").expect("tenant");
+        let scope = AccessScope::merge(
+            tenant.clone(),
+            [GrantScope::paths(vec![
+                ScopePath::parse("agent/1").expect("scope path"),
+                ScopePath::parse("agent/2").expect("scope path"),
+            ])
+            .expect("grant scope")],
+        );
+
+        let scope = scope.subtract([GrantScope::paths(vec![
+            ScopePath::parse("agent/1/store/7").expect("scope path"),
+            ScopePath::parse("agent/2").expect("scope path"),
+            ScopePath::parse("agent/3").expect("scope path"),
+        ])
+        .expect("grant scope")]);
+
+        assert_eq!(
+            scope,
+            AccessScope::Restricted(RestrictedScope {
+                tenant,
+                allowed: Some((
+                    vec![ScopePath::parse("agent/1").expect("scope path")],
+                    Vec::new()
+                )),
+                excluded: vec![ScopePath::parse("agent/1/store/7").expect("scope path")],
+                excluded_patterns: Vec::new(),
+            })
+        );
+        assert!(scope.allows_path(&ScopePath::parse("agent/1/store/8").expect("scope path")));
+        assert!(!scope.allows_path(&ScopePath::parse("agent/1/store/7/order/1").expect("path")));
+    }
+
+    #[test]
+    fn access_scope_subtract_should_clear_scope_for_tenant_deny() {
+        let tenant = TenantId::parse("tenant_1").expect("tenant");
+        let scope = AccessScope::merge(tenant, [GrantScope::tenant()]);
+
+        assert_eq!(scope.subtract([GrantScope::tenant()]), AccessScope::None);
+    }
+
+    #[test]
+    fn access_scope_should_compact_patterns_by_subsumption() {
+        let tenant = TenantId::parse("tenant_1").expect("tenant");
+        let scope = AccessScope::merge(
+            tenant.clone(),
+            [
+                GrantScope::paths_with_patterns(
+                    vec![
+                        ScopePath::parse("agent/1").expect("scope path"),
+                        ScopePath::parse("region/eu/warehouse/3").expect("scope path"),
+                    ],
+                    vec![
+                        ScopePattern::parse("agent/1/store/*").expect("scope pattern"),
+                        ScopePattern::parse("region/*/warehouse/**").expect("scope pattern"),
+                    ],
+                )
+                .expect("grant scope"),
+                GrantScope::paths_with_patterns(
+                    Vec::new(),
+                    vec![
+                        ScopePattern::parse("region/eu/warehouse/*").expect("scope pattern"),
+                        ScopePattern::parse("agent/*/store/42").expect("scope pattern"),
+                    ],
+                )
+                .expect("grant scope"),
+            ],
+        );
+
+        assert_eq!(
+            scope,
+            AccessScope::Paths {
+                tenant,
+                roots: vec![ScopePath::parse("agent/1").expect("scope path")],
+                patterns: vec![
+                    ScopePattern::parse("agent/*/store/42").expect("scope pattern"),
+                    ScopePattern::parse("region/*/warehouse/**").expect("scope pattern"),
+                ],
+            }
+        );
+        assert!(scope.allows_path(&ScopePath::parse("agent/9/store/42").expect("path")));
+        assert!(scope.allows_path(&ScopePath::parse("agent/9/store/42/order/1").expect("path")));
+        assert!(!scope.allows_path(&ScopePath::parse("agent/9/store/41").expect("path")));
+        assert!(scope.allows_path(&ScopePath::parse("region/us/warehouse/1/bin").expect("path")));
+    }
+
+    #[test]
+    fn access_scope_subtract_should_handle_pattern_denies() {
+        let tenant = TenantId::parse("tenant_1").expect("tenant");
+        let scope = AccessScope::merge(
+            tenant.clone(),
+            [GrantScope::paths_with_patterns(
+                vec![ScopePath::parse("agent/1").expect("scope path")],
+                vec![ScopePattern::parse("region/*/warehouse").expect("scope pattern")],
+            )
+            .expect("grant scope")],
+        );
+
+        let scope = scope.subtract([GrantScope::paths_with_patterns(
+            Vec::new(),
+            vec![
+                ScopePattern::parse("*/*/warehouse").expect("scope pattern"),
+                ScopePattern::parse("agent/1/store/*/**").expect("scope pattern"),
+                ScopePattern::parse("team/*").expect("scope pattern"),
+            ],
+        )
+        .expect("grant scope")]);
+
+        assert_eq!(
+            scope,
+            AccessScope::Restricted(RestrictedScope {
+                tenant,
+                allowed: Some((
+                    vec![ScopePath::parse("agent/1").expect("scope path")],
+                    Vec::new()
+                )),
+                excluded: Vec::new(),
+                excluded_patterns: vec![
+                    ScopePattern::parse("*/*/warehouse/**").expect("scope pattern"),
+                    ScopePattern::parse("agent/1/store/*/**").expect("scope pattern"),
+                ],
+            })
+        );
+        assert!(scope.allows_path(&ScopePath::parse("agent/1/store").expect("path")));
+        assert!(!scope.allows_path(&ScopePath::parse("agent/1/warehouse").expect("path")));
+        assert!(!scope.allows_path(&ScopePath::parse("agent/1/store/7/order").expect("path")));
+    }
+
+    #[test]
+    fn access_scope_subtract_should_deny_pattern_subtree() {
+        let tenant = TenantId::parse("tenant_1").expect("tenant");
+        let path = |value: &str| ScopePath::parse(value).expect("scope path");
+        let scope = AccessScope::merge(
+            tenant,
+            [GrantScope::paths(vec![path("agent")]).expect("grant scope")],
+        )
+        .subtract([GrantScope::paths_with_patterns(
+            Vec::new(),
+            vec![ScopePattern::parse("agent/*/store/7").expect("scope pattern")],
+        )
+        .expect("grant scope")]);
+
+        assert_eq!(
+            scope.excluded_patterns(),
+            [ScopePattern::parse("agent/*/store/7/**").expect("scope pattern")]
+        );
+        assert!(!scope.allows_path(&path("agent/3/store/7")));
+        assert!(!scope.allows_path(&path("agent/3/store/7/invoice/1")));
+        assert!(scope.allows_path(&path("agent/3/store/8/invoice/1")));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_should_reject_empty_grant_paths() {
+        let err = serde_json::from_str::<GrantScope>(r#"{"type":"paths","roots":[]}"#)
+            .expect_err("must reject");
+        assert!(err.to_string().contains("must not be empty"));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_should_round_trip_mixed_roots_and_patterns() {
+        let json = r#"{"type":"paths","roots":["agent/1","agent/*/store/42"]}"#;
+        let scope = serde_json::from_str::<GrantScope>(json).expect("grant scope");
+
+        assert_eq!(scope.roots().len(), 1);
+        assert_eq!(scope.patterns()[0].as_str(), "agent/*/store/42/**");
+        assert_eq!(
+            serde_json::to_string(&scope).expect("json"),
+            r#"{"type":"paths","roots":["agent/1","agent/*/store/42/**"]}"#
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_should_omit_empty_exclusions() {
+        let tenant = TenantId::parse("tenant_1").expect("tenant");
+        let roots = vec![ScopePath::parse("agent/1").expect("scope path")];
+        let scope = AccessScope::merge(tenant, [GrantScope::paths(roots).expect("grant scope")]);
+
+        assert_eq!(
+            serde_json::to_string(&scope).expect("json"),
+            r#"{"type":"paths","tenant":"tenant_1","roots":["agent/1"]}"#
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_should_round_trip_restricted_scope_and_reject_missing_exclusions() {
+        let json = r#"{"type":"restricted","tenant":"tenant_1","excluded":["agent/7"]}"#;
+        let scope = serde_json::from_str::<AccessScope>(json).expect("access scope");
+
+        assert!(
+            matches!(&scope, AccessScope::Restricted(restricted) if restricted.is_tenant_wide())
+        );
+        assert!(!scope.allows_path(&ScopePath::parse("agent/7/store/1").expect("path")));
+        assert_eq!(serde_json::to_string(&scope).expect("json"), json);
+
+        let err =
+            serde_json::from_str::<AccessScope>(r#"{"type":"restricted","tenant":"tenant_1"}"#)
+                .expect_err("must reject");
+        assert!(err.to_string().contains("excluded"));
+    }
+}
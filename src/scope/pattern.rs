@@ -0,0 +1,332 @@
+use super::{MAX_SCOPE_PATH_LEN, ScopePath, ScopeRoots, validate_segment};
+use crate::error::{Error, Result};
+use std::fmt;
+
+/// 带通配段的范围路径模式，例如 `agent/*/store/42` 或 `region/*/warehouse/**`。
+///
+/// `*` 匹配恰好一段；`**` 只能作为最后一段，匹配零段或多段。模式与普通根路径语义一致，
+/// 总是同时覆盖所匹配节点的后代，因此 `agent/*` 与 `agent/*/**` 等价，解析后统一为后者。
+#[derive(Clone, Debug, Eq, PartialEq, Hash, Ord, PartialOrd)]
+pub struct ScopePattern(String);
+
+impl ScopePattern {
+    /// 解析并校验范围路径模式，至少需要包含一个 `*` 或 `**` 段。
+    pub fn parse(value: impl AsRef<str>) -> Result<Self> {
+        let trimmed = value.as_ref().trim();
+        if trimmed.is_empty() {
+            return Err(Error::InvalidScope(
+                "scope pattern must not be empty".to_string(),
+            ));
+        }
+        let segments: Vec<&str> = trimmed.split('/').collect();
+        let last = segments.len() - 1;
+        let mut has_wildcard = false;
+        for (index, segment) in segments.iter().enumerate() {
+            match *segment {
+                "*" => has_wildcard = true,
+                "**" if index == last && index > 0 => has_wildcard = true,
+                "**" => {
+                    return Err(Error::InvalidScope(
+                        "`**` must be the last segment of a scope pattern and follow another segment"
+                            .to_string(),
+                    ));
+                }
+                _ => validate_segment(segment)?,
+            }
+        }
+        if !has_wildcard {
+            return Err(Error::InvalidScope(
+                "scope pattern must contain `*` or `**`, use ScopePath for literal paths"
+                    .to_string(),
+            ));
+        }
+        let normalized = if trimmed.ends_with("/**") {
+            trimmed.to_string()
+        } else {
+            format!("{trimmed}/**")
+        };
+        if normalized.len() > MAX_SCOPE_PATH_LEN {
+            return Err(Error::InvalidScope(format!(
+                "scope pattern length must be <= {MAX_SCOPE_PATH_LEN}"
+            )));
+        }
+        Ok(Self(normalized))
+    }
+
+    /// 返回模式字符串。
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// 返回模式是否匹配目标路径，即目标是某个匹配节点或其后代。
+    pub fn matches(&self, target: &ScopePath) -> bool {
+        let fixed = self.fixed_segments();
+        let parts: Vec<&str> = target.as_str().split('/').collect();
+        parts.len() >= fixed.len() && segments_compatible(&fixed, &parts)
+    }
+
+    /// 返回模式是否覆盖 `root` 及其全部后代。
+    pub(crate) fn covers_path(&self, root: &ScopePath) -> bool {
+        self.matches(root)
+    }
+
+    /// 返回模式匹配的所有节点是否都位于 `root` 子树内。
+    pub(crate) fn covered_by_path(&self, root: &ScopePath) -> bool {
+        let fixed = self.fixed_segments();
+        let parts: Vec<&str> = root.as_str().split('/').collect();
+        fixed.len() >= parts.len()
+            && fixed
+                .iter()
+                .zip(&parts)
+                .all(|(segment, part)| *segment == Some(*part))
+    }
+
+    /// 返回当前模式是否覆盖 `other` 匹配的全部节点。
+    pub(crate) fn covers(&self, other: &ScopePattern) -> bool {
+        let fixed = self.fixed_segments();
+        let other_fixed = other.fixed_segments();
+        other_fixed.len() >= fixed.len()
+            && fixed
+                .iter()
+                .zip(&other_fixed)
+                .all(|(segment, other)| segment.is_none() || segment == other)
+    }
+
+    /// 返回模式是否可能匹配 `root` 子树内的某个节点。
+    pub(crate) fn overlaps_path(&self, root: &ScopePath) -> bool {
+        let fixed = self.fixed_segments();
+        let parts: Vec<&str> = root.as_str().split('/').collect();
+        segments_compatible(&fixed, &parts)
+    }
+
+    /// 返回两个模式是否可能匹配同一个节点。
+    pub(crate) fn overlaps(&self, other: &ScopePattern) -> bool {
+        self.fixed_segments()
+            .iter()
+            .zip(&other.fixed_segments())
+            .all(|(left, right)| left.is_none() || right.is_none() || left == right)
+    }
+
+    /// 把模式收窄到 `root` 子树内，例如 `agent/*/store` 收窄到 `agent/3` 得到根路径
+    /// `agent/3/store`；没有交集时返回 `None`。
+    pub(crate) fn narrow_to(&self, root: &ScopePath) -> Option<ScopeRoots> {
+        let fixed = self.fixed_segments();
+        let parts: Vec<&str> = root.as_str().split('/').collect();
+        if !segments_compatible(&fixed, &parts) {
+            return None;
+        }
+        let mut segments = parts.clone();
+        if fixed.len() > parts.len() {
+            segments.extend(
+                fixed[parts.len()..]
+                    .iter()
+                    .map(|segment| segment.unwrap_or("*")),
+            );
+        }
+        roots_from_segments(&segments)
+    }
+
+    /// 计算两个模式都匹配的节点，例如 `agent/*/store` 与 `agent/1/*` 得到根路径
+    /// `agent/1/store`；没有交集时返回 `None`。
+    ///
+    /// 逐段求交：字面段优先于 `*`，字面段不同则没有交集；较短一侧按 `*` 延伸到较长一侧。
+    pub(crate) fn meet(&self, other: &ScopePattern) -> Option<ScopeRoots> {
+        let fixed = self.fixed_segments();
+        let other_fixed = other.fixed_segments();
+        let mut segments = Vec::with_capacity(fixed.len().max(other_fixed.len()));
+        for index in 0..fixed.len().max(other_fixed.len()) {
+            let left = fixed.get(index).copied().flatten();
+            let right = other_fixed.get(index).copied().flatten();
+            segments.push(match (left, right) {
+                (Some(left), Some(right)) if left != right => return None,
+                (Some(literal), _) | (_, Some(literal)) => literal,
+                (None, None) => "*",
+            });
+        }
+        roots_from_segments(&segments)
+    }
+
+    /// 返回去掉结尾 `**` 后的定长段，`None` 表示 `*`。
+    fn fixed_segments(&self) -> Vec<Option<&str>> {
+        self.0
+            .strip_suffix("/**")
+            .unwrap_or(&self.0)
+            .split('/')
+            .map(|segment| (segment != "*").then_some(segment))
+            .collect()
+    }
+}
+
+/// 把逐段求出的结果包装为范围：含 `*` 时是模式，否则是根路径。
+fn roots_from_segments(segments: &[&str]) -> Option<ScopeRoots> {
+    let joined = segments.join("/");
+    if segments.contains(&"*") {
+        ScopeRoots::with_patterns(Vec::new(), vec![ScopePattern::parse(joined).ok()?]).ok()
+    } else {
+        ScopeRoots::new(vec![ScopePath::parse(joined).ok()?]).ok()
+    }
+}
+
+/// 逐段比较模式段与路径段，只比较两者共有的长度。
+fn segments_compatible(fixed: &[Option<&str>], parts: &[&str]) -> bool {
+    fixed
+        .iter()
+        .zip(parts)
+        .all(|(segment, part)| segment.is_none_or(|literal| literal == *part))
+}
+
+impl fmt::Display for ScopePattern {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl AsRef<str> for ScopePattern {
+    fn as_ref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl TryFrom<&str> for ScopePattern {
+    type Error = Error;
+
+    fn try_from(value: &str) -> Result<Self> {
+        Self::parse(value)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for ScopePattern {
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for ScopePattern {
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let value = String::deserialize(deserializer)?;
+        Self::parse(value).map_err(serde::de::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ScopePattern;
+    use crate::ScopePath;
+
+    /// 解析测试模式。
+    fn pattern(value: &str) -> ScopePattern {
+        ScopePattern::parse(value).expect("scope pattern")
+    }
+
+    /// 解析测试路径。
+    fn path(value: &str) -> ScopePath {
+        ScopePath::parse(value).expect("scope path")
+    }
+
+    #[test]
+    fn meet_should_combine_literals_segment_by_segment() {
+        let met = pattern("agent/*/store/**")
+            .meet(&pattern("agent/1/*/**"))
+            .expect("met");
+        assert_eq!(met.as_slice(), &[path("agent/1/store")]);
+
+        let met = pattern("agent/*/**")
+            .meet(&pattern("*/*/store/*"))
+            .expect("met");
+        assert_eq!(met.patterns(), &[pattern("agent/*/store/*")]);
+
+        let met = pattern("agent/*").meet(&pattern("*/1")).expect("met");
+        assert_eq!(met.as_slice(), &[path("agent/1")]);
+
+        let met = pattern("agent/*")
+            .meet(&pattern("agent/*/store"))
+            .expect("met");
+        assert_eq!(met.patterns(), &[pattern("agent/*/store")]);
+
+        assert!(pattern("agent/*").meet(&pattern("store/*")).is_none());
+    }
+
+    #[test]
+    fn narrow_to_should_specialize_pattern_within_root() {
+        let narrowed = pattern("agent/*/store/*/**")
+            .narrow_to(&path("agent/3"))
+            .expect("narrowed");
+        assert_eq!(narrowed.patterns(), &[pattern("agent/3/store/*/**")]);
+
+        let narrowed = pattern("agent/*/**")
+            .narrow_to(&path("agent/3"))
+            .expect("narrowed");
+        assert_eq!(narrowed.as_slice(), &[path("agent/3")]);
+
+        let narrowed = pattern("agent/*")
+            .narrow_to(&path("agent/3/store"))
+            .expect("narrowed");
+        assert_eq!(narrowed.as_slice(), &[path("agent/3/store")]);
+
+        assert!(
+            pattern("agent/*/store")
+                .narrow_to(&path("region/1"))
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn scope_pattern_should_reject_invalid_shapes() {
+        for value in [
+            "",
+            "agent/1",
+            "**",
+            "agent/**/store",
+            "agent/st*re",
+            "agent//*",
+        ] {
+            assert!(
+                ScopePattern::parse(value).is_err(),
+                "{value} must be rejected"
+            );
+        }
+    }
+
+    #[test]
+    fn scope_pattern_should_match_subtrees_like_roots() {
+        let store = pattern("agent/*/store/42");
+        let warehouses = pattern("region/*/warehouse/**");
+
+        assert_eq!(store, pattern("agent/*/store/42/**"));
+        assert!(store.matches(&path("agent/7/store/42")));
+        assert!(store.matches(&path("agent/7/store/42/order/1")));
+        assert!(!store.matches(&path("agent/7/store")));
+        assert!(!store.matches(&path("agent/7/team/1/store/42")));
+        assert!(warehouses.matches(&path("region/eu/warehouse")));
+        assert!(warehouses.matches(&path("region/eu/warehouse/3/bin/9")));
+        assert!(!warehouses.matches(&path("region/eu")));
+    }
+
+    #[test]
+    fn scope_pattern_should_detect_subsumption() {
+        let all_stores = pattern("agent/*/store/**");
+
+        assert!(all_stores.covers(&pattern("agent/*/store/42")));
+        assert!(all_stores.covers(&pattern("agent/*/store/*/order/**")));
+        assert!(!pattern("agent/*/store/42").covers(&all_stores));
+        assert!(!pattern("agent/1/*").covers(&pattern("agent/*/store")));
+        assert!(all_stores.covers_path(&path("agent/1/store")));
+        assert!(pattern("agent/*").covers_path(&path("agent/1")));
+        assert!(!pattern("agent/*/store").covers_path(&path("agent/1")));
+        assert!(pattern("agent/1/*/**").covered_by_path(&path("agent/1")));
+        assert!(!pattern("agent/*/store").covered_by_path(&path("agent/1")));
+        assert!(pattern("agent/*/store/42").overlaps_path(&path("agent/1")));
+        assert!(pattern("agent/*").overlaps_path(&path("agent/1/store")));
+        assert!(!pattern("agent/*/store").overlaps_path(&path("agent/1/team")));
+        assert!(pattern("agent/*/store/**").overlaps(&pattern("agent/1/*/42")));
+        assert!(!pattern("agent/*/store").overlaps(&pattern("agent/1/team/*")));
+    }
+}
@@ -0,0 +1,491 @@
+//! 把 [`AccessScope`] 编译为参数化 SQL 过滤条件。
+//!
+//! 列名会原样拼接进 SQL，只能传入代码里写死的可信标识符；路径一律通过参数绑定。
+
+use crate::error::{Error, Result};
+use crate::scope::{AccessScope, ScopePath, ScopePattern};
+
+/// `LIKE` 使用的转义字符；不用反斜杠，避免 MySQL 字符串字面量的二次转义。
+const LIKE_ESCAPE: char = '!';
+
+/// MySQL 比较时使用的二进制排序规则，`ScopePath` 区分大小写，默认的 `_ci` 规则不区分。
+const MYSQL_BINARY_COLLATION: &str = "utf8mb4_bin";
+
+/// 目标数据库方言，决定占位符写法。
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub enum SqlDialect {
+    /// PostgreSQL，占位符为 `$1`、`$2`……
+    Postgres,
+    /// MySQL，占位符为 `?`。
+    MySql,
+    /// SQLite，占位符为 `?`。
+    Sqlite,
+}
+
+/// 范围路径在表中的存储方式。
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+enum PathEncoding {
+    /// 物化路径字符串列，例如 `agent/1/store/2`，用等值加前缀 `LIKE` 匹配。
+    MaterializedPath,
+    /// PostgreSQL `ltree` 列，例如 `agent.1.store.2`，用 `<@` 匹配。
+    Ltree,
+}
+
+/// 编译后的 SQL 条件及其按顺序绑定的参数。
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SqlPredicate {
+    /// 可直接拼在 `WHERE` 或 `AND` 之后的条件片段。
+    pub sql: String,
+    /// 与占位符一一对应的参数。
+    pub params: Vec<String>,
+}
+
+/// `AccessScope` 到 SQL 条件的编译器。
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SqlFilter {
+    column: String,
+    dialect: SqlDialect,
+    encoding: PathEncoding,
+    first_param: usize,
+}
+
+impl SqlFilter {
+    /// 创建针对物化路径列的编译器。
+    pub fn materialized_path(dialect: SqlDialect, column: impl Into<String>) -> Self {
+        Self {
+            column: column.into(),
+            dialect,
+            encoding: PathEncoding::MaterializedPath,
+            first_param: 1,
+        }
+    }
+
+    /// 创建针对 PostgreSQL `ltree` 列的编译器。
+    pub fn postgres_ltree(column: impl Into<String>) -> Self {
+        Self {
+            column: column.into(),
+            dialect: SqlDialect::Postgres,
+            encoding: PathEncoding::Ltree,
+            first_param: 1,
+        }
+    }
+
+    /// 设置第一个 PostgreSQL 占位符的序号，便于拼接到已有参数之后。
+    pub fn first_param(mut self, index: usize) -> Self {
+        self.first_param = index.max(1);
+        self
+    }
+
+    /// 编译访问范围。
+    ///
+    /// `AccessScope::None` 返回恒假条件；`AccessScope::Tenant` 不需要额外过滤，返回 `None`。
+    /// 条件只约束范围路径，租户隔离仍需调用方自行加上 `tenant_id` 条件。
+    /// 路径模式在 PostgreSQL 上编译为正则或 `lquery`，在 MySQL 上编译为 `REGEXP`，
+    /// SQLite 没有内置正则，遇到模式时返回错误。
+    ///
+    /// 生成的比较都区分大小写：MySQL 显式使用 `utf8mb4_bin` 排序规则，要求路径列为
+    /// `utf8mb4` 字符集；SQLite 用 `GLOB` 代替不区分 ASCII 大小写的 `LIKE`。
+    pub fn render(&self, scope: &AccessScope) -> Result<Option<SqlPredicate>> {
+        let mut builder = Builder {
+            filter: self,
+            params: Vec::new(),
+        };
+        let sql = match scope {
+            AccessScope::None => "1 = 0".to_string(),
+            AccessScope::Tenant { .. } => return Ok(None),
+            AccessScope::Paths {
+                roots, patterns, ..
+            } => builder.any_of(roots, patterns)?,
+            AccessScope::Restricted(scope) if scope.is_tenant_wide() => format!(
+                "NOT {}",
+                builder.any_of(scope.excluded(), scope.excluded_patterns())?
+            ),
+            AccessScope::Restricted(scope) => {
+                let allowed = builder.any_of(scope.roots(), scope.patterns())?;
+                format!(
+                    "({allowed} AND NOT {})",
+                    builder.any_of(scope.excluded(), scope.excluded_patterns())?
+                )
+            }
+        };
+        Ok(Some(SqlPredicate {
+            sql,
+            params: builder.params,
+        }))
+    }
+}
+
+/// 单次编译过程中的参数累积器。
+struct Builder<'a> {
+    filter: &'a SqlFilter,
+    params: Vec<String>,
+}
+
+impl Builder<'_> {
+    /// 生成“位于任一根路径子树内或匹配任一模式”的条件，结果自带括号。
+    fn any_of(&mut self, roots: &[ScopePath], patterns: &[ScopePattern]) -> Result<String> {
+        let mut clauses = roots
+            .iter()
+            .map(|root| self.subtree(root))
+            .collect::<Result<Vec<_>>>()?;
+        for pattern in patterns {
+            clauses.push(self.pattern(pattern)?);
+        }
+        Ok(match clauses.as_slice() {
+            [single] => single.clone(),
+            _ => format!("({})", clauses.join(" OR ")),
+        })
+    }
+
+    /// 生成“等于根路径或位于其下”的条件。
+    fn subtree(&mut self, root: &ScopePath) -> Result<String> {
+        let column = self.column();
+        match (self.filter.encoding, self.filter.dialect) {
+            (PathEncoding::MaterializedPath, SqlDialect::Sqlite) => {
+                let exact = self.bind(escape_glob(root.as_str()));
+                // 追加 `/` 再匹配，`agent/1` 不会命中 `agent/10`。
+                let prefix = self.bind(format!("{}/*", escape_glob(root.as_str())));
+                Ok(format!("({column} GLOB {exact} OR {column} GLOB {prefix})"))
+            }
+            (PathEncoding::MaterializedPath, _) => {
+                let exact = self.bind(root.as_str().to_string());
+                let prefix = self.bind(format!("{}/%", escape_like(root.as_str())));
+                Ok(format!(
+                    "({column} = {exact} OR {column} LIKE {prefix} ESCAPE '{LIKE_ESCAPE}')"
+                ))
+            }
+            (PathEncoding::Ltree, _) => {
+                let label = self.bind(ltree_path(root)?);
+                Ok(format!("{column} <@ {label}::ltree"))
+            }
+        }
+    }
+
+    /// 生成匹配路径模式的条件。
+    fn pattern(&mut self, pattern: &ScopePattern) -> Result<String> {
+        let column = self.column();
+        match (self.filter.encoding, self.filter.dialect) {
+            (PathEncoding::Ltree, _) => {
+                let query = self.bind(lquery(pattern)?);
+                Ok(format!("{column} ~ {query}::lquery"))
+            }
+            (PathEncoding::MaterializedPath, SqlDialect::Postgres) => {
+                let regex = self.bind(path_regex(pattern));
+                Ok(format!("{column} ~ {regex}"))
+            }
+            (PathEncoding::MaterializedPath, SqlDialect::MySql) => {
+                let regex = self.bind(path_regex(pattern));
+                Ok(format!("{column} REGEXP {regex}"))
+            }
+            (PathEncoding::MaterializedPath, SqlDialect::Sqlite) => Err(Error::InvalidScope(
+                format!("scope pattern `{pattern}` cannot be rendered for sqlite"),
+            )),
+        }
+    }
+
+    /// 返回用于比较的列表达式，MySQL 上附加二进制排序规则。
+    fn column(&self) -> String {
+        let column = &self.filter.column;
+        match self.filter.dialect {
+            SqlDialect::MySql => format!("{column} COLLATE {MYSQL_BINARY_COLLATION}"),
+            SqlDialect::Postgres | SqlDialect::Sqlite => column.clone(),
+        }
+    }
+
+    /// 记录参数并返回对应占位符。
+    fn bind(&mut self, value: String) -> String {
+        self.params.push(value);
+        match self.filter.dialect {
+            SqlDialect::Postgres => {
+                format!("${}", self.filter.first_param + self.params.len() - 1)
+            }
+            SqlDialect::MySql | SqlDialect::Sqlite => "?".to_string(),
+        }
+    }
+}
+
+/// 转义 `LIKE` 通配符；`ScopePath` 允许 `_`，不转义会匹配任意字符。
+fn escape_like(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for ch in value.chars() {
+        if matches!(ch, '%' | '_' | LIKE_ESCAPE) {
+            escaped.push(LIKE_ESCAPE);
+        }
+        escaped.push(ch);
+    }
+    escaped
+}
+
+/// 转义 `GLOB` 元字符，把 `*`、`?`、`[` 包进字符类按字面匹配。
+fn escape_glob(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for ch in value.chars() {
+        if matches!(ch, '*' | '?' | '[') {
+            escaped.push('[');
+            escaped.push(ch);
+            escaped.push(']');
+        } else {
+            escaped.push(ch);
+        }
+    }
+    escaped
+}
+
+/// 返回路径段中 `ltree` 标签不接受的字符；PostgreSQL 16 之前的标签不允许 `-`。
+fn invalid_ltree_char(value: &str) -> Option<char> {
+    value.chars().find(|ch| matches!(ch, '.' | '-'))
+}
+
+/// 把范围路径转换为 `ltree` 标签路径。
+fn ltree_path(path: &ScopePath) -> Result<String> {
+    if let Some(ch) = invalid_ltree_char(path.as_str()) {
+        return Err(Error::InvalidScope(format!(
+            "scope path `{path}` cannot be encoded as ltree: segment contains `{ch}`"
+        )));
+    }
+    Ok(path.as_str().replace('/', "."))
+}
+
+/// 把路径模式转换为锚定的正则表达式。
+fn path_regex(pattern: &ScopePattern) -> String {
+    let mut regex = String::from("^");
+    for (index, segment) in pattern.as_str().split('/').enumerate() {
+        match segment {
+            "**" => {
+                regex.push_str("(/.*)?");
+                continue;
+            }
+            _ if index > 0 => regex.push('/'),
+            _ => {}
+        }
+        if segment == "*" {
+            regex.push_str("[^/]+");
+        } else {
+            // 合法路径段只含字母、数字、`_`、`-`、`.`，其中只有 `.` 是正则元字符。
+            regex.push_str(&segment.replace('.', "\\."));
+        }
+    }
+    regex.push('$');
+    regex
+}
+
+/// 把路径模式转换为 `lquery`。
+fn lquery(pattern: &ScopePattern) -> Result<String> {
+    let labels = pattern
+        .as_str()
+        .split('/')
+        .map(|segment| match segment {
+            "*" => Ok("*{1}"),
+            "**" => Ok("*"),
+            _ => match invalid_ltree_char(segment) {
+                Some(ch) => Err(Error::InvalidScope(format!(
+                    "scope pattern `{pattern}` cannot be encoded as lquery: segment contains `{ch}`"
+                ))),
+                None => Ok(segment),
+            },
+        })
+        .collect::<Result<Vec<_>>>()?;
+    Ok(labels.join("."))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{SqlDialect, SqlFilter, SqlPredicate};
+    use crate::{AccessScope, GrantScope, ScopePath, ScopePattern, TenantId};
+
+    /// 构造测试路径列表。
+    fn paths(values: &[&str]) -> Vec<ScopePath> {
+        values
+            .iter()
+            .map(|value| ScopePath::parse(value).expect("scope path"))
+            .collect()
+    }
+
+    /// 构造测试租户。
+    fn tenant() -> TenantId {
+        TenantId::parse("tenant_1").expect("tenant")
+    }
+
+    /// 构造路径级授权范围。
+    fn grant(values: &[&str]) -> GrantScope {
+        GrantScope::paths(paths(values)).expect("grant scope")
+    }
+
+    #[test]
+    fn render_should_map_none_and_tenant_scopes() {
+        let filter = SqlFilter::materialized_path(SqlDialect::Postgres, "scope_path");
+
+        let none = filter.render(&AccessScope::None).expect("render");
+        let tenant = filter
+            .render(&AccessScope::Tenant { tenant: tenant() })
+            .expect("render");
+
+        assert_eq!(
+            none,
+            Some(SqlPredicate {
+                sql: "1 = 0".to_string(),
+                params: Vec::new(),
+            })
+        );
+        assert_eq!(tenant, None);
+    }
+
+    #[test]
+    fn render_should_escape_like_prefix_and_number_postgres_params() {
+        let filter =
+            SqlFilter::materialized_path(SqlDialect::Postgres, "o.scope_path").first_param(3);
+        let scope = AccessScope::merge(tenant(), [grant(&["agent/1", "team_a"])])
+            .subtract([grant(&["agent/1/store/7"])]);
+
+        let predicate = filter.render(&scope).expect("render").expect("predicate");
+
+        assert_eq!(
+            predicate.sql,
+            "(((o.scope_path = $3 OR o.scope_path LIKE $4 ESCAPE '!') \
+             OR (o.scope_path = $5 OR o.scope_path LIKE $6 ESCAPE '!')) \
+             AND NOT (o.scope_path = $7 OR o.scope_path LIKE $8 ESCAPE '!'))"
+        );
+        assert_eq!(
+            predicate.params,
+            vec![
+                "agent/1",
+                "agent/1/%",
+                "team_a",
+                "team!_a/%",
+                "agent/1/store/7",
+                "agent/1/store/7/%",
+            ]
+        );
+    }
+
+    #[test]
+    fn render_should_compare_case_sensitively_on_mysql() {
+        let scope = AccessScope::Tenant { tenant: tenant() }.subtract([grant(&["agent/2"])]);
+
+        let predicate = SqlFilter::materialized_path(SqlDialect::MySql, "scope_path")
+            .render(&scope)
+            .expect("render")
+            .expect("predicate");
+
+        assert_eq!(
+            predicate.sql,
+            "NOT (scope_path COLLATE utf8mb4_bin = ? \
+             OR scope_path COLLATE utf8mb4_bin LIKE ? ESCAPE '!')"
+        );
+        assert_eq!(predicate.params, vec!["agent/2", "agent/2/%"]);
+    }
+
+    #[test]
+    fn render_should_compare_case_sensitively_on_sqlite() {
+        let scope = AccessScope::Tenant { tenant: tenant() }.subtract([grant(&["agent/2"])]);
+
+        let predicate = SqlFilter::materialized_path(SqlDialect::Sqlite, "scope_path")
+            .render(&scope)
+            .expect("render")
+            .expect("predicate");
+
+        assert_eq!(
+            predicate.sql,
+            "NOT (scope_path GLOB ? OR scope_path GLOB ?)"
+        );
+        assert_eq!(predicate.params, vec!["agent/2", "agent/2/*"]);
+    }
+
+    #[test]
+    fn render_should_encode_ltree_paths() {
+        let filter = SqlFilter::postgres_ltree("scope_tree");
+        let scope = AccessScope::Paths {
+            tenant: tenant(),
+            roots: paths(&["agent/1", "agent/2"]),
+            patterns: Vec::new(),
+        };
+
+        let predicate = filter.render(&scope).expect("render").expect("predicate");
+        let err = filter
+            .render(&AccessScope::Paths {
+                tenant: tenant(),
+                roots: paths(&["region/v1.2"]),
+                patterns: Vec::new(),
+            })
+            .expect_err("must reject");
+        let dash_err = filter
+            .render(&AccessScope::Paths {
+                tenant: tenant(),
+                roots: paths(&["region/cn-east"]),
+                patterns: Vec::new(),
+            })
+            .expect_err("must reject");
+
+        assert_eq!(
+            predicate.sql,
+            "(scope_tree <@ $1::ltree OR scope_tree <@ $2::ltree)"
+        );
+        assert_eq!(predicate.params, vec!["agent.1", "agent.2"]);
+        assert!(err.to_string().contains("ltree"));
+        assert!(dash_err.to_string().contains("`-`"));
+    }
+
+    #[test]
+    fn render_should_compile_patterns_per_dialect() {
+        let pattern = |value: &str| ScopePattern::parse(value).expect("scope pattern");
+        let with_patterns = |roots: &[&str], patterns: &[&str]| {
+            GrantScope::paths_with_patterns(
+                paths(roots),
+                patterns.iter().map(|value| pattern(value)).collect(),
+            )
+            .expect("grant scope")
+        };
+        let scope =
+            AccessScope::merge(tenant(), [with_patterns(&[], &["region/*/v1.0"])]).subtract([
+                with_patterns(&["region/eu/v1.0/public"], &["region/*/v1.0/secret"]),
+            ]);
+        let ltree_scope = AccessScope::merge(tenant(), [with_patterns(&[], &["agent/*/store"])])
+            .subtract([with_patterns(&[], &["agent/*/store/9"])]);
+
+        let postgres = SqlFilter::materialized_path(SqlDialect::Postgres, "scope_path")
+            .render(&scope)
+            .expect("render")
+            .expect("predicate");
+        let mysql = SqlFilter::materialized_path(SqlDialect::MySql, "scope_path")
+            .render(&scope)
+            .expect("render")
+            .expect("predicate");
+        let ltree = SqlFilter::postgres_ltree("scope_tree")
+            .render(&ltree_scope)
+            .expect("render")
+            .expect("predicate");
+        let sqlite = SqlFilter::materialized_path(SqlDialect::Sqlite, "scope_path").render(&scope);
+
+        assert_eq!(
+            postgres.sql,
+            "(scope_path ~ $1 AND NOT ((scope_path = $2 OR scope_path LIKE $3 ESCAPE '!') \
+             OR scope_path ~ $4))"
+        );
+        assert_eq!(
+            postgres.params,
+            vec![
+                r"^region/[^/]+/v1\.0(/.*)?$",
+                "region/eu/v1.0/public",
+                "region/eu/v1.0/public/%",
+                r"^region/[^/]+/v1\.0/secret(/.*)?$",
+            ]
+        );
+        assert_eq!(
+            mysql.sql,
+            "(scope_path COLLATE utf8mb4_bin REGEXP ? \
+             AND NOT ((scope_path COLLATE utf8mb4_bin = ? \
+             OR scope_path COLLATE utf8mb4_bin LIKE ? ESCAPE '!') \
+             OR scope_path COLLATE utf8mb4_bin REGEXP ?))"
+        );
+        assert_eq!(mysql.params, postgres.params);
+        assert_eq!(
+            ltree.sql,
+            "(scope_tree ~ $1::lquery AND NOT scope_tree ~ $2::lquery)"
+        );
+        assert_eq!(
+            ltree.params,
+            vec!["agent.*{1}.store.*", "agent.*{1}.store.9.*"]
+        );
+        assert!(sqlite.is_err());
+    }
+}
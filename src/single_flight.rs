@@ -0,0 +1,143 @@
+use crate::sync::lock;
+use std::collections::HashMap;
+use std::future::Future;
+use std::hash::Hash;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll, Waker};
+
+/// 按键合并并发计算，同一时刻每个键只有一个计算在执行。
+///
+/// 不依赖具体异步运行时：等待者通过 [`Waker`] 被唤醒。
+#[derive(Debug)]
+pub(crate) struct SingleFlight<K, V> {
+    flights: Mutex<HashMap<K, Arc<Flight<V>>>>,
+}
+
+/// 一次进行中的计算。
+#[derive(Debug)]
+struct Flight<V> {
+    state: Mutex<FlightState<V>>,
+}
+
+/// 进行中计算的状态。
+#[derive(Debug)]
+enum FlightState<V> {
+    /// 计算仍在执行，保存等待者的唤醒器。
+    Running(Vec<Waker>),
+    /// 计算结束；`None` 表示领导者失败或被取消，等待者需要重新竞争。
+    Finished(Option<V>),
+}
+
+impl<K, V> SingleFlight<K, V>
+where
+    K: Clone + Eq + Hash,
+    V: Clone,
+{
+    /// 创建空的合并器。
+    pub(crate) fn new() -> Self {
+        Self {
+            flights: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// 执行或加入 `key` 对应的计算。
+    ///
+    /// 领导者返回自己的结果（包括错误）；等待者只共享成功结果，
+    /// 领导者失败或其 future 被丢弃时，等待者重新竞争领导者。
+    pub(crate) async fn run<E, F, Fut>(&self, key: K, compute: F) -> Result<V, E>
+    where
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = Result<V, E>>,
+    {
+        let flight = loop {
+            let (flight, leader) = {
+                let mut flights = lock(&self.flights);
+                match flights.get(&key) {
+                    Some(flight) => (Arc::clone(flight), false),
+                    None => {
+                        let flight = Arc::new(Flight {
+                            state: Mutex::new(FlightState::Running(Vec::new())),
+                        });
+                        flights.insert(key.clone(), Arc::clone(&flight));
+                        (flight, true)
+                    }
+                }
+            };
+            if leader {
+                break flight;
+            }
+            if let Some(value) = (Wait { flight: &flight }).await {
+                return Ok(value);
+            }
+        };
+
+        let mut guard = LeaderGuard {
+            flights: &self.flights,
+            key: &key,
+            flight,
+            value: None,
+        };
+        let result = compute().await;
+        guard.value = result.as_ref().ok().cloned();
+        drop(guard);
+        result
+    }
+}
+
+/// 领导者持有的守卫，结束或被丢弃时发布结果并唤醒等待者。
+struct LeaderGuard<'a, K, V>
+where
+    K: Eq + Hash,
+{
+    flights: &'a Mutex<HashMap<K, Arc<Flight<V>>>>,
+    key: &'a K,
+    flight: Arc<Flight<V>>,
+    value: Option<V>,
+}
+
+impl<K, V> Drop for LeaderGuard<'_, K, V>
+where
+    K: Eq + Hash,
+{
+    fn drop(&mut self) {
+        {
+            let mut flights = lock(self.flights);
+            if flights
+                .get(self.key)
+                .is_some_and(|flight| Arc::ptr_eq(flight, &self.flight))
+            {
+                flights.remove(self.key);
+            }
+        }
+        let finished = FlightState::Finished(self.value.take());
+        let previous = std::mem::replace(&mut *lock(&self.flight.state), finished);
+        if let FlightState::Running(wakers) = previous {
+            for waker in wakers {
+                waker.wake();
+            }
+        }
+    }
+}
+
+/// 等待领导者发布结果的 future。
+struct Wait<'a, V> {
+    flight: &'a Flight<V>,
+}
+
+impl<V: Clone> Future for Wait<'_, V> {
+    type Output = Option<V>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let mut state = lock(&self.flight.state);
+        match &mut *state {
+            FlightState::Finished(value) => Poll::Ready(value.clone()),
+            FlightState::Running(wakers) => {
+                if !wakers.iter().any(|waker| waker.will_wake(cx.waker())) {
+                    wakers.push(cx.waker().clone());
+                }
+                Poll::Pending
+            }
+        }
+    }
+}
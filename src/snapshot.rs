@@ -0,0 +1,93 @@
+use crate::ids::RoleId;
+use crate::permission::Permission;
+use crate::request::AuthSubject;
+use crate::scope::AccessScope;
+
+/// 主体被授予的单个权限及其合并后的访问范围。
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GrantedPermission {
+    /// 被授予的权限。
+    pub permission: Permission,
+    /// 合并允许授权并扣除显式拒绝后的访问范围。
+    pub scope: AccessScope,
+    /// 贡献了允许授权的角色，已去重并排序。
+    pub roles: Vec<RoleId>,
+    /// 是否有贡献授权带有权限条件；为 `true` 时实际访问还取决于请求上下文。
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub conditional: bool,
+}
+
+/// 主体在租户内拥有的全部权限快照。
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SubjectPermissions {
+    /// 快照所属主体。
+    pub subject: AuthSubject,
+    /// 按权限排序的已授予权限。
+    pub permissions: Vec<GrantedPermission>,
+}
+
+impl SubjectPermissions {
+    /// 按权限字面值精确查找授予记录。
+    ///
+    /// 不做通配符匹配：只授予 `invoice:*` 时查找 `invoice:read` 返回 `None`。
+    /// 判断主体能否执行某个操作应使用引擎的判定 API。
+    pub fn get_exact(&self, permission: &Permission) -> Option<&GrantedPermission> {
+        self.permissions
+            .binary_search_by(|granted| granted.permission.cmp(permission))
+            .ok()
+            .map(|index| &self.permissions[index])
+    }
+
+    /// 按权限字面值判断快照中是否包含某个权限，规则同 [`SubjectPermissions::get_exact`]。
+    pub fn contains_exact(&self, permission: &Permission) -> bool {
+        self.get_exact(permission).is_some()
+    }
+}
+
+#[cfg(all(test, feature = "serde"))]
+mod tests {
+    use super::*;
+    use crate::ids::{PrincipalId, TenantId};
+    use crate::scope::{GrantScope, ScopePath};
+
+    #[test]
+    fn subject_permissions_should_roundtrip_through_serde() {
+        let tenant = TenantId::parse("tenant_1").expect("tenant");
+        let snapshot = SubjectPermissions {
+            subject: AuthSubject::new(
+                tenant.clone(),
+                PrincipalId::parse("user_1").expect("principal"),
+            ),
+            permissions: vec![GrantedPermission {
+                permission: Permission::parse("invoice:read").expect("permission"),
+                scope: AccessScope::merge(
+                    tenant,
+                    [
+                        GrantScope::paths(vec![ScopePath::parse("agent/1").expect("scope path")])
+                            .expect("grant scope"),
+                    ],
+                )
+                .subtract([GrantScope::paths(vec![
+                    ScopePath::parse("agent/1/store/9").expect("scope path"),
+                ])
+                .expect("grant scope")]),
+                roles: vec![RoleId::parse("reader").expect("role")],
+                conditional: false,
+            }],
+        };
+
+        let json = serde_json::to_value(&snapshot).expect("serialize");
+        assert_eq!(json["permissions"][0]["permission"], "invoice:read");
+        assert_eq!(json["permissions"][0]["scope"]["type"], "restricted");
+        assert_eq!(json["permissions"][0]["scope"]["roots"][0], "agent/1");
+        assert_eq!(
+            json["permissions"][0]["scope"]["excluded"][0],
+            "agent/1/store/9"
+        );
+
+        let decoded: SubjectPermissions = serde_json::from_value(json).expect("deserialize");
+        assert_eq!(decoded, snapshot);
+    }
+}
@@ -1,3 +1,4 @@
+use crate::condition::PermissionCondition;
 use crate::error::SourceError;
 use crate::ids::{RoleId, TenantId};
 use crate::permission::Permission;
@@ -59,4 +60,16 @@ pub trait AuthorizationSource: Send + Sync {
         tenant: &TenantId,
         role: &RoleId,
     ) -> std::result::Result<Vec<RoleId>, SourceError>;
+
+    /// 返回附加在租户角色权限上的条件。
+    ///
+    /// 默认没有条件。未启用 [`crate::EngineConfig::enable_conditions`] 时引擎仍会读取条件，
+    /// 用于跳过有条件的允许授权，避免条件被静默忽略。
+    async fn role_permission_conditions(
+        &self,
+        _tenant: &TenantId,
+        _role: &RoleId,
+    ) -> std::result::Result<Vec<PermissionCondition>, SourceError> {
+        Ok(Vec::new())
+    }
 }
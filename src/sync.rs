@@ -0,0 +1,9 @@
+use std::sync::{Mutex, MutexGuard};
+
+/// 获取互斥锁，并在锁中毒时恢复内部值。
+pub(crate) fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    match mutex.lock() {
+        Ok(guard) => guard,
+        Err(poisoned) => poisoned.into_inner(),
+    }
+}
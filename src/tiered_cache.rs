@@ -0,0 +1,531 @@
+use crate::cache::{Cache, CacheEntryMeta, EffectiveGrant, StatusEntryMeta};
+use crate::ids::{PrincipalId, RoleId, TenantId};
+use crate::source::{MembershipStatus, TenantStatus};
+use async_trait::async_trait;
+
+/// 两级缓存组合，例如本地 [`MemoryCache`](crate::MemoryCache) 叠加共享的远程缓存。
+///
+/// 读取先查 L1，未命中再查 L2 并回填 L1；写入和失效同时作用于两级。
+/// 回填通过 [`Cache::get_effective_grants_with_meta`] 携带 L2 条目的剩余存活时间和依赖角色，
+/// 租户状态和成员状态通过对应的 `*_with_meta` 方法携带剩余存活时间；
+/// L2 不提供元数据时不回填，只返回 L2 的结果。
+#[derive(Debug, Clone, Default)]
+pub struct TieredCache<L1, L2> {
+    l1: L1,
+    l2: L2,
+}
+
+impl<L1, L2> TieredCache<L1, L2> {
+    /// 使用本地 L1 和共享 L2 创建两级缓存。
+    pub fn new(l1: L1, l2: L2) -> Self {
+        Self { l1, l2 }
+    }
+
+    /// 返回 L1 缓存。
+    pub fn l1(&self) -> &L1 {
+        &self.l1
+    }
+
+    /// 返回 L2 缓存。
+    pub fn l2(&self) -> &L2 {
+        &self.l2
+    }
+}
+
+impl<L1: Cache, L2: Cache> TieredCache<L1, L2> {
+    /// 读取 L2 及其元数据，带元数据时按剩余存活时间和依赖角色回填 L1。
+    async fn read_l2(
+        &self,
+        tenant: &TenantId,
+        principal: &PrincipalId,
+        config_signature: &str,
+    ) -> Option<(Vec<EffectiveGrant>, Option<CacheEntryMeta>)> {
+        let (grants, meta) = self
+            .l2
+            .get_effective_grants_with_meta(tenant, principal, config_signature)
+            .await?;
+        if let Some(meta) = &meta {
+            self.l1
+                .set_effective_grants_with_meta(
+                    tenant,
+                    principal,
+                    config_signature,
+                    grants.clone(),
+                    meta.clone(),
+                )
+                .await;
+        }
+        Some((grants, meta))
+    }
+
+    /// 读取 L2 租户状态及其元数据，带元数据时按剩余存活时间回填 L1。
+    async fn read_l2_tenant_status(
+        &self,
+        tenant: &TenantId,
+        policy_version: &str,
+    ) -> Option<(TenantStatus, Option<StatusEntryMeta>)> {
+        let (status, meta) = self
+            .l2
+            .get_tenant_status_with_meta(tenant, policy_version)
+            .await?;
+        if let Some(meta) = meta {
+            self.l1
+                .set_tenant_status_with_meta(tenant, policy_version, status, meta)
+                .await;
+        }
+        Some((status, meta))
+    }
+
+    /// 读取 L2 成员状态及其元数据，带元数据时按剩余存活时间回填 L1。
+    async fn read_l2_membership_status(
+        &self,
+        tenant: &TenantId,
+        principal: &PrincipalId,
+        policy_version: &str,
+    ) -> Option<(MembershipStatus, Option<StatusEntryMeta>)> {
+        let (status, meta) = self
+            .l2
+            .get_membership_status_with_meta(tenant, principal, policy_version)
+            .await?;
+        if let Some(meta) = meta {
+            self.l1
+                .set_membership_status_with_meta(tenant, principal, policy_version, status, meta)
+                .await;
+        }
+        Some((status, meta))
+    }
+}
+
+#[async_trait]
+impl<L1: Cache, L2: Cache> Cache for TieredCache<L1, L2> {
+    /// 依次读取 L1 和 L2，L2 命中且带元数据时回填 L1。
+    async fn get_effective_grants(
+        &self,
+        tenant: &TenantId,
+        principal: &PrincipalId,
+        config_signature: &str,
+    ) -> Option<Vec<EffectiveGrant>> {
+        if let Some(grants) = self
+            .l1
+            .get_effective_grants(tenant, principal, config_signature)
+            .await
+        {
+            return Some(grants);
+        }
+        self.read_l2(tenant, principal, config_signature)
+            .await
+            .map(|(grants, _)| grants)
+    }
+
+    /// 依次读取 L1 和 L2；只有 L1 提供刷新提示，L2 命中的条目刚回填到 L1，不要求刷新。
+    async fn get_effective_grants_with_refresh(
+        &self,
+        tenant: &TenantId,
+        principal: &PrincipalId,
+        config_signature: &str,
+    ) -> Option<(Vec<EffectiveGrant>, bool)> {
+        if let Some(hit) = self
+            .l1
+            .get_effective_grants_with_refresh(tenant, principal, config_signature)
+            .await
+        {
+            return Some(hit);
+        }
+        self.read_l2(tenant, principal, config_signature)
+            .await
+            .map(|(grants, _)| (grants, false))
+    }
+
+    /// 依次读取 L1 和 L2 及其元数据，L2 命中且带元数据时回填 L1。
+    async fn get_effective_grants_with_meta(
+        &self,
+        tenant: &TenantId,
+        principal: &PrincipalId,
+        config_signature: &str,
+    ) -> Option<(Vec<EffectiveGrant>, Option<CacheEntryMeta>)> {
+        if let Some(hit) = self
+            .l1
+            .get_effective_grants_with_meta(tenant, principal, config_signature)
+            .await
+        {
+            return Some(hit);
+        }
+        self.read_l2(tenant, principal, config_signature).await
+    }
+
+    /// 同时写入两级缓存。
+    async fn set_effective_grants(
+        &self,
+        tenant: &TenantId,
+        principal: &PrincipalId,
+        config_signature: &str,
+        grants: Vec<EffectiveGrant>,
+    ) {
+        self.l2
+            .set_effective_grants(tenant, principal, config_signature, grants.clone())
+            .await;
+        self.l1
+            .set_effective_grants(tenant, principal, config_signature, grants)
+            .await;
+    }
+
+    /// 同时写入两级缓存，并把元数据传给两级。
+    async fn set_effective_grants_with_meta(
+        &self,
+        tenant: &TenantId,
+        principal: &PrincipalId,
+        config_signature: &str,
+        grants: Vec<EffectiveGrant>,
+        meta: CacheEntryMeta,
+    ) {
+        self.l2
+            .set_effective_grants_with_meta(
+                tenant,
+                principal,
+                config_signature,
+                grants.clone(),
+                meta.clone(),
+            )
+            .await;
+        self.l1
+            .set_effective_grants_with_meta(tenant, principal, config_signature, grants, meta)
+            .await;
+    }
+
+    /// 依次读取两级租户状态，L2 命中且带元数据时回填 L1。
+    async fn get_tenant_status(
+        &self,
+        tenant: &TenantId,
+        policy_version: &str,
+    ) -> Option<TenantStatus> {
+        if let Some(status) = self.l1.get_tenant_status(tenant, policy_version).await {
+            return Some(status);
+        }
+        self.read_l2_tenant_status(tenant, policy_version)
+            .await
+            .map(|(status, _)| status)
+    }
+
+    /// 依次读取两级租户状态及其元数据，L2 命中且带元数据时回填 L1。
+    async fn get_tenant_status_with_meta(
+        &self,
+        tenant: &TenantId,
+        policy_version: &str,
+    ) -> Option<(TenantStatus, Option<StatusEntryMeta>)> {
+        if let Some(hit) = self
+            .l1
+            .get_tenant_status_with_meta(tenant, policy_version)
+            .await
+        {
+            return Some(hit);
+        }
+        self.read_l2_tenant_status(tenant, policy_version).await
+    }
+
+    /// 同时写入两级租户状态。
+    async fn set_tenant_status(
+        &self,
+        tenant: &TenantId,
+        policy_version: &str,
+        status: TenantStatus,
+    ) {
+        self.l2
+            .set_tenant_status(tenant, policy_version, status)
+            .await;
+        self.l1
+            .set_tenant_status(tenant, policy_version, status)
+            .await;
+    }
+
+    /// 同时写入两级租户状态，并把元数据传给两级。
+    async fn set_tenant_status_with_meta(
+        &self,
+        tenant: &TenantId,
+        policy_version: &str,
+        status: TenantStatus,
+        meta: StatusEntryMeta,
+    ) {
+        self.l2
+            .set_tenant_status_with_meta(tenant, policy_version, status, meta)
+            .await;
+        self.l1
+            .set_tenant_status_with_meta(tenant, policy_version, status, meta)
+            .await;
+    }
+
+    /// 依次读取两级成员状态，L2 命中且带元数据时回填 L1。
+    async fn get_membership_status(
+        &self,
+        tenant: &TenantId,
+        principal: &PrincipalId,
+        policy_version: &str,
+    ) -> Option<MembershipStatus> {
+        if let Some(status) = self
+            .l1
+            .get_membership_status(tenant, principal, policy_version)
+            .await
+        {
+            return Some(status);
+        }
+        self.read_l2_membership_status(tenant, principal, policy_version)
+            .await
+            .map(|(status, _)| status)
+    }
+
+    /// 依次读取两级成员状态及其元数据，L2 命中且带元数据时回填 L1。
+    async fn get_membership_status_with_meta(
+        &self,
+        tenant: &TenantId,
+        principal: &PrincipalId,
+        policy_version: &str,
+    ) -> Option<(MembershipStatus, Option<StatusEntryMeta>)> {
+        if let Some(hit) = self
+            .l1
+            .get_membership_status_with_meta(tenant, principal, policy_version)
+            .await
+        {
+            return Some(hit);
+        }
+        self.read_l2_membership_status(tenant, principal, policy_version)
+            .await
+    }
+
+    /// 同时写入两级成员状态。
+    async fn set_membership_status(
+        &self,
+        tenant: &TenantId,
+        principal: &PrincipalId,
+        policy_version: &str,
+        status: MembershipStatus,
+    ) {
+        self.l2
+            .set_membership_status(tenant, principal, policy_version, status)
+            .await;
+        self.l1
+            .set_membership_status(tenant, principal, policy_version, status)
+            .await;
+    }
+
+    /// 同时写入两级成员状态，并把元数据传给两级。
+    async fn set_membership_status_with_meta(
+        &self,
+        tenant: &TenantId,
+        principal: &PrincipalId,
+        policy_version: &str,
+        status: MembershipStatus,
+        meta: StatusEntryMeta,
+    ) {
+        self.l2
+            .set_membership_status_with_meta(tenant, principal, policy_version, status, meta)
+            .await;
+        self.l1
+            .set_membership_status_with_meta(tenant, principal, policy_version, status, meta)
+            .await;
+    }
+
+    /// 先失效 L2 再失效 L1，避免 L1 从 L2 回填旧状态。
+    async fn invalidate_tenant_status(&self, tenant: &TenantId) {
+        self.l2.invalidate_tenant_status(tenant).await;
+        self.l1.invalidate_tenant_status(tenant).await;
+    }
+
+    /// 先失效 L2 再失效 L1。
+    async fn invalidate_membership(&self, tenant: &TenantId, principal: &PrincipalId) {
+        self.l2.invalidate_membership(tenant, principal).await;
+        self.l1.invalidate_membership(tenant, principal).await;
+    }
+
+    /// 先失效 L2 再失效 L1。
+    async fn invalidate_principal(&self, tenant: &TenantId, principal: &PrincipalId) {
+        self.l2.invalidate_principal(tenant, principal).await;
+        self.l1.invalidate_principal(tenant, principal).await;
+    }
+
+    /// 先失效 L2 再失效 L1。
+    async fn invalidate_role(&self, tenant: &TenantId, role: &RoleId) {
+        self.l2.invalidate_role(tenant, role).await;
+        self.l1.invalidate_role(tenant, role).await;
+    }
+
+    /// 先失效 L2 再失效 L1。
+    async fn invalidate_tenant(&self, tenant: &TenantId) {
+        self.l2.invalidate_tenant(tenant).await;
+        self.l1.invalidate_tenant(tenant).await;
+    }
+
+    /// 先失效 L2 再失效 L1。
+    async fn invalidate_all(&self) {
+        self.l2.invalidate_all().await;
+        self.l1.invalidate_all().await;
+    }
+}
+
+#[cfg(all(test, feature = "memory-cache"))]
+mod tests {
+    use super::TieredCache;
+    use crate::cache::{Cache, CacheEntryMeta, EffectiveGrant};
+    use crate::{
+        GrantScope, MembershipStatus, MemoryCache, Permission, PrincipalId, RoleId, TenantId,
+        TenantStatus,
+    };
+    use futures::executor::block_on;
+    use std::time::Duration;
+
+    /// 构造测试标识符。
+    fn ids() -> (TenantId, PrincipalId, RoleId) {
+        (
+            TenantId::parse("tenant_1").expect("tenant"),
+            PrincipalId::parse("user_1").expect("principal"),
+            RoleId::parse("reader").expect("role"),
+        )
+    }
+
+    /// 构造租户级测试授权。
+    fn grants(role: RoleId) -> Vec<EffectiveGrant> {
+        vec![EffectiveGrant::new(
+            role,
+            Permission::parse("invoice:read").expect("permission"),
+            GrantScope::tenant(),
+        )]
+    }
+
+    #[test]
+    fn tiered_cache_should_backfill_l1_from_l2() {
+        let (tenant, principal, role) = ids();
+        let status_cache = || MemoryCache::new(8).with_status_ttl(Duration::from_secs(60));
+        let cache = TieredCache::new(status_cache(), status_cache());
+        block_on(
+            cache
+                .l2()
+                .set_effective_grants(&tenant, &principal, "a", grants(role.clone())),
+        );
+        block_on(
+            cache
+                .l2()
+                .set_tenant_status(&tenant, "", TenantStatus::Active),
+        );
+
+        assert_eq!(
+            block_on(cache.get_effective_grants(&tenant, &principal, "a")),
+            Some(grants(role.clone()))
+        );
+        assert_eq!(
+            block_on(cache.get_tenant_status(&tenant, "")),
+            Some(TenantStatus::Active)
+        );
+
+        // 清空 L2 后仍能从回填的 L1 读取。
+        block_on(cache.l2().invalidate_all());
+        assert_eq!(
+            block_on(cache.get_effective_grants(&tenant, &principal, "a")),
+            Some(grants(role))
+        );
+        assert_eq!(cache.l1().stats().total.hits, 1);
+        assert!(block_on(cache.get_effective_grants(&tenant, &principal, "b")).is_none());
+    }
+
+    #[test]
+    fn tiered_cache_should_write_through_and_invalidate_both_tiers() {
+        let (tenant, principal, role) = ids();
+        let cache = TieredCache::new(MemoryCache::new(8), MemoryCache::new(8));
+        let meta = CacheEntryMeta {
+            max_ttl: None,
+            roles: Some(vec![role.clone()]),
+        };
+        block_on(cache.set_effective_grants_with_meta(
+            &tenant,
+            &principal,
+            "a",
+            grants(role.clone()),
+            meta,
+        ));
+
+        for tier in [cache.l1(), cache.l2()] {
+            assert!(block_on(tier.get_effective_grants(&tenant, &principal, "a")).is_some());
+        }
+
+        block_on(cache.invalidate_role(&tenant, &role));
+
+        for tier in [cache.l1(), cache.l2()] {
+            assert!(block_on(tier.get_effective_grants(&tenant, &principal, "a")).is_none());
+        }
+    }
+
+    #[test]
+    fn tiered_cache_should_backfill_l1_with_l2_entry_meta() {
+        let (tenant, principal, role) = ids();
+        let cache = TieredCache::new(MemoryCache::new(8), MemoryCache::new(8));
+        let meta = CacheEntryMeta {
+            max_ttl: Some(Duration::from_millis(20)),
+            roles: Some(vec![role.clone()]),
+        };
+        block_on(cache.l2().set_effective_grants_with_meta(
+            &tenant,
+            &principal,
+            "a",
+            grants(role.clone()),
+            meta,
+        ));
+
+        assert!(block_on(cache.get_effective_grants(&tenant, &principal, "a")).is_some());
+        let (_, backfilled) = block_on(
+            cache
+                .l1()
+                .get_effective_grants_with_meta(&tenant, &principal, "a"),
+        )
+        .expect("backfilled");
+        let backfilled = backfilled.expect("meta");
+        assert_eq!(backfilled.roles, Some(vec![role]));
+        assert!(
+            backfilled
+                .max_ttl
+                .is_some_and(|ttl| ttl <= Duration::from_millis(20))
+        );
+
+        // 无关角色的失效不影响回填条目，条目在 L2 的存活时间上限到达后从 L1 过期。
+        let other = RoleId::parse("writer").expect("role");
+        block_on(cache.l1().invalidate_role(&tenant, &other));
+        assert!(block_on(cache.l1().get_effective_grants(&tenant, &principal, "a")).is_some());
+        std::thread::sleep(Duration::from_millis(30));
+        assert!(block_on(cache.l1().get_effective_grants(&tenant, &principal, "a")).is_none());
+    }
+
+    #[test]
+    fn tiered_cache_should_backfill_statuses_with_remaining_ttl() {
+        let (tenant, principal, _) = ids();
+        let l2 = MemoryCache::new(8).with_status_ttl(Duration::from_millis(20));
+        let l1 = MemoryCache::new(8).with_status_ttl(Duration::from_secs(60));
+        let cache = TieredCache::new(l1, l2);
+        block_on(
+            cache
+                .l2()
+                .set_tenant_status(&tenant, "", TenantStatus::Active),
+        );
+        block_on(cache.l2().set_membership_status(
+            &tenant,
+            &principal,
+            "",
+            MembershipStatus::Active,
+        ));
+
+        assert_eq!(
+            block_on(cache.get_tenant_status(&tenant, "")),
+            Some(TenantStatus::Active)
+        );
+        assert_eq!(
+            block_on(cache.get_membership_status(&tenant, &principal, "")),
+            Some(MembershipStatus::Active)
+        );
+        let (_, meta) =
+            block_on(cache.l1().get_tenant_status_with_meta(&tenant, "")).expect("backfilled");
+        assert!(
+            meta.and_then(|meta| meta.max_ttl)
+                .is_some_and(|ttl| ttl <= Duration::from_millis(20))
+        );
+
+        // 回填到 L1 的状态随 L2 条目一起过期，不会被 L1 延长。
+        std::thread::sleep(Duration::from_millis(30));
+        assert!(block_on(cache.l1().get_tenant_status(&tenant, "")).is_none());
+        assert!(block_on(cache.l1().get_membership_status(&tenant, &principal, "")).is_none());
+    }
+}
@@ -5,8 +5,8 @@ use futures::executor::block_on;
 use rs_tenant::{
     AccessDecision, AccessScope, AuthSubject, AuthorizationSource, DenyReason, EngineBuilder,
     Error, GrantScope, MembershipStatus, MemorySource, Permission, PrincipalId, RoleAssignment,
-    RoleId, ScopePath, ScopeQuery, ScopedAccessRequest, SourceError, TenantAccessRequest, TenantId,
-    TenantStatus,
+    RoleId, ScopePath, ScopePattern, ScopeQuery, ScopedAccessRequest, SourceError,
+    TenantAccessRequest, TenantId, TenantStatus,
 };
 
 /// 解析测试租户标识符。
@@ -164,6 +164,7 @@ fn tenant_standard_authorization_flow_should_return_scope_and_allow_target() {
         AccessScope::Paths {
             tenant: tenant.clone(),
             roots: vec![parse_path("agent/1")],
+            patterns: Vec::new(),
         }
     );
 
@@ -171,6 +172,7 @@ fn tenant_standard_authorization_flow_should_return_scope_and_allow_target() {
         subject,
         permission: parse_permission("invoice:read"),
         target: parse_path("agent/1/store/9"),
+        context: Default::default(),
     }))
     .expect("decision");
     assert_eq!(decision, AccessDecision::Allow);
@@ -189,6 +191,7 @@ fn tenant_deny_by_default_flow_should_explain_every_gate() {
     let explanation = block_on(engine.explain_tenant(TenantAccessRequest {
         subject: subject(&tenant, &principal),
         permission: required_permission.clone(),
+        context: Default::default(),
     }))
     .expect("explanation");
     assert_eq!(explanation.decision, AccessDecision::Deny);
@@ -208,6 +211,7 @@ fn tenant_deny_by_default_flow_should_explain_every_gate() {
     let explanation = block_on(engine.explain_tenant(TenantAccessRequest {
         subject: subject(&tenant, &principal),
         permission: required_permission.clone(),
+        context: Default::default(),
     }))
     .expect("explanation");
     assert_eq!(explanation.decision, AccessDecision::Deny);
@@ -221,6 +225,7 @@ fn tenant_deny_by_default_flow_should_explain_every_gate() {
     let explanation = block_on(engine.explain_tenant(TenantAccessRequest {
         subject: subject(&tenant, &principal),
         permission: required_permission.clone(),
+        context: Default::default(),
     }))
     .expect("explanation");
     assert_eq!(explanation.decision, AccessDecision::Deny);
@@ -242,6 +247,7 @@ fn tenant_deny_by_default_flow_should_explain_every_gate() {
     let explanation = block_on(engine.explain_tenant(TenantAccessRequest {
         subject: subject(&tenant, &principal),
         permission: required_permission.clone(),
+        context: Default::default(),
     }))
     .expect("explanation");
     assert_eq!(explanation.decision, AccessDecision::Deny);
@@ -268,6 +274,7 @@ fn tenant_deny_by_default_flow_should_explain_every_gate() {
         subject: subject(&tenant, &principal),
         permission: required_permission,
         target: parse_path("agent/2/store/9"),
+        context: Default::default(),
     }))
     .expect("explanation");
     assert_eq!(explanation.decision, AccessDecision::Deny);
@@ -296,6 +303,7 @@ fn role_hierarchy_flow_should_require_engine_switch() {
         subject: subject(&tenant, &principal),
         permission: parse_permission("invoice:read"),
         target: parse_path("agent/1/store/9"),
+        context: Default::default(),
     }))
     .expect("decision");
     assert_eq!(strict, AccessDecision::Deny);
@@ -308,11 +316,47 @@ fn role_hierarchy_flow_should_require_engine_switch() {
         subject: subject(&tenant, &principal),
         permission: parse_permission("invoice:read"),
         target: parse_path("agent/1/store/9"),
+        context: Default::default(),
     }))
     .expect("decision");
     assert_eq!(inherited, AccessDecision::Allow);
 }
 
+#[test]
+fn scope_pattern_flow_should_allow_matching_nodes_only() {
+    let tenant = parse_tenant("tenant_pattern_flow");
+    let principal = parse_principal("user_pattern_flow");
+    let role = parse_role("store_42_reader");
+    let source = active_tenant_source(&tenant, &principal);
+    source.add_role_assignment(
+        tenant.clone(),
+        principal.clone(),
+        role.clone(),
+        GrantScope::paths_with_patterns(
+            Vec::new(),
+            vec![ScopePattern::parse("agent/*/store/42/**").expect("scope pattern")],
+        )
+        .expect("grant scope"),
+    );
+    source.add_role_permission(tenant.clone(), role, parse_permission("invoice:read"));
+    let engine = EngineBuilder::new(source).build();
+    let check = |target: &str| {
+        block_on(engine.can_access_scope(ScopedAccessRequest {
+            subject: subject(&tenant, &principal),
+            permission: parse_permission("invoice:read"),
+            target: parse_path(target),
+            context: Default::default(),
+        }))
+        .expect("decision")
+    };
+
+    // 模式授权覆盖任意 agent 下的 store 42 子树，但不会覆盖同级 store。
+    assert_eq!(check("agent/1/store/42"), AccessDecision::Allow);
+    assert_eq!(check("agent/7/store/42/order/1"), AccessDecision::Allow);
+    assert_eq!(check("agent/7/store/43"), AccessDecision::Deny);
+    assert_eq!(check("agent/7"), AccessDecision::Deny);
+}
+
 #[test]
 fn tenant_source_error_flow_should_return_error_instead_of_deny() {
     let subject = subject(
@@ -322,6 +366,7 @@ fn tenant_source_error_flow_should_return_error_instead_of_deny() {
     let request = TenantAccessRequest {
         subject: subject.clone(),
         permission: parse_permission("invoice:read"),
+        context: Default::default(),
     };
 
     // 每个数据源读取点失败时，Engine 都必须 fail closed 并返回 Error::Source。
@@ -364,6 +409,7 @@ fn cache_invalidation_flow_should_refresh_changed_authorization() {
     let request = TenantAccessRequest {
         subject: subject(&tenant, &principal),
         permission: parse_permission("invoice:read"),
+        context: Default::default(),
     };
 
     // 第一次查询会把“没有权限”的有效授权写入缓存。
@@ -416,6 +462,7 @@ fn cache_invalidation_matrix_should_keep_scope_boundaries() {
     let request = |tenant: &TenantId, principal: &PrincipalId| TenantAccessRequest {
         subject: subject(tenant, principal),
         permission: parse_permission("invoice:read"),
+        context: Default::default(),
     };
 
     // 先缓存三个拒绝结果，后续通过失效 API 验证影响范围。
@@ -496,6 +543,7 @@ fn cache_configuration_flow_should_isolate_signatures_and_support_disabled_cache
     let request = TenantAccessRequest {
         subject: subject(&tenant, &principal),
         permission: parse_permission("invoice:read"),
+        context: Default::default(),
     };
 
     // 不同 EngineConfig 的缓存签名必须隔离，wildcard 关闭的拒绝不能污染开启后的允许。
@@ -522,6 +570,7 @@ fn cache_configuration_flow_should_isolate_signatures_and_support_disabled_cache
     let request = TenantAccessRequest {
         subject: subject(&tenant, &principal),
         permission: parse_permission("order:read"),
+        context: Default::default(),
     };
     assert_eq!(
         block_on(disabled_engine.can_tenant(request.clone())).expect("decision"),
@@ -534,6 +583,94 @@ fn cache_configuration_flow_should_isolate_signatures_and_support_disabled_cache
     );
 }
 
+#[cfg(feature = "memory-cache")]
+#[test]
+fn cache_signature_should_isolate_condition_evaluation() {
+    use rs_tenant::{AttributeValue, Condition, DenyReason, MemoryCache, RequestContext};
+
+    let tenant = parse_tenant("tenant_cache_condition");
+    let principal = parse_principal("user_cache_condition");
+    let role = parse_role("approver");
+    let source = active_tenant_source(&tenant, &principal);
+    source.add_role_assignment(
+        tenant.clone(),
+        principal.clone(),
+        role.clone(),
+        GrantScope::tenant(),
+    );
+    source.add_role_permission(
+        tenant.clone(),
+        role.clone(),
+        parse_permission("invoice:approve"),
+    );
+    source.add_permission_condition(
+        tenant.clone(),
+        role,
+        parse_permission("invoice:approve"),
+        Condition::parse("amount < 10000").expect("condition"),
+    );
+    let cache = MemoryCache::new(16);
+    let plain_engine = EngineBuilder::new(source.clone())
+        .cache(cache.clone())
+        .build();
+    let condition_engine = EngineBuilder::new(source)
+        .enable_conditions(true)
+        .cache(cache)
+        .build();
+    let request = TenantAccessRequest {
+        subject: subject(&tenant, &principal),
+        permission: parse_permission("invoice:approve"),
+        context: RequestContext::from([("amount".to_string(), AttributeValue::Int(20_000))]),
+    };
+
+    // 未启用条件的引擎跳过了有条件的授权并缓存结果，启用条件的引擎不能复用它。
+    assert_eq!(
+        block_on(plain_engine.can_tenant(request.clone())).expect("decision"),
+        AccessDecision::Deny
+    );
+    let explanation = block_on(condition_engine.explain_tenant(request)).expect("explanation");
+    assert_eq!(explanation.decision, AccessDecision::Deny);
+    assert_eq!(explanation.reason, Some(DenyReason::ConditionFailed));
+}
+
+#[cfg(feature = "memory-cache")]
+#[test]
+fn cache_entry_should_not_outlive_role_assignment_expiry() {
+    use rs_tenant::MemoryCache;
+    use std::time::{Duration, SystemTime};
+
+    let tenant = parse_tenant("tenant_cache_expiry");
+    let principal = parse_principal("user_cache_expiry");
+    let role = parse_role("on_call");
+    let source = active_tenant_source(&tenant, &principal);
+    source.insert_role_assignment(
+        tenant.clone(),
+        principal.clone(),
+        RoleAssignment::new(role.clone(), GrantScope::tenant())
+            .with_expires_at(SystemTime::now() + Duration::from_millis(50)),
+    );
+    source.add_role_permission(tenant.clone(), role, parse_permission("invoice:read"));
+    let engine = EngineBuilder::new(source)
+        .cache(MemoryCache::new(16))
+        .build();
+    let request = TenantAccessRequest {
+        subject: subject(&tenant, &principal),
+        permission: parse_permission("invoice:read"),
+        context: Default::default(),
+    };
+
+    // 缓存没有配置 TTL，但条目仍然必须随临时角色分配一起过期。
+    assert_eq!(
+        block_on(engine.can_tenant(request.clone())).expect("decision"),
+        AccessDecision::Allow
+    );
+    std::thread::sleep(Duration::from_millis(80));
+    assert_eq!(
+        block_on(engine.can_tenant(request)).expect("decision"),
+        AccessDecision::Deny
+    );
+}
+
 #[cfg(feature = "memory-cache")]
 #[test]
 #[ignore = "并发压力测试；需要手动运行以避免拖慢默认测试"]
@@ -573,6 +710,7 @@ fn cache_concurrent_access_and_invalidation_should_not_deadlock_or_panic() {
                 let decision = block_on(engine.can_tenant(TenantAccessRequest {
                     subject: subject(&tenant, &principal),
                     permission: parse_permission("invoice:read"),
+                    context: Default::default(),
                 }))
                 .expect("decision");
                 assert_eq!(decision, AccessDecision::Allow);
@@ -621,11 +759,13 @@ mod serde_contracts {
         let tenant_request = TenantAccessRequest {
             subject: auth_subject.clone(),
             permission: parse_permission("invoice:update"),
+            context: Default::default(),
         };
         let scoped_request = ScopedAccessRequest {
             subject: auth_subject,
             permission: parse_permission("invoice:read"),
             target: parse_path("agent/1/store/9"),
+            context: Default::default(),
         };
 
         // 公共租户 DTO 的 JSON roundtrip 必须保持强类型字段和值对象校验。
@@ -149,6 +149,7 @@ fn perf_can_tenant_and_scoped_access() {
             subject: subject.clone(),
             permission: permission.clone(),
             target: scope.clone(),
+            context: Default::default(),
         }))
         .unwrap();
         black_box(result);
@@ -170,6 +171,7 @@ fn perf_can_tenant_and_scoped_access() {
     let warm = block_on(engine.can_tenant(TenantAccessRequest {
         subject: subject.clone(),
         permission: permission.clone(),
+        context: Default::default(),
     }))
     .unwrap();
     assert_eq!(warm, AccessDecision::Allow);
@@ -177,6 +179,7 @@ fn perf_can_tenant_and_scoped_access() {
         let result = block_on(engine.can_tenant(TenantAccessRequest {
             subject: subject.clone(),
             permission: permission.clone(),
+            context: Default::default(),
         }))
         .unwrap();
         black_box(result);
@@ -187,6 +190,7 @@ fn perf_can_tenant_and_scoped_access() {
             subject: subject.clone(),
             permission: permission.clone(),
             target: scope.clone(),
+            context: Default::default(),
         }))
         .unwrap();
         black_box(result);
@@ -206,6 +210,7 @@ fn perf_can_tenant_and_scoped_access() {
                 subject: subject.clone(),
                 permission: permission.clone(),
                 target,
+                context: Default::default(),
             }))
             .unwrap();
             black_box(result);
@@ -231,6 +236,7 @@ fn perf_can_tenant_and_scoped_access() {
         subject: subject.clone(),
         permission: permission.clone(),
         target: scope.clone(),
+        context: Default::default(),
     }))
     .unwrap();
 
@@ -248,6 +254,7 @@ fn perf_can_tenant_and_scoped_access() {
                     subject: subject.clone(),
                     permission: permission.clone(),
                     target: scope.clone(),
+                    context: Default::default(),
                 }))
                 .unwrap();
                 black_box(result);